@@ -0,0 +1,77 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::fmt::Write;
+use winprint::ticket::document::{
+    reader::ParsableXmlDocument, stream::FeatureReader, OwnedName, PrintCapabilitiesDocument,
+    NS_PSK,
+};
+
+const PRINT_CAPABILITIES: &str = include_str!("../test_data/print_capabilities.xml");
+
+/// Grow the real capabilities to the size emitted by production MFP drivers,
+/// with hundreds of media sizes and a big base64 DEVMODE snapshot.
+fn large_print_capabilities() -> String {
+    let mut extra = String::new();
+    extra.push_str(r#"<psf:Feature name="ns0000:CustomMediaSize">"#);
+    for i in 0..400 {
+        write!(
+            extra,
+            r#"<psf:Option name="ns0000:Custom{i}" constrained="psk:None">
+                <psf:ScoredProperty name="psk:MediaSizeWidth"><psf:Value xsi:type="xsd:integer">{}</psf:Value></psf:ScoredProperty>
+                <psf:ScoredProperty name="psk:MediaSizeHeight"><psf:Value xsi:type="xsd:integer">{}</psf:Value></psf:ScoredProperty>
+                <psf:Property name="psk:DisplayName"><psf:Value xsi:type="xsd:string">Custom {i}</psf:Value></psf:Property>
+            </psf:Option>"#,
+            100000 + i * 100,
+            150000 + i * 100,
+        )
+        .unwrap();
+    }
+    extra.push_str("</psf:Feature>");
+    extra.push_str(
+        r#"<psf:Property name="ns0000:DevModeSnapshot"><psf:Value xsi:type="xsd:base64Binary">"#,
+    );
+    for _ in 0..(2 << 20) / 76 {
+        extra.push_str(
+            "QUJDREVGR0hJSktMTU5PUFFSU1RVVldYWVphYmNkZWZnaGlqa2xtbm9wcXJzdHV2d3h5ejAxMjM0\n",
+        );
+    }
+    extra.push_str("</psf:Value></psf:Property>");
+    PRINT_CAPABILITIES.replace(
+        "</psf:PrintCapabilities>",
+        &format!("{}</psf:PrintCapabilities>", extra),
+    )
+}
+
+fn bench_print_capabilities(c: &mut Criterion) {
+    let inputs = [
+        ("real", PRINT_CAPABILITIES.to_string()),
+        ("large", large_print_capabilities()),
+    ];
+    let page_media_size = OwnedName::qualified("PageMediaSize", NS_PSK, Some("psk"));
+
+    let mut group = c.benchmark_group("print_capabilities");
+    for (name, xml) in &inputs {
+        group.throughput(Throughput::Bytes(xml.len() as u64));
+        group.bench_with_input(BenchmarkId::new("document", name), xml, |b, xml| {
+            b.iter(|| PrintCapabilitiesDocument::parse_from_bytes(black_box(xml)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("stream_all", name), xml, |b, xml| {
+            b.iter(|| {
+                FeatureReader::from_bytes(black_box(xml.as_bytes()))
+                    .map(Result::unwrap)
+                    .count()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("stream_one", name), xml, |b, xml| {
+            b.iter(|| {
+                FeatureReader::from_bytes(black_box(xml.as_bytes()))
+                    .only([page_media_size.clone()])
+                    .map(Result::unwrap)
+                    .count()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_print_capabilities);
+criterion_main!(benches);
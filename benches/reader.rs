@@ -0,0 +1,43 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use winprint::ticket::document::{
+    reader::ParsableXmlDocument, PrintCapabilitiesDocument, PrintTicketDocument,
+};
+
+const PRINT_TICKET: &[u8] = include_bytes!("../test_data/print_ticket.xml");
+const PRINT_CAPABILITIES: &[u8] = include_bytes!("../test_data/print_capabilities.xml");
+
+fn bench_reader(c: &mut Criterion) {
+    let mut group = c.benchmark_group("reader");
+
+    group.throughput(Throughput::Bytes(PRINT_TICKET.len() as u64));
+    group.bench_function(BenchmarkId::new("event_reader", "print_ticket"), |b| {
+        b.iter(|| {
+            let mut reader = xml::EventReader::new(black_box(PRINT_TICKET));
+            PrintTicketDocument::parse(&mut reader).unwrap()
+        })
+    });
+    group.bench_function(BenchmarkId::new("bytes", "print_ticket"), |b| {
+        b.iter(|| PrintTicketDocument::parse_from_bytes(black_box(PRINT_TICKET)).unwrap())
+    });
+
+    group.throughput(Throughput::Bytes(PRINT_CAPABILITIES.len() as u64));
+    group.bench_function(
+        BenchmarkId::new("event_reader", "print_capabilities"),
+        |b| {
+            b.iter(|| {
+                let mut reader = xml::EventReader::new(black_box(PRINT_CAPABILITIES));
+                PrintCapabilitiesDocument::parse(&mut reader).unwrap()
+            })
+        },
+    );
+    group.bench_function(BenchmarkId::new("bytes", "print_capabilities"), |b| {
+        b.iter(|| {
+            PrintCapabilitiesDocument::parse_from_bytes(black_box(PRINT_CAPABILITIES)).unwrap()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_reader);
+criterion_main!(benches);
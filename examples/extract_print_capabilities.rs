@@ -1,7 +1,10 @@
+#[cfg(windows)]
 use std::io::Write;
 
+#[cfg(windows)]
 use winprint::{printer::PrinterDevice, ticket::PrintCapabilities};
 
+#[cfg(windows)]
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     std::io::stdout().write_all(b"Extract print capabilities to current directory? (Y/n): ")?;
     std::io::stdout().flush()?;
@@ -18,3 +21,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
     Ok(())
 }
+
+#[cfg(not(windows))]
+fn main() {
+    eprintln!("This example requires a Windows printer device.");
+}
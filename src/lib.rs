@@ -1,4 +1,3 @@
-#![cfg(windows)]
 #![warn(missing_docs)]
 
 //! A crate for printing to a Windows printer device using Windows API.
@@ -13,6 +12,7 @@
 //! First, get all printer devices via `PrinterDevice::all()` and filter for the device you want to use.
 //!
 //! ```rust
+//! # #[cfg(windows)] {
 //! use winprint::printer::PrinterDevice;
 //!
 //! fn get_my_device() -> PrinterDevice {
@@ -22,6 +22,7 @@
 //!         .find(|x| x.name() == "My Printer")
 //!         .expect("My Printer not found")
 //! }
+//! # }
 //! ```
 //!
 //! Then, create a printer and send a file to it. Currently, there are two kinds of printers available:
@@ -32,6 +33,7 @@
 //! not meaning the printer device.
 //!
 //! ```rust
+//! # #[cfg(windows)] {
 //! use std::path::Path;
 //! use winprint::printer::FilePrinter;
 //! use winprint::printer::PrinterDevice;
@@ -44,6 +46,7 @@
 //! # let path_buf = Path::new(env!("CARGO_MANIFEST_DIR")).join("test_data/test_document.xps");
 //! # let path = path_buf.as_path();
 //! xps.print(path, Default::default()).unwrap();
+//! # }
 //! ```
 //!
 //! ## Specify the printing preferences
@@ -62,6 +65,7 @@
 //! - Print the file with the print ticket.
 //!
 //! ```rust
+//! # #[cfg(windows)] {
 //! use std::path::Path;
 //! use winprint::printer::FilePrinter;
 //! use winprint::printer::PrinterDevice;
@@ -87,20 +91,31 @@
 //! # let path_buf = Path::new(env!("CARGO_MANIFEST_DIR")).join("test_data/test_document.xps");
 //! # let path = path_buf.as_path();
 //! xps.print(path, ticket).unwrap();
+//! # }
 //! ```
 //!
 //! [Print Schema Specification]: https://learn.microsoft.com/en-us/windows/win32/printdocs/printschema
 //!
+//! # Platform Support
+//! Printing itself is only available on Windows. The Print Schema layer in [`ticket`]
+//! (the document model, its reader and writer, and the typed option packs) is pure Rust
+//! and builds on every platform, so print tickets can be generated and inspected anywhere.
+//! Only [`printer`], `PrintTicketBuilder` and the `fetch*` functions require Windows.
+//!
 //! # Features
 //! - `pdfium`: Enable PDFium support for printing PDF files.
 
+#[cfg(windows)]
 mod bindings;
 /// Provides a way to print various types of data to a printer device.
+#[cfg(windows)]
 pub mod printer;
 /// Utilities for testing
+#[cfg(windows)]
 pub mod test_utils;
 /// Provides a way to specify the printing preferences.
 pub mod ticket;
+#[cfg(windows)]
 mod utils;
 #[cfg(test)]
 mod tests {
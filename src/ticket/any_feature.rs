@@ -0,0 +1,292 @@
+use super::{
+    define_predefined_name,
+    document::{
+        ParameterInit, PrintFeature, PrintFeatureOption, PrintTicketDocument, WithProperties,
+        NS_PSF, NS_PSK,
+    },
+    PredefinedName, PrintCapabilities, PrintTicket,
+};
+use strum::{EnumString, IntoStaticStr, VariantArray};
+use xml::name::OwnedName;
+
+define_predefined_name! {
+    namespace = NS_PSK;
+    #[derive(EnumString, IntoStaticStr, VariantArray, Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+    /// Represents how many options of a feature can be selected, as the `psf:SelectionType` property.
+    pub enum SelectionType {
+        /// Exactly one option is selected. This is assumed if the feature does not specify it.
+        #[default]
+        PickOne,
+        /// More than one option can be selected at the same time.
+        PickMany,
+    }
+}
+
+impl SelectionType {
+    /// Get the selection type of the given feature.
+    /// If the feature does not specify a known selection type, [`SelectionType::PickOne`] is returned.
+    pub fn of(feature: &PrintFeature) -> Self {
+        feature
+            .get_property("SelectionType", Some(NS_PSF))
+            .and_then(|x| x.value.as_ref())
+            .and_then(|x| x.qualified_name())
+            .and_then(Self::from_name)
+            .unwrap_or_default()
+    }
+}
+
+fn display_name(properties: &impl WithProperties) -> Option<&str> {
+    properties
+        .get_property("DisplayName", Some(NS_PSK))
+        .and_then(|x| x.value.as_ref())
+        .and_then(|x| x.string())
+}
+
+#[derive(Clone, Debug)]
+/// Represents any feature of print capabilities, including the ones without a typed [`FeatureOptionPack`](super::FeatureOptionPack).
+pub struct AnyFeature<'a> {
+    capabilities: &'a PrintCapabilities,
+    path: Vec<OwnedName>,
+    feature: &'a PrintFeature,
+}
+
+impl<'a> AnyFeature<'a> {
+    /// List the top-level features defined in the capabilities.
+    pub fn list(capabilities: &'a PrintCapabilities) -> impl Iterator<Item = Self> + 'a {
+        capabilities
+            .document
+            .features
+            .iter()
+            .map(move |feature| Self {
+                capabilities,
+                path: vec![feature.name.clone()],
+                feature,
+            })
+    }
+
+    /// List all features defined in the capabilities, each followed by its sub-features.
+    pub fn list_all(capabilities: &'a PrintCapabilities) -> impl Iterator<Item = Self> + 'a {
+        let mut result = vec![];
+        for feature in Self::list(capabilities) {
+            feature.collect_into(&mut result);
+        }
+        result.into_iter()
+    }
+
+    fn collect_into(self, result: &mut Vec<Self>) {
+        let features = self.features().collect::<Vec<_>>();
+        result.push(self);
+        for feature in features {
+            feature.collect_into(result);
+        }
+    }
+
+    /// Get the name of the feature.
+    pub fn name(&self) -> &OwnedName {
+        &self.feature.name
+    }
+
+    /// Get the names of the features from the top-level one to this feature.
+    pub fn path(&self) -> &[OwnedName] {
+        &self.path
+    }
+
+    /// Get the DOM of the feature.
+    pub fn feature(&self) -> &'a PrintFeature {
+        self.feature
+    }
+
+    /// Get display name of the feature.
+    pub fn display_name(&self) -> Option<&'a str> {
+        display_name(self.feature)
+    }
+
+    /// Get the selection type of the feature.
+    pub fn selection_type(&self) -> SelectionType {
+        SelectionType::of(self.feature)
+    }
+
+    /// List all options of the feature, with the default values of the parameters they depend on.
+    pub fn options(&self) -> impl Iterator<Item = AnyOption> + '_ {
+        self.feature.options.iter().map(|option| {
+            let parameters = self
+                .capabilities
+                .default_parameters_for(option.parameters_dependent().as_slice())
+                .collect();
+            AnyOption {
+                path: self.path.clone(),
+                option: option.clone(),
+                parameters,
+            }
+        })
+    }
+
+    /// List the sub-features of the feature.
+    pub fn features(&self) -> impl Iterator<Item = AnyFeature<'a>> + '_ {
+        self.feature.features.iter().map(|feature| {
+            let mut path = self.path.clone();
+            path.push(feature.name.clone());
+            AnyFeature {
+                capabilities: self.capabilities,
+                path,
+                feature,
+            }
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Represents an option of [`AnyFeature`], with the parameters that is used by the option.
+pub struct AnyOption {
+    path: Vec<OwnedName>,
+    option: PrintFeatureOption,
+    parameters: Vec<ParameterInit>,
+}
+
+impl AnyOption {
+    /// Get the name of the feature of the option.
+    pub fn feature_name(&self) -> &OwnedName {
+        self.path
+            .last()
+            .expect("the path of a feature is never empty")
+    }
+
+    /// Get the names of the features from the top-level one to the feature of the option.
+    pub fn feature_path(&self) -> &[OwnedName] {
+        &self.path
+    }
+
+    /// Get the DOM of the option.
+    pub fn option(&self) -> &PrintFeatureOption {
+        &self.option
+    }
+
+    /// Get the mutable reference to the DOM of the option.
+    pub fn option_mut(&mut self) -> &mut PrintFeatureOption {
+        &mut self.option
+    }
+
+    /// Get the parameters that is used by the option.
+    pub fn parameters(&self) -> &[ParameterInit] {
+        &self.parameters
+    }
+
+    /// Get the mutable reference to the parameters that is used by the option.
+    pub fn parameters_mut(&mut self) -> &mut Vec<ParameterInit> {
+        &mut self.parameters
+    }
+
+    /// Get the names of the parameters that the option depends on.
+    pub fn parameters_dependent(&self) -> Vec<OwnedName> {
+        self.option.parameters_dependent()
+    }
+
+    /// Get display name of the option.
+    pub fn display_name(&self) -> Option<&str> {
+        display_name(&self.option)
+    }
+
+    /// Convert the option into the option and the parameters.
+    pub fn into_option_with_parameters(self) -> (PrintFeatureOption, Vec<ParameterInit>) {
+        (self.option, self.parameters)
+    }
+}
+
+impl From<AnyOption> for PrintTicket {
+    fn from(value: AnyOption) -> Self {
+        let mut path = value.path;
+        let mut feature = PrintFeature::new(
+            path.pop().expect("the path of a feature is never empty"),
+            vec![],
+            vec![value.option],
+            vec![],
+        );
+        while let Some(name) = path.pop() {
+            feature = PrintFeature::new(name, vec![], vec![], vec![feature]);
+        }
+        PrintTicketDocument::new(vec![], value.parameters, vec![feature]).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AnyFeature, SelectionType};
+    use crate::ticket::test_fixtures::{sample_capabilities, sample_device_capabilities_document};
+    use crate::ticket::{
+        document::{reader::ParsableXmlDocument, PrintTicketDocument},
+        PrintCapabilities, PrintTicket,
+    };
+
+    #[test]
+    fn list_nested_features() {
+        let capabilities = sample_capabilities();
+        let nup = capabilities
+            .all_features()
+            .find(|x| x.name().local_name == "JobNUpAllDocumentsContiguously")
+            .unwrap();
+        assert_eq!(nup.selection_type(), SelectionType::PickOne);
+        let nested = nup
+            .features()
+            .map(|x| x.name().local_name.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(nested, vec!["PresentationDirection", "Borders"]);
+
+        let borders = capabilities
+            .all_features()
+            .find(|x| x.name().local_name == "Borders")
+            .unwrap();
+        assert_eq!(
+            borders
+                .path()
+                .iter()
+                .map(|x| x.local_name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["JobNUpAllDocumentsContiguously", "Borders"]
+        );
+        assert!(capabilities.features().count() < capabilities.all_features().count());
+    }
+
+    #[test]
+    fn nested_option_into_print_ticket() {
+        let capabilities = sample_capabilities();
+        let borders = capabilities
+            .all_features()
+            .find(|x| x.name().local_name == "Borders")
+            .unwrap();
+        let on = borders
+            .options()
+            .find(|x| x.option().name.as_ref().unwrap().local_name == "On")
+            .unwrap();
+        let ticket = PrintTicket::from(on);
+        let document = PrintTicketDocument::parse_from_bytes(ticket.xml).unwrap();
+        let nup = &document.features[0];
+        assert_eq!(nup.name.local_name, "JobNUpAllDocumentsContiguously");
+        assert!(nup.options.is_empty());
+        let option = nup.features[0].options[0].name.as_ref().unwrap();
+        assert_eq!(option.local_name, "On");
+    }
+
+    #[test]
+    fn option_with_parameters() {
+        let capabilities = PrintCapabilities {
+            document: sample_device_capabilities_document().to_print_capabilities(),
+        };
+        let media_size = AnyFeature::list(&capabilities)
+            .find(|x| x.name().local_name == "PageMediaSize")
+            .unwrap();
+        assert_eq!(media_size.display_name(), Some("Paper Size"));
+        let custom = media_size
+            .options()
+            .find(|x| x.option().name.as_ref().unwrap().local_name == "CustomMediaSize")
+            .unwrap();
+        assert_eq!(custom.parameters_dependent().len(), 2);
+        assert_eq!(
+            custom
+                .parameters()
+                .iter()
+                .map(|x| x.value.integer())
+                .collect::<Vec<_>>(),
+            vec![Some(215900), Some(279400)]
+        );
+    }
+}
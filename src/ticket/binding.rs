@@ -0,0 +1,152 @@
+use super::{
+    define_feature_option_pack,
+    document::{ParameterInit, PrintFeatureOption, PropertyValue, WithScoredProperties, NS_PSK},
+    set_referenced_parameter, FeatureKeyword, Keyword, PredefinedBinding, PrintCapabilities,
+    PropertyKeyword, SetParameterError,
+};
+
+define_feature_option_pack!(
+    FeatureKeyword::JobBindAllDocuments.name(),
+    JobBinding,
+    PredefinedBinding
+);
+define_feature_option_pack!(
+    FeatureKeyword::DocumentBinding.name(),
+    DocumentBinding,
+    PredefinedBinding
+);
+
+fn gutter(option: &PrintFeatureOption, parameters: &[ParameterInit]) -> Option<u32> {
+    option
+        .get_scored_property("BindingGutter", Some(NS_PSK))
+        .and_then(|x| x.value_with(parameters))
+        .and_then(|x| x.integer())
+        .and_then(|x| u32::try_from(x).ok())
+}
+
+macro_rules! impl_binding {
+    ($name:ident) => {
+        impl $name {
+            /// Get the width of the binding gutter in microns,
+            /// from the `psk:BindingGutter` scored property or the parameter it references.
+            pub fn gutter(&self) -> Option<u32> {
+                gutter(&self.option, &self.parameters)
+            }
+
+            /// Set the width of the binding gutter in microns, through the parameter referenced by
+            /// the `psk:BindingGutter` scored property,
+            /// after checking the value against the parameter definition of the capabilities.
+            pub fn set_gutter(
+                &mut self,
+                capabilities: &PrintCapabilities,
+                microns: u32,
+            ) -> Result<(), SetParameterError> {
+                let value = i32::try_from(microns).map_or_else(
+                    |_| PropertyValue::BigInteger(microns.to_string()),
+                    PropertyValue::Integer,
+                );
+                set_referenced_parameter(
+                    capabilities,
+                    &self.option,
+                    &mut self.parameters,
+                    PropertyKeyword::BindingGutter,
+                    value,
+                )
+            }
+        }
+    };
+}
+
+impl_binding!(JobBinding);
+impl_binding!(DocumentBinding);
+
+#[cfg(test)]
+mod tests {
+    use crate::ticket::test_fixtures::capabilities_with;
+    use crate::ticket::{
+        document::{reader::ParsableXmlDocument, PrintTicketDocument},
+        FeatureOptionPack, FeatureOptionPackWithPredefined, ParameterKeyword, PredefinedBinding,
+        PredefinedName, PrintCapabilities, PrintTicket, SetParameterError,
+    };
+
+    fn capabilities() -> PrintCapabilities {
+        capabilities_with(
+            r#"    <psf:ParameterDef name="psk:JobBindAllDocumentsGutter">
+        <psf:Property name="psf:DataType">
+            <psf:Value xsi:type="xsd:QName">xsd:integer</psf:Value>
+        </psf:Property>
+        <psf:Property name="psf:DefaultValue">
+            <psf:Value xsi:type="xsd:integer">5000</psf:Value>
+        </psf:Property>
+        <psf:Property name="psf:MinValue">
+            <psf:Value xsi:type="xsd:integer">0</psf:Value>
+        </psf:Property>
+        <psf:Property name="psf:MaxValue">
+            <psf:Value xsi:type="xsd:integer">50000</psf:Value>
+        </psf:Property>
+    </psf:ParameterDef>
+    <psf:Feature name="psk:JobBindAllDocuments">
+        <psf:Option name="psk:None" />
+        <psf:Option name="psk:BindLeft">
+            <psf:ScoredProperty name="psk:BindingGutter">
+                <psf:ParameterRef name="psk:JobBindAllDocumentsGutter" />
+            </psf:ScoredProperty>
+        </psf:Option>
+        <psf:Option name="psk:Booklet">
+            <psf:ScoredProperty name="psk:BindingGutter">
+                <psf:Value xsi:type="xsd:integer">0</psf:Value>
+            </psf:ScoredProperty>
+        </psf:Option>
+    </psf:Feature>"#,
+        )
+    }
+
+    #[test]
+    fn binding_gutter() {
+        let capabilities = capabilities();
+        let bindings = capabilities.job_bindings().collect::<Vec<_>>();
+        assert_eq!(
+            bindings
+                .iter()
+                .map(|x| (x.as_predefined_name(), x.gutter()))
+                .collect::<Vec<_>>(),
+            vec![
+                (Some(PredefinedBinding::None), None),
+                (Some(PredefinedBinding::BindLeft), Some(5000)),
+                (Some(PredefinedBinding::Booklet), Some(0)),
+            ]
+        );
+        assert_eq!(capabilities.document_bindings().count(), 0);
+
+        let mut booklet = bindings[2].clone();
+        assert!(matches!(
+            booklet.set_gutter(&capabilities, 1000),
+            Err(SetParameterError::NotParameterized(_))
+        ));
+        assert_eq!(booklet.gutter(), Some(0));
+
+        let mut bind_left = bindings[1].clone();
+        bind_left.parameters_mut()[0].name.prefix = Some("other".to_string());
+        bind_left.set_gutter(&capabilities, 12000).unwrap();
+        assert_eq!(bind_left.parameters().len(), 1);
+        assert_eq!(bind_left.gutter(), Some(12000));
+        assert!(matches!(
+            bind_left.set_gutter(&capabilities, 60000),
+            Err(SetParameterError::InvalidValue(_))
+        ));
+        assert!(matches!(
+            bind_left.set_gutter(&capabilities, u32::MAX),
+            Err(SetParameterError::InvalidValue(_))
+        ));
+        assert_eq!(bind_left.gutter(), Some(12000));
+        let ticket = PrintTicket::from(bind_left);
+        let document = PrintTicketDocument::parse_from_bytes(ticket.xml).unwrap();
+        let gutter = &document.parameter_inits[0];
+        assert_eq!(
+            ParameterKeyword::from_name(&gutter.name),
+            Some(ParameterKeyword::JobBindAllDocumentsGutter)
+        );
+        assert_eq!(document.parameter_inits.len(), 1);
+        assert_eq!(gutter.value.integer(), Some(12000));
+    }
+}
@@ -52,6 +52,8 @@ impl PrintTicketBuilder {
     }
 
     /// Merge the given print ticket into the current print ticket.
+    ///
+    /// See [`merge_and_validate`](super::merge_and_validate) for merging without a printer device.
     pub fn merge(&mut self, delta: impl Into<PrintTicket>) -> Result<(), PrintTicketBuilderError> {
         unsafe {
             let base = SHCreateMemStream(Some(self.xml.as_ref()))
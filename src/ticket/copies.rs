@@ -1,8 +1,7 @@
 use super::{
-    document::{ParameterInit, PrintTicketDocument, PropertyValue, NS_PSK},
-    PrintTicket,
+    document::{ParameterInit, PrintTicketDocument, PropertyValue},
+    Keyword, ParameterKeyword, PrintTicket,
 };
-use xml::name::OwnedName;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(transparent)]
@@ -19,19 +18,19 @@ pub struct Copies(pub u16);
 
 impl From<Copies> for PrintTicket {
     fn from(copies: Copies) -> Self {
-        PrintTicketDocument {
-            properties: vec![],
-            parameter_inits: vec![ParameterInit {
-                name: OwnedName::qualified("JobCopiesAllDocuments", NS_PSK, Some("psk")),
-                value: PropertyValue::Integer(copies.0 as i32),
-            }],
-            features: vec![],
-        }
+        PrintTicketDocument::new(
+            vec![],
+            vec![ParameterInit::new(
+                ParameterKeyword::JobCopiesAllDocuments.name(),
+                PropertyValue::Integer(copies.0 as i32),
+            )],
+            vec![],
+        )
         .into()
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, windows))]
 mod tests {
     use crate::{
         test_utils::null_device,
@@ -0,0 +1,374 @@
+use super::{
+    visit::{VisitMut, VisitableMut},
+    xsd::normalize_decimal,
+    PrintCapabilitiesDocument, PrintFeature, PrintSchemaDocument, PrintTicketDocument, Property,
+    PropertyValue, ScoredProperty, XmlExtra, NS_PSF, NS_PSK, NS_XSD, NS_XSI,
+};
+use std::{
+    collections::BTreeMap,
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+use xml::name::OwnedName;
+
+/// A trait for Print Schema documents which have a canonical form.
+///
+/// The canonical form of a document:
+/// - orders features, options, properties, scored-properties and parameters by namespace URI and local name,
+/// - uses `psf`, `psk`, `xsd` and `xsi` as prefixes for the standard namespaces,
+///   and `ns0000`, `ns0001`, ... for other namespaces, in the order of their URIs,
+/// - collapses runs of whitespace in string values into single spaces, and trims them,
+/// - writes decimal values without redundant signs and zeros,
+/// - drops the XML content kept by the lossless mode, such as comments and driver private elements.
+///
+/// Two documents are semantically equal if their canonical forms are equal.
+pub trait Canonicalize: Clone + Eq + Hash {
+    /// Convert the document into its canonical form in place.
+    fn canonicalize(&mut self);
+
+    /// Replace the prefixes with the canonical ones in place, without any other change.
+    ///
+    /// Prefixes inside attribute values of the content kept by the lossless mode are not changed.
+    fn assign_standard_prefixes(&mut self);
+
+    /// Get the canonical form of the document.
+    fn to_canonical(&self) -> Self {
+        let mut result = self.clone();
+        result.canonicalize();
+        result
+    }
+
+    /// Determine if the documents are semantically equal.
+    fn semantic_eq(&self, other: &Self) -> bool {
+        self.to_canonical() == other.to_canonical()
+    }
+
+    /// Feed the canonical form of the document into the given hasher.
+    /// Semantically equal documents have the same hash.
+    fn semantic_hash<H: Hasher>(&self, state: &mut H) {
+        self.to_canonical().hash(state)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+/// A document in canonical form, whose [`PartialEq`] and [`Hash`] are semantic.
+///
+/// This is useful to de-duplicate documents, or to use them as keys of hash maps.
+///
+/// # Example
+/// ```rust
+/// use std::collections::HashSet;
+/// use winprint::ticket::document::{
+///     canonical::Canonical, reader::ParsableXmlDocument, PrintTicketDocument,
+/// };
+///
+/// let a = PrintTicketDocument::parse_from_bytes(
+///     r#"<psf:PrintTicket xmlns:psf="http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework" xmlns:psk="http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords" version="1">
+///     <psf:Feature name="psk:PageMediaSize"><psf:Option name="psk:ISOA4" /></psf:Feature>
+/// </psf:PrintTicket>"#,
+/// )
+/// .unwrap();
+/// let b = PrintTicketDocument::parse_from_bytes(
+///     r#"<psf:PrintTicket xmlns:psf="http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework" xmlns:ns0000="http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords" version="1">
+///     <psf:Feature name="ns0000:PageMediaSize"><psf:Option name="ns0000:ISOA4" /></psf:Feature>
+/// </psf:PrintTicket>"#,
+/// )
+/// .unwrap();
+///
+/// let mut cache = HashSet::new();
+/// cache.insert(Canonical::new(a));
+/// cache.insert(Canonical::new(b));
+/// assert_eq!(cache.len(), 1);
+/// ```
+pub struct Canonical<T: Canonicalize>(T);
+
+impl<T: Canonicalize> Canonical<T> {
+    /// Convert the document into its canonical form.
+    pub fn new(mut document: T) -> Self {
+        document.canonicalize();
+        Self(document)
+    }
+
+    /// Into the document in canonical form.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Canonicalize> Deref for Canonical<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Canonicalize> From<T> for Canonical<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl Canonicalize for PrintTicketDocument {
+    fn canonicalize(&mut self) {
+        self.extra = XmlExtra::default();
+        canonicalize_properties(&mut self.properties);
+        for parameter_init in &mut self.parameter_inits {
+            parameter_init.extra = XmlExtra::default();
+            canonicalize_value(&mut parameter_init.value);
+        }
+        sort_by_name(&mut self.parameter_inits, |x| Some(&x.name));
+        canonicalize_features(&mut self.features);
+        self.assign_standard_prefixes();
+    }
+
+    fn assign_standard_prefixes(&mut self) {
+        assign_standard_prefixes(self);
+    }
+}
+
+impl Canonicalize for PrintCapabilitiesDocument {
+    fn canonicalize(&mut self) {
+        self.extra = XmlExtra::default();
+        canonicalize_properties(&mut self.properties);
+        for parameter_def in &mut self.parameter_defs {
+            parameter_def.extra = XmlExtra::default();
+            canonicalize_properties(&mut parameter_def.properties);
+        }
+        sort_by_name(&mut self.parameter_defs, |x| Some(&x.name));
+        canonicalize_features(&mut self.features);
+        self.assign_standard_prefixes();
+    }
+
+    fn assign_standard_prefixes(&mut self) {
+        assign_standard_prefixes(self);
+    }
+}
+
+impl Canonicalize for PrintSchemaDocument {
+    fn canonicalize(&mut self) {
+        match self {
+            PrintSchemaDocument::PrintCapabilities(x) => x.canonicalize(),
+            PrintSchemaDocument::PrintTicket(x) => x.canonicalize(),
+        }
+    }
+
+    fn assign_standard_prefixes(&mut self) {
+        match self {
+            PrintSchemaDocument::PrintCapabilities(x) => x.assign_standard_prefixes(),
+            PrintSchemaDocument::PrintTicket(x) => x.assign_standard_prefixes(),
+        }
+    }
+}
+
+/// Visits every name, including the namespace declarations kept by the lossless mode.
+struct ForEachName<F: FnMut(&mut OwnedName)>(F);
+
+impl<F: FnMut(&mut OwnedName)> VisitMut for ForEachName<F> {
+    fn visit_name_mut(&mut self, name: &mut OwnedName, _path: &mut Vec<OwnedName>) {
+        (self.0)(name);
+    }
+}
+
+fn assign_standard_prefixes(document: &mut impl VisitableMut) {
+    let mut namespaces = BTreeMap::<String, String>::new();
+    document.accept_mut(&mut ForEachName(|name: &mut OwnedName| {
+        if let Some(namespace) = name.namespace_ref() {
+            namespaces.entry(namespace.to_string()).or_default();
+        }
+    }));
+    // the standard ones first, then the others in the order of their URIs
+    let mut index = 0;
+    for (namespace, prefix) in namespaces.iter_mut() {
+        *prefix = match namespace.as_str() {
+            NS_PSF => "psf".to_string(),
+            NS_PSK => "psk".to_string(),
+            NS_XSD => "xsd".to_string(),
+            NS_XSI => "xsi".to_string(),
+            _ => {
+                index += 1;
+                format!("ns{:04}", index - 1)
+            }
+        };
+    }
+    document.accept_mut(&mut ForEachName(|name: &mut OwnedName| {
+        if let Some(prefix) = name.namespace_ref().and_then(|x| namespaces.get(x)) {
+            name.prefix = Some(prefix.clone());
+        }
+    }));
+}
+
+fn collapse_whitespace(s: &mut String) {
+    let collapsed = s.split_whitespace().collect::<Vec<_>>().join(" ");
+    *s = collapsed;
+}
+
+fn canonicalize_value(value: &mut PropertyValue) {
+    match value {
+        PropertyValue::String(s) | PropertyValue::Unknown(_, s) => collapse_whitespace(s),
+        PropertyValue::Decimal(d) => *d = normalize_decimal(d),
+        PropertyValue::Integer(_)
+        | PropertyValue::BigInteger(_)
+        | PropertyValue::Boolean(_)
+        | PropertyValue::Base64Binary(_)
+        | PropertyValue::DateTime(_)
+        | PropertyValue::QName(_) => {}
+    }
+}
+
+/// Sort by namespace URI and local name. Unnamed items come last, in their original order.
+fn sort_by_name<T>(items: &mut [T], name: impl Fn(&T) -> Option<&OwnedName>) {
+    items.sort_by(|a, b| match (name(a), name(b)) {
+        (Some(a), Some(b)) => (&a.namespace, &a.local_name).cmp(&(&b.namespace, &b.local_name)),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+}
+
+fn canonicalize_properties(properties: &mut [Property]) {
+    for property in properties.iter_mut() {
+        property.extra = XmlExtra::default();
+        if let Some(value) = &mut property.value {
+            canonicalize_value(value);
+        }
+        canonicalize_properties(&mut property.properties);
+    }
+    sort_by_name(properties, |x| Some(&x.name));
+}
+
+fn canonicalize_scored_properties(scored_properties: &mut [ScoredProperty]) {
+    for scored_property in scored_properties.iter_mut() {
+        scored_property.extra = XmlExtra::default();
+        if let Some(value) = &mut scored_property.value {
+            canonicalize_value(value);
+        }
+        canonicalize_scored_properties(&mut scored_property.scored_properties);
+        canonicalize_properties(&mut scored_property.properties);
+    }
+    sort_by_name(scored_properties, |x| x.name.as_ref());
+}
+
+fn canonicalize_features(features: &mut [PrintFeature]) {
+    for feature in features.iter_mut() {
+        feature.extra = XmlExtra::default();
+        canonicalize_properties(&mut feature.properties);
+        for option in &mut feature.options {
+            option.extra = XmlExtra::default();
+            canonicalize_scored_properties(&mut option.scored_properties);
+            canonicalize_properties(&mut option.properties);
+        }
+        sort_by_name(&mut feature.options, |x| x.name.as_ref());
+        canonicalize_features(&mut feature.features);
+    }
+    sort_by_name(features, |x| Some(&x.name));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Canonical, Canonicalize};
+    use crate::ticket::document::{
+        reader::ParsableXmlDocument, PrintCapabilitiesDocument, PrintTicketDocument, PropertyValue,
+        NS_PSK,
+    };
+    use std::collections::{hash_map::DefaultHasher, HashSet};
+    use std::hash::Hasher;
+    use xml::name::OwnedName;
+
+    fn ticket(prefix: &str, body: &str) -> PrintTicketDocument {
+        let xml = format!(
+            r#"<psf:PrintTicket version="1"
+    xmlns:psf="http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework"
+    xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+    xmlns:xsd="http://www.w3.org/2001/XMLSchema"
+    xmlns:oem="http://vendor/"
+    xmlns:{0}="http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords">
+    {1}
+</psf:PrintTicket>"#,
+            prefix,
+            body.replace("{}", prefix)
+        );
+        PrintTicketDocument::parse_from_bytes(xml).unwrap()
+    }
+
+    fn hash_of(document: &impl Canonicalize) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        document.semantic_hash(&mut hasher);
+        hasher.finish()
+    }
+
+    const BODY_1: &str = r#"<psf:Feature name="{}:PageOrientation"><psf:Option name="{}:Landscape" /></psf:Feature>
+    <psf:Feature name="{}:PageMediaSize"><psf:Option name="{}:ISOA4" /></psf:Feature>
+    <psf:Feature name="oem:Borders"><psf:Option name="oem:On" /></psf:Feature>
+    <psf:Property name="{}:JobName">
+        <psf:Value xsi:type="xsd:string">  Monthly
+            report </psf:Value>
+    </psf:Property>"#;
+
+    const BODY_2: &str = r#"<psf:Property name="{}:JobName">
+        <psf:Value xsi:type="xsd:string">Monthly report</psf:Value>
+    </psf:Property>
+    <psf:Feature name="oem:Borders"><psf:Option name="oem:On" /></psf:Feature>
+    <psf:Feature name="{}:PageMediaSize"><psf:Option name="{}:ISOA4" /></psf:Feature>
+    <psf:Feature name="{}:PageOrientation"><psf:Option name="{}:Landscape" /></psf:Feature>"#;
+
+    #[test]
+    fn prefixes_order_and_whitespace_are_ignored() {
+        let a = ticket("psk", BODY_1);
+        let b = ticket("ns0000", BODY_2);
+        assert_ne!(a, b);
+        assert!(a.semantic_eq(&b));
+        assert_eq!(hash_of(&a), hash_of(&b));
+    }
+
+    #[test]
+    fn different_documents_are_not_equal() {
+        let a = ticket("psk", BODY_1);
+        let b = ticket("psk", &BODY_1.replace("Landscape", "Portrait"));
+        assert!(!a.semantic_eq(&b));
+    }
+
+    #[test]
+    fn canonical_form() {
+        let document = ticket("ns0000", BODY_1).to_canonical();
+        let names = document
+            .features
+            .iter()
+            .map(|x| x.name.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                format!("{{{}}}psk:PageMediaSize", NS_PSK),
+                format!("{{{}}}psk:PageOrientation", NS_PSK),
+                "{http://vendor/}ns0000:Borders".to_string(),
+            ]
+        );
+        assert_eq!(
+            document.properties[0].value,
+            Some(PropertyValue::String("Monthly report".to_string()))
+        );
+        assert_eq!(
+            document.properties[0].name,
+            OwnedName::qualified("JobName", NS_PSK, Some("psk"))
+        );
+    }
+
+    #[test]
+    fn canonical_as_key() {
+        let mut set = HashSet::new();
+        set.insert(Canonical::new(ticket("psk", BODY_1)));
+        set.insert(Canonical::new(ticket("ns0000", BODY_2)));
+        set.insert(Canonical::new(ticket("psk", "")));
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn canonical_capabilities_are_stable() {
+        let xml = include_bytes!("../../../test_data/print_capabilities.xml");
+        let document = PrintCapabilitiesDocument::parse_from_bytes(xml).unwrap();
+        let canonical = document.to_canonical();
+        assert!(canonical.semantic_eq(&document));
+        assert_eq!(canonical, canonical.to_canonical());
+    }
+}
@@ -0,0 +1,190 @@
+use super::{
+    ParameterDef, ParameterInit, PrintCapabilitiesDocument, PrintFeature, PrintFeatureOption,
+    PrintTicketDocument, Property, ScoredProperty,
+};
+use xml::name::OwnedName;
+
+/// The `psf2` attributes of a parameter definition, with the `psf` properties and the `xsd` types they are read as.
+///
+/// The `psf2:default` attribute is read as the `psf:DefaultValue` property, and typed by the `xsi:type` attribute
+/// which is read as the `psf:DataType` property.
+pub(super) const PARAMETER_ATTRIBUTES: &[(&str, &str, &str)] = &[
+    ("mandatory", "Mandatory", "QName"),
+    ("minValue", "MinValue", "integer"),
+    ("maxValue", "MaxValue", "integer"),
+    ("multiple", "Multiple", "integer"),
+    ("minLength", "MinLength", "integer"),
+    ("maxLength", "MaxLength", "integer"),
+    ("unitType", "UnitType", "string"),
+];
+
+#[derive(Clone, PartialEq, Eq, Hash, fmt_derive::Debug)]
+/// Represents a PrintDeviceCapabilities document of the Print Schema version 2.
+///
+/// The elements of the document are named by their keywords and typed by the `psf2:psftype` attribute.
+/// Parameter definitions are kept as [`ParameterDef`]s, with their `psf2` attributes as `psf` properties,
+/// so that [`ParameterDef::definition`] reads them as usual.
+pub struct PrintDeviceCapabilitiesDocument {
+    /// Properties of the document
+    pub properties: Vec<Property>,
+    /// Parameter definitions
+    pub parameter_defs: Vec<ParameterDef>,
+    /// Features
+    pub features: Vec<DeviceFeature>,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, fmt_derive::Debug)]
+/// Represents a feature of a [`PrintDeviceCapabilitiesDocument`].
+pub struct DeviceFeature {
+    /// The name of the feature.
+    #[fmt("{}", self.name)]
+    pub name: OwnedName,
+    /// Properties of the feature
+    pub properties: Vec<Property>,
+    /// Available options
+    pub options: Vec<DeviceOption>,
+    /// Sub-features of the feature
+    pub features: Vec<DeviceFeature>,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, fmt_derive::Debug)]
+/// Represents an option of a [`DeviceFeature`].
+pub struct DeviceOption {
+    /// The name of the option.
+    #[fmt("{}", self.name)]
+    pub name: OwnedName,
+    /// Whether the option is the default of the feature, as the `psf2:default` attribute.
+    pub default: bool,
+    /// Scored-properties of the option
+    pub scored_properties: Vec<ScoredProperty>,
+    /// Properties of the option
+    pub properties: Vec<Property>,
+}
+
+impl DeviceFeature {
+    /// Get the default option, or the first option if none is marked as default.
+    pub fn default_option(&self) -> Option<&DeviceOption> {
+        self.options
+            .iter()
+            .find(|x| x.default)
+            .or(self.options.first())
+    }
+}
+
+impl PrintDeviceCapabilitiesDocument {
+    /// Convert the document into a PrintCapabilities document.
+    ///
+    /// The default options are not part of a PrintCapabilities document, see
+    /// [`PrintDeviceCapabilitiesDocument::default_print_ticket`] to keep them.
+    pub fn to_print_capabilities(&self) -> PrintCapabilitiesDocument {
+        PrintCapabilitiesDocument::from(self)
+    }
+
+    /// Create the PrintTicket document which selects the default option of every feature,
+    /// and initializes every parameter which has a default value.
+    pub fn default_print_ticket(&self) -> PrintTicketDocument {
+        PrintTicketDocument::new(
+            vec![],
+            self.parameter_defs
+                .iter()
+                .filter_map(|def| {
+                    def.default_value()
+                        .map(|value| ParameterInit::new(def.name.clone(), value.clone()))
+                })
+                .collect(),
+            self.features.iter().map(default_feature).collect(),
+        )
+    }
+}
+
+fn default_feature(feature: &DeviceFeature) -> PrintFeature {
+    PrintFeature::new(
+        feature.name.clone(),
+        vec![],
+        feature
+            .default_option()
+            .map(PrintFeatureOption::from)
+            .into_iter()
+            .collect(),
+        feature.features.iter().map(default_feature).collect(),
+    )
+}
+
+impl From<&PrintDeviceCapabilitiesDocument> for PrintCapabilitiesDocument {
+    fn from(document: &PrintDeviceCapabilitiesDocument) -> Self {
+        PrintCapabilitiesDocument::new(
+            document.properties.clone(),
+            document.parameter_defs.clone(),
+            document.features.iter().map(PrintFeature::from).collect(),
+        )
+    }
+}
+
+impl From<&DeviceFeature> for PrintFeature {
+    fn from(feature: &DeviceFeature) -> Self {
+        PrintFeature::new(
+            feature.name.clone(),
+            feature.properties.clone(),
+            feature
+                .options
+                .iter()
+                .map(PrintFeatureOption::from)
+                .collect(),
+            feature.features.iter().map(PrintFeature::from).collect(),
+        )
+    }
+}
+
+impl From<&DeviceOption> for PrintFeatureOption {
+    fn from(option: &DeviceOption) -> Self {
+        PrintFeatureOption::new(
+            Some(option.name.clone()),
+            option.scored_properties.clone(),
+            option.properties.clone(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ticket::test_fixtures::sample_device_capabilities_document;
+    use crate::ticket::{
+        FeatureOptionPackWithPredefined, MediaSizeTuple, PredefinedMediaName,
+        PredefinedPageOrientation, PrintCapabilities,
+    };
+
+    #[test]
+    fn convert_to_print_capabilities() {
+        let capabilities = PrintCapabilities {
+            document: sample_device_capabilities_document().to_print_capabilities(),
+        };
+        let a4 = capabilities
+            .page_media_sizes()
+            .find(|x| x.as_predefined_name() == Some(PredefinedMediaName::ISOA4))
+            .unwrap();
+        assert_eq!(a4.size(), MediaSizeTuple::mm(210, 297));
+        assert_eq!(
+            capabilities
+                .page_orientations()
+                .filter_map(|x| x.as_predefined_name())
+                .collect::<Vec<_>>(),
+            vec![
+                PredefinedPageOrientation::Portrait,
+                PredefinedPageOrientation::Landscape
+            ]
+        );
+        assert_eq!(capabilities.max_copies().map(|x| x.0), Some(999));
+    }
+
+    #[test]
+    fn default_print_ticket() {
+        let ticket = sample_device_capabilities_document().default_print_ticket();
+        let selected = ticket
+            .features
+            .iter()
+            .map(|x| x.options[0].name.as_ref().unwrap().local_name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(selected, vec!["NorthAmericaLetter", "Portrait"]);
+        assert_eq!(ticket.parameter_inits.len(), 3);
+    }
+}
@@ -0,0 +1,524 @@
+use super::{
+    device_capabilities::PARAMETER_ATTRIBUTES,
+    reader::{
+        keeps_invalid_value, parse_qname, parse_type_attribute, parse_value, DiagnosticAction,
+        ParsableXmlDocument, ParseDiagnostic, ParseOptions, ParsePrintSchemaError,
+    },
+    xsd::parse_boolean,
+    DeviceFeature, DeviceOption, ParameterDef, PrintDeviceCapabilitiesDocument,
+    PrintDeviceResourcesDocument, Property, PropertyValue, ScoredProperty, NS_PSF, NS_PSF2, NS_XSD,
+};
+use xml::{
+    attribute::OwnedAttribute,
+    common::{Position, TextPosition},
+    name::OwnedName,
+    namespace::{Namespace, NS_XML_URI},
+    reader::XmlEvent,
+    EventReader,
+};
+
+/// An element of the document, read before it is interpreted by its `psf2:psftype` attribute.
+struct RawElement {
+    name: OwnedName,
+    attributes: Vec<OwnedAttribute>,
+    namespace: Namespace,
+    text: String,
+    children: Vec<RawElement>,
+    pos: TextPosition,
+}
+
+impl RawElement {
+    fn attribute(&self, local_name: &str, namespace: Option<&str>) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|x| x.name.local_name == local_name && x.name.namespace_ref() == namespace)
+            .map(|x| x.value.as_str())
+    }
+
+    fn psftype(&self) -> Option<&str> {
+        self.attribute("psftype", Some(NS_PSF2))
+    }
+}
+
+fn read_root<R>(reader: &mut EventReader<R>) -> Result<RawElement, ParsePrintSchemaError>
+where
+    R: std::io::Read,
+{
+    let mut stack = Vec::<RawElement>::new();
+    loop {
+        let e = reader.next().map_err(ParsePrintSchemaError::InvalidXml)?;
+        match e {
+            XmlEvent::StartElement {
+                name,
+                attributes,
+                namespace,
+            } => stack.push(RawElement {
+                name,
+                attributes,
+                namespace,
+                text: String::new(),
+                children: vec![],
+                pos: reader.position(),
+            }),
+            XmlEvent::EndElement { .. } => {
+                let element = stack.pop().expect("the reader checks element nesting");
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(element),
+                    None => return Ok(element),
+                }
+            }
+            XmlEvent::Characters(s) | XmlEvent::CData(s) | XmlEvent::Whitespace(s) => {
+                if let Some(element) = stack.last_mut() {
+                    element.text.push_str(&s);
+                }
+            }
+            XmlEvent::EndDocument => {
+                return Err(ParsePrintSchemaError::InvalidPrintSchema {
+                    pos: reader.position(),
+                    reason: "No valid root element found".to_string(),
+                })
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Interprets the elements, collecting the diagnostics in lenient mode.
+struct DeviceCapabilitiesBuilder {
+    diagnostics: Option<Vec<ParseDiagnostic>>,
+}
+
+impl DeviceCapabilitiesBuilder {
+    /// Return an error, or report the element as skipped in lenient mode.
+    fn skip(&mut self, element: &RawElement, reason: String) -> Result<(), ParsePrintSchemaError> {
+        match self.diagnostics.as_mut() {
+            Some(diagnostics) => {
+                diagnostics.push(ParseDiagnostic {
+                    pos: element.pos,
+                    reason,
+                    action: DiagnosticAction::Skipped,
+                });
+                Ok(())
+            }
+            None => Err(ParsePrintSchemaError::InvalidPrintSchema {
+                pos: element.pos,
+                reason,
+            }),
+        }
+    }
+
+    /// Skip a child whose `psf2:psftype` is not allowed in its parent.
+    /// Elements without the attribute are extensions, and ignored.
+    fn skip_child(
+        &mut self,
+        child: &RawElement,
+        parent: &RawElement,
+    ) -> Result<(), ParsePrintSchemaError> {
+        match child.psftype() {
+            Some(psftype) => self.skip(
+                child,
+                format!("{} is not allowed in {}", psftype, parent.name),
+            ),
+            None => Ok(()),
+        }
+    }
+
+    fn value(
+        &mut self,
+        element: &RawElement,
+        value: &str,
+        value_type: OwnedName,
+    ) -> Result<PropertyValue, ParsePrintSchemaError> {
+        match parse_value(
+            value.to_string(),
+            value_type.clone(),
+            element.namespace.clone(),
+            element.pos,
+        ) {
+            Ok(value) => Ok(value),
+            Err(ParsePrintSchemaError::InvalidPrintSchema { pos, reason }) => {
+                match self.diagnostics.as_mut() {
+                    Some(diagnostics) => {
+                        diagnostics.push(ParseDiagnostic {
+                            pos,
+                            reason,
+                            action: DiagnosticAction::KeptRaw,
+                        });
+                        Ok(PropertyValue::Unknown(value_type, value.to_string()))
+                    }
+                    None if keeps_invalid_value(&value_type) => {
+                        Ok(PropertyValue::Unknown(value_type, value.to_string()))
+                    }
+                    None => Err(ParsePrintSchemaError::InvalidPrintSchema { pos, reason }),
+                }
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Read the value of the element from its text, typed by the `xsi:type` attribute.
+    fn element_value(
+        &mut self,
+        element: &RawElement,
+    ) -> Result<Option<PropertyValue>, ParsePrintSchemaError> {
+        parse_type_attribute(&element.attributes, &element.namespace)
+            .map(|value_type| self.value(element, &element.text, value_type))
+            .transpose()
+    }
+
+    fn document(
+        &mut self,
+        root: &RawElement,
+    ) -> Result<PrintDeviceCapabilitiesDocument, ParsePrintSchemaError> {
+        let mut document = PrintDeviceCapabilitiesDocument {
+            properties: vec![],
+            parameter_defs: vec![],
+            features: vec![],
+        };
+        for child in &root.children {
+            match child.psftype() {
+                Some("Property") => document.properties.push(self.property(child)?),
+                Some("ParameterDef") => document.parameter_defs.push(self.parameter_def(child)?),
+                Some("Feature") => document.features.push(self.feature(child)?),
+                _ => self.skip_child(child, root)?,
+            }
+        }
+        Ok(document)
+    }
+
+    fn feature(&mut self, element: &RawElement) -> Result<DeviceFeature, ParsePrintSchemaError> {
+        let mut feature = DeviceFeature {
+            name: element.name.clone(),
+            properties: vec![],
+            options: vec![],
+            features: vec![],
+        };
+        for child in &element.children {
+            match child.psftype() {
+                Some("Property") => feature.properties.push(self.property(child)?),
+                Some("Option") => feature.options.push(self.option(child)?),
+                Some("Feature") => feature.features.push(self.feature(child)?),
+                _ => self.skip_child(child, element)?,
+            }
+        }
+        Ok(feature)
+    }
+
+    fn option(&mut self, element: &RawElement) -> Result<DeviceOption, ParsePrintSchemaError> {
+        let default = match element.attribute("default", Some(NS_PSF2)) {
+            Some(value) => match parse_boolean(value.trim()) {
+                Some(value) => value,
+                None => {
+                    self.skip(element, "Invalid default attribute".to_string())?;
+                    false
+                }
+            },
+            None => false,
+        };
+        let mut option = DeviceOption {
+            name: element.name.clone(),
+            default,
+            scored_properties: vec![],
+            properties: vec![],
+        };
+        for child in &element.children {
+            match child.psftype() {
+                Some("ScoredProperty") => {
+                    option.scored_properties.push(self.scored_property(child)?)
+                }
+                Some("Property") => option.properties.push(self.property(child)?),
+                _ => self.skip_child(child, element)?,
+            }
+        }
+        Ok(option)
+    }
+
+    fn scored_property(
+        &mut self,
+        element: &RawElement,
+    ) -> Result<ScoredProperty, ParsePrintSchemaError> {
+        let mut scored_property = ScoredProperty::new(
+            Some(element.name.clone()),
+            None,
+            self.element_value(element)?,
+            vec![],
+            vec![],
+        );
+        for child in &element.children {
+            match child.psftype() {
+                Some("ParameterRef") => scored_property.parameter_ref = Some(child.name.clone()),
+                Some("ScoredProperty") => scored_property
+                    .scored_properties
+                    .push(self.scored_property(child)?),
+                Some("Property") => scored_property.properties.push(self.property(child)?),
+                _ => self.skip_child(child, element)?,
+            }
+        }
+        Ok(scored_property)
+    }
+
+    fn property(&mut self, element: &RawElement) -> Result<Property, ParsePrintSchemaError> {
+        let mut property =
+            Property::new(element.name.clone(), self.element_value(element)?, vec![]);
+        for child in &element.children {
+            match child.psftype() {
+                Some("Property") => property.properties.push(self.property(child)?),
+                _ => self.skip_child(child, element)?,
+            }
+        }
+        Ok(property)
+    }
+
+    fn parameter_def(
+        &mut self,
+        element: &RawElement,
+    ) -> Result<ParameterDef, ParsePrintSchemaError> {
+        let psf = |local_name: &str| OwnedName::qualified(local_name, NS_PSF, Some("psf"));
+        let xsd = |local_name: &str| OwnedName::qualified(local_name, NS_XSD, Some("xsd"));
+        let property =
+            |name: OwnedName, value: PropertyValue| Property::new(name, Some(value), vec![]);
+
+        let mut properties = vec![];
+        let data_type = parse_type_attribute(&element.attributes, &element.namespace);
+        if let Some(data_type) = &data_type {
+            properties.push(property(
+                psf("DataType"),
+                PropertyValue::QName(data_type.clone()),
+            ));
+        }
+        if let Some(default) = element.attribute("default", Some(NS_PSF2)) {
+            let value_type = data_type.unwrap_or_else(|| xsd("string"));
+            let value = self.value(element, default, value_type)?;
+            properties.push(property(psf("DefaultValue"), value));
+        }
+        for (attribute, local_name, value_type) in PARAMETER_ATTRIBUTES {
+            if let Some(value) = element.attribute(attribute, Some(NS_PSF2)) {
+                let value = if *value_type == "QName" {
+                    PropertyValue::QName(parse_qname(&element.namespace, value.trim()))
+                } else {
+                    self.value(element, value, xsd(value_type))?
+                };
+                properties.push(property(psf(local_name), value));
+            }
+        }
+        for child in &element.children {
+            match child.psftype() {
+                Some("Property") => properties.push(self.property(child)?),
+                _ => self.skip_child(child, element)?,
+            }
+        }
+        Ok(ParameterDef::new(element.name.clone(), properties))
+    }
+}
+
+/// Read a document of the Print Schema version 2 with the expected root element,
+/// returning its content, its `xml:lang` attribute and the diagnostics of the lenient mode.
+fn parse_device_document<R>(
+    reader: &mut EventReader<R>,
+    options: &ParseOptions,
+    expected: &'static str,
+) -> Result<
+    (
+        PrintDeviceCapabilitiesDocument,
+        Option<String>,
+        Vec<ParseDiagnostic>,
+    ),
+    ParsePrintSchemaError,
+>
+where
+    R: std::io::Read,
+{
+    let root = read_root(reader)?;
+    let found = match (root.name.namespace_ref(), root.name.local_name.as_str()) {
+        (Some(NS_PSF), "PrintCapabilities") => "PrintCapabilities",
+        (Some(NS_PSF), "PrintTicket") => "PrintTicket",
+        (Some(NS_PSF2), "PrintDeviceCapabilities") => "PrintDeviceCapabilities",
+        (Some(NS_PSF2), "PrintDeviceResources") => "PrintDeviceResources",
+        _ => {
+            return Err(ParsePrintSchemaError::InvalidPrintSchema {
+                pos: root.pos,
+                reason: "No valid root element found".to_string(),
+            })
+        }
+    };
+    if found != expected {
+        return Err(ParsePrintSchemaError::WrongDocumentType { expected, found });
+    }
+    let mut builder = DeviceCapabilitiesBuilder {
+        diagnostics: options.is_lenient().then(Vec::new),
+    };
+    let document = builder.document(&root)?;
+    let language = root.attribute("lang", Some(NS_XML_URI)).map(str::to_string);
+    Ok((document, language, builder.diagnostics.unwrap_or_default()))
+}
+
+impl ParsableXmlDocument for PrintDeviceCapabilitiesDocument {
+    type Error = ParsePrintSchemaError;
+
+    fn parse<R>(reader: &mut EventReader<R>) -> Result<Self, Self::Error>
+    where
+        R: std::io::Read,
+    {
+        Self::parse_with_options(reader, &ParseOptions::default())
+    }
+
+    /// Parse the XML document from the given XML reader, with the given options.
+    /// Return the diagnostics of the lenient mode as well, which are always empty otherwise.
+    ///
+    /// The content which is not part of the model is not kept, even in lossless mode.
+    /// Documents of other types, like PrintCapabilities, are rejected as the wrong document type.
+    fn parse_with_diagnostics<R>(
+        reader: &mut EventReader<R>,
+        options: &ParseOptions,
+    ) -> Result<(Self, Vec<ParseDiagnostic>), Self::Error>
+    where
+        R: std::io::Read,
+    {
+        let (document, _, diagnostics) =
+            parse_device_document(reader, options, "PrintDeviceCapabilities")?;
+        Ok((document, diagnostics))
+    }
+}
+
+impl ParsableXmlDocument for PrintDeviceResourcesDocument {
+    type Error = ParsePrintSchemaError;
+
+    fn parse<R>(reader: &mut EventReader<R>) -> Result<Self, Self::Error>
+    where
+        R: std::io::Read,
+    {
+        Self::parse_with_options(reader, &ParseOptions::default())
+    }
+
+    /// Parse the XML document from the given XML reader, with the given options.
+    /// Return the diagnostics of the lenient mode as well, which are always empty otherwise.
+    ///
+    /// The content which is not part of the model is not kept, even in lossless mode.
+    /// Documents of other types, like PrintDeviceCapabilities, are rejected as the wrong document type.
+    fn parse_with_diagnostics<R>(
+        reader: &mut EventReader<R>,
+        options: &ParseOptions,
+    ) -> Result<(Self, Vec<ParseDiagnostic>), Self::Error>
+    where
+        R: std::io::Read,
+    {
+        let (document, language, diagnostics) =
+            parse_device_document(reader, options, "PrintDeviceResources")?;
+        Ok((
+            PrintDeviceResourcesDocument {
+                language,
+                properties: document.properties,
+                parameter_defs: document.parameter_defs,
+                features: document.features,
+            },
+            diagnostics,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ticket::document::{
+        reader::{DiagnosticAction, ParsableXmlDocument, ParseOptions, ParsePrintSchemaError},
+        ParameterMandatory, PrintDeviceCapabilitiesDocument, PropertyValue,
+    };
+
+    const XML: &[u8] = include_bytes!("../../../test_data/print_device_capabilities.xml");
+
+    #[test]
+    fn parse_print_device_capabilities() {
+        let document = PrintDeviceCapabilitiesDocument::parse_from_bytes(XML).unwrap();
+        assert_eq!(document.properties.len(), 1);
+        assert_eq!(
+            document.properties[0].value,
+            Some(PropertyValue::String("Sample Printer".to_string()))
+        );
+
+        assert_eq!(document.parameter_defs.len(), 3);
+        let copies = document.parameter_defs[0].definition();
+        assert_eq!(copies.name.local_name, "JobCopiesAllDocuments");
+        assert_eq!(copies.data_type.unwrap().local_name, "integer");
+        assert_eq!(copies.default_value, Some(PropertyValue::Integer(1)));
+        assert_eq!(copies.mandatory, Some(ParameterMandatory::Unconditional));
+        assert_eq!((copies.min_value, copies.max_value), (Some(1), Some(999)));
+        assert_eq!(copies.unit_type.as_deref(), Some("copies"));
+        assert_eq!(document.parameter_defs[0].properties.len(), 8);
+
+        let media_size = &document.features[0];
+        assert_eq!(media_size.name.local_name, "PageMediaSize");
+        assert_eq!(media_size.properties.len(), 1);
+        assert_eq!(media_size.options.len(), 3);
+        assert_eq!(
+            media_size.default_option().unwrap().name.local_name,
+            "NorthAmericaLetter"
+        );
+        let a4 = &media_size.options[0];
+        assert!(!a4.default);
+        assert_eq!(
+            a4.scored_properties[0].value,
+            Some(PropertyValue::Integer(210000))
+        );
+        let custom = &media_size.options[2].scored_properties[0];
+        assert_eq!(custom.value, None);
+        assert_eq!(
+            custom.parameter_ref.as_ref().unwrap().local_name,
+            "PageMediaSizeMediaSizeWidth"
+        );
+    }
+
+    #[test]
+    fn wrong_type_should_return_error() {
+        let xml = include_bytes!("../../../test_data/print_capabilities.xml");
+        assert!(matches!(
+            PrintDeviceCapabilitiesDocument::parse_from_bytes(xml),
+            Err(ParsePrintSchemaError::WrongDocumentType {
+                expected: "PrintDeviceCapabilities",
+                found: "PrintCapabilities"
+            })
+        ));
+    }
+
+    #[test]
+    fn lenient_mode_reports_invalid_nodes() {
+        let xml = r#"<psf2:PrintDeviceCapabilities version="2"
+    xmlns:psf2="http://schemas.microsoft.com/windows/2013/12/printing/printschemaframework2"
+    xmlns:psk="http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords"
+    xmlns:xsd="http://www.w3.org/2001/XMLSchema"
+    xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
+    <psk:PageOrientation psf2:psftype="Feature">
+        <psk:Portrait psf2:psftype="Option" psf2:default="yes" />
+        <psk:MediaSizeWidth psf2:psftype="ScoredProperty" />
+    </psk:PageOrientation>
+    <psk:JobCopiesAllDocuments psf2:psftype="ParameterDef" xsi:type="xsd:integer" psf2:default="one" />
+</psf2:PrintDeviceCapabilities>"#;
+        assert!(matches!(
+            PrintDeviceCapabilitiesDocument::parse_from_bytes(xml),
+            Err(ParsePrintSchemaError::InvalidPrintSchema { .. })
+        ));
+
+        let (document, diagnostics) =
+            PrintDeviceCapabilitiesDocument::parse_from_bytes_with_diagnostics(
+                xml,
+                &ParseOptions::new().lenient(true),
+            )
+            .unwrap();
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|x| (x.pos.row + 1, x.action))
+                .collect::<Vec<_>>(),
+            vec![
+                (7, DiagnosticAction::Skipped),
+                (8, DiagnosticAction::Skipped),
+                (10, DiagnosticAction::KeptRaw),
+            ]
+        );
+        let orientation = &document.features[0];
+        assert_eq!(orientation.options.len(), 1);
+        assert!(!orientation.options[0].default);
+        assert!(matches!(
+            document.parameter_defs[0].default_value(),
+            Some(PropertyValue::Unknown(_, x)) if x == "one"
+        ));
+    }
+}
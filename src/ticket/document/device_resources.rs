@@ -0,0 +1,317 @@
+use super::{
+    print_schema::same_name, DeviceFeature, ParameterDef, PrintCapabilitiesDocument, PrintFeature,
+    Property, PropertyValue, NS_PSK,
+};
+use xml::name::OwnedName;
+
+#[derive(Clone, PartialEq, Eq, Hash, fmt_derive::Debug)]
+/// Represents a PrintDeviceResources document of the Print Schema version 2,
+/// which holds the localized strings of a PrintDeviceCapabilities document for one locale.
+///
+/// The features, options and parameter definitions mirror the ones of the PrintDeviceCapabilities document.
+/// Top-level properties are string resources, which display names of type `xsd:QName` refer to by name.
+pub struct PrintDeviceResourcesDocument {
+    /// The locale of the strings, as the `xml:lang` attribute. `None` for neutral resources.
+    pub language: Option<String>,
+    /// Properties of the document
+    pub properties: Vec<Property>,
+    /// Parameter definitions
+    pub parameter_defs: Vec<ParameterDef>,
+    /// Features
+    pub features: Vec<DeviceFeature>,
+}
+
+impl PrintDeviceResourcesDocument {
+    /// Check whether the resources are neutral, that is, without a locale.
+    pub fn is_neutral(&self) -> bool {
+        self.language.as_deref().is_none_or(|x| x.trim().is_empty())
+    }
+
+    fn feature(&self, path: &[OwnedName]) -> Option<&DeviceFeature> {
+        let (first, rest) = path.split_first()?;
+        let mut feature = self.features.iter().find(|x| same_name(&x.name, first))?;
+        for name in rest {
+            feature = feature.features.iter().find(|x| same_name(&x.name, name))?;
+        }
+        Some(feature)
+    }
+
+    fn resource(&self, name: &OwnedName) -> Option<&str> {
+        self.properties
+            .iter()
+            .find(|x| same_name(&x.name, name))
+            .and_then(|x| x.value.as_ref())
+            .and_then(|x| x.string())
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+/// Represents the PrintDeviceResources documents of a device, one per locale,
+/// used to localize the display names of a PrintCapabilities document.
+pub struct PrintDeviceResources {
+    documents: Vec<PrintDeviceResourcesDocument>,
+}
+
+impl FromIterator<PrintDeviceResourcesDocument> for PrintDeviceResources {
+    fn from_iter<T: IntoIterator<Item = PrintDeviceResourcesDocument>>(iter: T) -> Self {
+        Self {
+            documents: iter.into_iter().collect(),
+        }
+    }
+}
+
+fn normalize_locale(locale: &str) -> String {
+    locale.trim().replace('_', "-").to_ascii_lowercase()
+}
+
+fn display_name_value(properties: &[Property]) -> Option<&PropertyValue> {
+    properties
+        .iter()
+        .find(|x| x.name.local_name == "DisplayName" && x.name.namespace_ref() == Some(NS_PSK))
+        .and_then(|x| x.value.as_ref())
+}
+
+fn display_name(properties: &[Property]) -> Option<&str> {
+    display_name_value(properties).and_then(|x| x.string())
+}
+
+fn set_display_name(properties: &mut Vec<Property>, value: &str) {
+    let value = Some(PropertyValue::String(value.to_string()));
+    match properties
+        .iter_mut()
+        .find(|x| x.name.local_name == "DisplayName" && x.name.namespace_ref() == Some(NS_PSK))
+    {
+        Some(property) => property.value = value,
+        None => properties.push(Property::new(
+            OwnedName::qualified("DisplayName", NS_PSK, Some("psk")),
+            value,
+            vec![],
+        )),
+    }
+}
+
+impl PrintDeviceResources {
+    /// Create an empty set of resources.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the resources of a locale.
+    pub fn push(&mut self, document: PrintDeviceResourcesDocument) {
+        self.documents.push(document);
+    }
+
+    /// Get all the documents.
+    pub fn documents(&self) -> &[PrintDeviceResourcesDocument] {
+        &self.documents
+    }
+
+    /// Get the documents to look up strings in for the given locale, like `de-DE`, from the most specific.
+    ///
+    /// These are the documents of the locale itself, then of its parent locales like `de`,
+    /// then the neutral ones. Locales are compared case-insensitively, and `_` is treated as `-`.
+    pub fn fallback_chain(&self, locale: &str) -> Vec<&PrintDeviceResourcesDocument> {
+        let mut locale = normalize_locale(locale);
+        let mut chain = vec![];
+        while !locale.is_empty() {
+            chain.extend(self.documents.iter().filter(|x| {
+                x.language
+                    .as_deref()
+                    .is_some_and(|x| normalize_locale(x) == locale)
+            }));
+            locale.truncate(locale.rfind('-').unwrap_or(0));
+        }
+        chain.extend(self.documents.iter().filter(|x| x.is_neutral()));
+        chain
+    }
+
+    /// Fill in or replace the display names of the features, options and parameter definitions
+    /// with the strings for the given locale, see [`PrintDeviceResources::fallback_chain`].
+    ///
+    /// A display name of type `xsd:QName` is replaced by the string resource it refers to.
+    /// Otherwise, the display name of the same feature, option or parameter definition in the resources is used.
+    /// Display names without a localized string are kept.
+    pub fn localize(&self, document: &mut PrintCapabilitiesDocument, locale: &str) {
+        let chain = self.fallback_chain(locale);
+        let localizer = Localizer { chain };
+        for feature in &mut document.features {
+            localizer.feature(feature, &mut vec![]);
+        }
+        for parameter_def in &mut document.parameter_defs {
+            let name = localizer.chain.iter().find_map(|x| {
+                x.parameter_defs
+                    .iter()
+                    .find(|x| same_name(&x.name, &parameter_def.name))
+                    .and_then(|x| display_name(&x.properties))
+            });
+            localizer.apply(&mut parameter_def.properties, name);
+        }
+    }
+}
+
+struct Localizer<'a> {
+    chain: Vec<&'a PrintDeviceResourcesDocument>,
+}
+
+impl<'a> Localizer<'a> {
+    fn feature(&self, feature: &mut PrintFeature, path: &mut Vec<OwnedName>) {
+        path.push(feature.name.clone());
+        let resources = self
+            .chain
+            .iter()
+            .filter_map(|x| x.feature(path))
+            .collect::<Vec<_>>();
+        let name = resources.iter().find_map(|x| display_name(&x.properties));
+        self.apply(&mut feature.properties, name);
+        for option in &mut feature.options {
+            let name = option.name.as_ref().and_then(|option_name| {
+                resources.iter().find_map(|x| {
+                    x.options
+                        .iter()
+                        .find(|x| same_name(&x.name, option_name))
+                        .and_then(|x| display_name(&x.properties))
+                })
+            });
+            self.apply(&mut option.properties, name);
+        }
+        for feature in &mut feature.features {
+            self.feature(feature, path);
+        }
+        path.pop();
+    }
+
+    fn apply(&self, properties: &mut Vec<Property>, localized: Option<&str>) {
+        let referred = match display_name_value(properties) {
+            Some(PropertyValue::QName(name)) => self.chain.iter().find_map(|x| x.resource(name)),
+            _ => None,
+        };
+        if let Some(name) = referred.or(localized) {
+            set_display_name(properties, name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PrintDeviceResources;
+    use crate::ticket::test_fixtures::sample_device_capabilities_document;
+    use crate::ticket::{
+        document::{reader::ParsableXmlDocument, PrintDeviceResourcesDocument},
+        FeatureOptionPack, PrintCapabilities,
+    };
+
+    fn resources(language: Option<&str>, body: &str) -> PrintDeviceResourcesDocument {
+        let xml = format!(
+            r#"<psf2:PrintDeviceResources version="2" {}
+    xmlns:psf2="http://schemas.microsoft.com/windows/2013/12/printing/printschemaframework2"
+    xmlns:psk="http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords"
+    xmlns:xsd="http://www.w3.org/2001/XMLSchema"
+    xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+    xmlns:ns0000="http://vendor/private">
+    {}
+</psf2:PrintDeviceResources>"#,
+            language
+                .map(|x| format!(r#"xml:lang="{}""#, x))
+                .unwrap_or_default(),
+            body
+        );
+        PrintDeviceResourcesDocument::parse_from_bytes(xml).unwrap()
+    }
+
+    fn device_resources() -> PrintDeviceResources {
+        [
+            resources(
+                None,
+                r#"<ns0000:LandscapeName psf2:psftype="Property" xsi:type="xsd:string">Landscape</ns0000:LandscapeName>
+    <psk:PageOrientation psf2:psftype="Feature">
+        <psk:DisplayName psf2:psftype="Property" xsi:type="xsd:string">Orientation</psk:DisplayName>
+        <psk:Portrait psf2:psftype="Option">
+            <psk:DisplayName psf2:psftype="Property" xsi:type="xsd:string">Portrait</psk:DisplayName>
+        </psk:Portrait>
+    </psk:PageOrientation>"#,
+            ),
+            resources(
+                Some("de"),
+                r#"<ns0000:LandscapeName psf2:psftype="Property" xsi:type="xsd:string">Querformat</ns0000:LandscapeName>
+    <psk:JobCopiesAllDocuments psf2:psftype="ParameterDef">
+        <psk:DisplayName psf2:psftype="Property" xsi:type="xsd:string">Kopien</psk:DisplayName>
+    </psk:JobCopiesAllDocuments>"#,
+            ),
+            resources(
+                Some("de-DE"),
+                r#"<psk:PageMediaSize psf2:psftype="Feature">
+        <psk:DisplayName psf2:psftype="Property" xsi:type="xsd:string">Papierformat</psk:DisplayName>
+        <psk:ISOA4 psf2:psftype="Option">
+            <psk:DisplayName psf2:psftype="Property" xsi:type="xsd:string">DIN A4</psk:DisplayName>
+        </psk:ISOA4>
+    </psk:PageMediaSize>
+    <psk:PageOrientation psf2:psftype="Feature">
+        <psk:Portrait psf2:psftype="Option">
+            <psk:DisplayName psf2:psftype="Property" xsi:type="xsd:string">Hochformat</psk:DisplayName>
+        </psk:Portrait>
+    </psk:PageOrientation>"#,
+            ),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    fn capabilities(resources: &PrintDeviceResources, locale: &str) -> PrintCapabilities {
+        let mut document = sample_device_capabilities_document().to_print_capabilities();
+        resources.localize(&mut document, locale);
+        PrintCapabilities { document }
+    }
+
+    fn orientation_names(capabilities: &PrintCapabilities) -> Vec<Option<String>> {
+        capabilities
+            .page_orientations()
+            .map(|x| x.display_name().map(str::to_string))
+            .collect()
+    }
+
+    #[test]
+    fn fallback_chain() {
+        let resources = device_resources();
+        let languages = |locale: &str| {
+            resources
+                .fallback_chain(locale)
+                .iter()
+                .map(|x| x.language.as_deref())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(languages("de_de"), vec![Some("de-DE"), Some("de"), None]);
+        assert_eq!(languages("de-AT"), vec![Some("de"), None]);
+        assert_eq!(languages("fr-FR"), vec![None]);
+    }
+
+    #[test]
+    fn localize_display_names() {
+        let resources = device_resources();
+        let capabilities = capabilities(&resources, "de-DE");
+        assert_eq!(
+            orientation_names(&capabilities),
+            vec![
+                Some("Hochformat".to_string()),
+                Some("Querformat".to_string())
+            ]
+        );
+        let a4 = capabilities
+            .page_media_sizes()
+            .find(|x| x.option().name.as_ref().unwrap().local_name == "ISOA4")
+            .unwrap();
+        assert_eq!(a4.display_name(), Some("DIN A4"));
+        let copies = &capabilities.document.parameter_defs[0];
+        assert_eq!(super::display_name(&copies.properties), Some("Kopien"));
+
+        let capabilities = self::capabilities(&resources, "en-US");
+        assert_eq!(
+            orientation_names(&capabilities),
+            vec![Some("Portrait".to_string()), Some("Landscape".to_string())]
+        );
+        let copies = &capabilities.document.parameter_defs[0];
+        assert_eq!(super::display_name(&copies.properties), Some("Copies"));
+
+        let capabilities = self::capabilities(&PrintDeviceResources::new(), "en-US");
+        assert_eq!(orientation_names(&capabilities), vec![None, None]);
+    }
+}
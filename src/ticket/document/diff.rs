@@ -0,0 +1,512 @@
+use super::{
+    same_name, same_value, ParameterDef, ParameterInit, PrintCapabilitiesDocument, PrintFeature,
+    PrintFeatureOption, PrintTicketDocument, Property, PropertyValue, ScoredProperty,
+};
+use std::fmt;
+use xml::name::OwnedName;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// Represents the kind of a node in a Print Schema document.
+pub enum NodeKind {
+    /// A [`PrintFeature`].
+    Feature,
+    /// A [`PrintFeatureOption`].
+    Option,
+    /// A [`ScoredProperty`].
+    ScoredProperty,
+    /// A [`Property`].
+    Property,
+    /// A [`ParameterInit`].
+    ParameterInit,
+    /// A [`ParameterDef`].
+    ParameterDef,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Represents a segment of the path to a node in a Print Schema document.
+pub struct PathSegment {
+    /// The kind of the node.
+    pub kind: NodeKind,
+    /// The name of the node, if it has one.
+    pub name: Option<OwnedName>,
+    /// The index of the node among its siblings with the same kind and name.
+    pub index: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Represents a change between two Print Schema documents.
+pub enum DiffChange {
+    /// The node only exists in the new document.
+    Added,
+    /// The node only exists in the old document.
+    Removed,
+    /// The value of the node changed.
+    ValueChanged {
+        /// The value in the old document.
+        old: Option<PropertyValue>,
+        /// The value in the new document.
+        new: Option<PropertyValue>,
+    },
+    /// The parameter referenced by the scored-property changed.
+    ParameterRefChanged {
+        /// The parameter referenced in the old document.
+        old: Option<OwnedName>,
+        /// The parameter referenced in the new document.
+        new: Option<OwnedName>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Represents a difference between two Print Schema documents.
+pub struct DiffEntry {
+    /// The path to the node, from the outermost one.
+    pub path: Vec<PathSegment>,
+    /// The change of the node.
+    pub change: DiffChange,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+/// Represents the structural differences between two Print Schema documents.
+///
+/// Nodes are matched by kind and qualified name, ignoring namespace prefixes.
+/// Added or removed nodes are reported once, without their descendants.
+///
+/// The [`Display`](fmt::Display) implementation renders a readable text report, one line per difference.
+pub struct DocumentDiff {
+    /// The differences, in document order.
+    pub entries: Vec<DiffEntry>,
+}
+
+impl DocumentDiff {
+    /// Determine if the documents are structurally equal.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl PrintTicketDocument {
+    /// Compare this document (as the old one) with the given document (as the new one).
+    pub fn diff(&self, new: &PrintTicketDocument) -> DocumentDiff {
+        let mut diff = DocumentDiff::default();
+        diff_children(
+            &mut vec![],
+            &ticket_children(self),
+            &ticket_children(new),
+            &mut diff.entries,
+        );
+        diff
+    }
+}
+
+impl PrintCapabilitiesDocument {
+    /// Compare this document (as the old one) with the given document (as the new one).
+    pub fn diff(&self, new: &PrintCapabilitiesDocument) -> DocumentDiff {
+        let mut diff = DocumentDiff::default();
+        diff_children(
+            &mut vec![],
+            &capabilities_children(self),
+            &capabilities_children(new),
+            &mut diff.entries,
+        );
+        diff
+    }
+}
+
+/// A uniform view of the nodes, so that all kinds can be compared in the same way.
+struct Node<'a> {
+    kind: NodeKind,
+    name: Option<&'a OwnedName>,
+    value: Option<&'a PropertyValue>,
+    parameter_ref: Option<&'a OwnedName>,
+    children: Vec<Node<'a>>,
+}
+
+impl<'a> Node<'a> {
+    fn new(kind: NodeKind, name: Option<&'a OwnedName>) -> Self {
+        Self {
+            kind,
+            name,
+            value: None,
+            parameter_ref: None,
+            children: vec![],
+        }
+    }
+}
+
+fn ticket_children(document: &PrintTicketDocument) -> Vec<Node<'_>> {
+    let mut result = vec![];
+    result.extend(document.properties.iter().map(property_node));
+    result.extend(document.parameter_inits.iter().map(parameter_init_node));
+    result.extend(document.features.iter().map(feature_node));
+    result
+}
+
+fn capabilities_children(document: &PrintCapabilitiesDocument) -> Vec<Node<'_>> {
+    let mut result = vec![];
+    result.extend(document.properties.iter().map(property_node));
+    result.extend(document.parameter_defs.iter().map(parameter_def_node));
+    result.extend(document.features.iter().map(feature_node));
+    result
+}
+
+fn feature_node(feature: &PrintFeature) -> Node<'_> {
+    let mut node = Node::new(NodeKind::Feature, Some(&feature.name));
+    node.children
+        .extend(feature.properties.iter().map(property_node));
+    node.children
+        .extend(feature.options.iter().map(option_node));
+    node.children
+        .extend(feature.features.iter().map(feature_node));
+    node
+}
+
+fn option_node(option: &PrintFeatureOption) -> Node<'_> {
+    let mut node = Node::new(NodeKind::Option, option.name.as_ref());
+    node.children
+        .extend(option.scored_properties.iter().map(scored_property_node));
+    node.children
+        .extend(option.properties.iter().map(property_node));
+    node
+}
+
+fn scored_property_node(scored_property: &ScoredProperty) -> Node<'_> {
+    let mut node = Node::new(NodeKind::ScoredProperty, scored_property.name.as_ref());
+    node.value = scored_property.value.as_ref();
+    node.parameter_ref = scored_property.parameter_ref.as_ref();
+    node.children.extend(
+        scored_property
+            .scored_properties
+            .iter()
+            .map(scored_property_node),
+    );
+    node.children
+        .extend(scored_property.properties.iter().map(property_node));
+    node
+}
+
+fn property_node(property: &Property) -> Node<'_> {
+    let mut node = Node::new(NodeKind::Property, Some(&property.name));
+    node.value = property.value.as_ref();
+    node.children
+        .extend(property.properties.iter().map(property_node));
+    node
+}
+
+fn parameter_init_node(parameter_init: &ParameterInit) -> Node<'_> {
+    let mut node = Node::new(NodeKind::ParameterInit, Some(&parameter_init.name));
+    node.value = Some(&parameter_init.value);
+    node
+}
+
+fn parameter_def_node(parameter_def: &ParameterDef) -> Node<'_> {
+    let mut node = Node::new(NodeKind::ParameterDef, Some(&parameter_def.name));
+    node.children
+        .extend(parameter_def.properties.iter().map(property_node));
+    node
+}
+
+fn same_optional_name(a: Option<&OwnedName>, b: Option<&OwnedName>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => same_name(a, b),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+fn same_optional_value(a: Option<&PropertyValue>, b: Option<&PropertyValue>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => same_value(a, b),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// Compute the index of each node among its siblings with the same kind and name.
+fn sibling_indices(nodes: &[Node<'_>]) -> Vec<usize> {
+    nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| {
+            nodes[..i]
+                .iter()
+                .filter(|x| x.kind == node.kind && same_optional_name(x.name, node.name))
+                .count()
+        })
+        .collect()
+}
+
+fn segment(node: &Node<'_>, index: usize) -> PathSegment {
+    PathSegment {
+        kind: node.kind,
+        name: node.name.cloned(),
+        index,
+    }
+}
+
+fn diff_children(
+    path: &mut Vec<PathSegment>,
+    old: &[Node<'_>],
+    new: &[Node<'_>],
+    entries: &mut Vec<DiffEntry>,
+) {
+    let old_indices = sibling_indices(old);
+    let new_indices = sibling_indices(new);
+    let find_in = |nodes: &[Node<'_>], indices: &[usize], node: &Node<'_>, index: usize| {
+        nodes.iter().zip(indices).position(|(x, i)| {
+            x.kind == node.kind && same_optional_name(x.name, node.name) && *i == index
+        })
+    };
+
+    for (old_node, &index) in old.iter().zip(&old_indices) {
+        path.push(segment(old_node, index));
+        match find_in(new, &new_indices, old_node, index) {
+            Some(position) => diff_node(path, old_node, &new[position], entries),
+            None => entries.push(DiffEntry {
+                path: path.clone(),
+                change: DiffChange::Removed,
+            }),
+        }
+        path.pop();
+    }
+    for (new_node, &index) in new.iter().zip(&new_indices) {
+        if find_in(old, &old_indices, new_node, index).is_none() {
+            path.push(segment(new_node, index));
+            entries.push(DiffEntry {
+                path: path.clone(),
+                change: DiffChange::Added,
+            });
+            path.pop();
+        }
+    }
+}
+
+fn diff_node(
+    path: &mut Vec<PathSegment>,
+    old: &Node<'_>,
+    new: &Node<'_>,
+    entries: &mut Vec<DiffEntry>,
+) {
+    if !same_optional_value(old.value, new.value) {
+        entries.push(DiffEntry {
+            path: path.clone(),
+            change: DiffChange::ValueChanged {
+                old: old.value.cloned(),
+                new: new.value.cloned(),
+            },
+        });
+    }
+    if !same_optional_name(old.parameter_ref, new.parameter_ref) {
+        entries.push(DiffEntry {
+            path: path.clone(),
+            change: DiffChange::ParameterRefChanged {
+                old: old.parameter_ref.cloned(),
+                new: new.parameter_ref.cloned(),
+            },
+        });
+    }
+    diff_children(path, &old.children, &new.children, entries);
+}
+
+/// Format the name for humans, with the prefix if present, or the namespace URI otherwise.
+pub(crate) fn fmt_name(name: &OwnedName, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match (name.prefix_ref(), name.namespace_ref()) {
+        (Some(prefix), _) => write!(f, "{}:{}", prefix, name.local_name),
+        (None, Some(namespace)) => write!(f, "{{{}}}{}", namespace, name.local_name),
+        (None, None) => write!(f, "{}", name.local_name),
+    }
+}
+
+/// Format the value for humans, with strings quoted and qualified names as [`fmt_name`].
+pub(crate) fn fmt_optional_value(
+    value: Option<&PropertyValue>,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    match value {
+        Some(PropertyValue::String(s)) => write!(f, "{:?}", s),
+        Some(PropertyValue::Integer(i)) => write!(f, "{}", i),
+        Some(PropertyValue::BigInteger(i)) => write!(f, "{}", i),
+        Some(PropertyValue::Decimal(d)) => write!(f, "{}", d),
+        Some(PropertyValue::Boolean(b)) => write!(f, "{}", b),
+        Some(PropertyValue::Base64Binary(b)) => write!(f, "<{} bytes>", b.len()),
+        Some(PropertyValue::DateTime(d)) => write!(f, "{}", d),
+        Some(PropertyValue::QName(q)) => fmt_name(q, f),
+        Some(PropertyValue::Unknown(t, s)) => {
+            write!(f, "{:?} (", s)?;
+            fmt_name(t, f)?;
+            write!(f, ")")
+        }
+        None => write!(f, "<none>"),
+    }
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.kind)?;
+        if let Some(name) = &self.name {
+            write!(f, "(")?;
+            fmt_name(name, f)?;
+            write!(f, ")")?;
+        }
+        if self.name.is_none() || self.index > 0 {
+            write!(f, "[{}]", self.index)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for DiffEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = match self.change {
+            DiffChange::Added => '+',
+            DiffChange::Removed => '-',
+            DiffChange::ValueChanged { .. } | DiffChange::ParameterRefChanged { .. } => '~',
+        };
+        write!(f, "{} ", sign)?;
+        for (i, segment) in self.path.iter().enumerate() {
+            if i > 0 {
+                write!(f, "/")?;
+            }
+            write!(f, "{}", segment)?;
+        }
+        match &self.change {
+            DiffChange::Added | DiffChange::Removed => Ok(()),
+            DiffChange::ValueChanged { old, new } => {
+                write!(f, ": ")?;
+                fmt_optional_value(old.as_ref(), f)?;
+                write!(f, " -> ")?;
+                fmt_optional_value(new.as_ref(), f)
+            }
+            DiffChange::ParameterRefChanged { old, new } => {
+                write!(f, ": ParameterRef ")?;
+                match old {
+                    Some(old) => fmt_name(old, f)?,
+                    None => write!(f, "<none>")?,
+                }
+                write!(f, " -> ")?;
+                match new {
+                    Some(new) => fmt_name(new, f),
+                    None => write!(f, "<none>"),
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for DocumentDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            writeln!(f, "{}", entry)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DiffChange, NodeKind};
+    use crate::ticket::document::{
+        reader::ParsableXmlDocument, PrintTicketDocument, PropertyValue,
+    };
+    use crate::ticket::test_fixtures::sample_capabilities_document;
+
+    fn ticket(prefix: &str, body: &str) -> PrintTicketDocument {
+        let xml = format!(
+            r#"<psf:PrintTicket version="1"
+    xmlns:psf="http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework"
+    xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+    xmlns:xsd="http://www.w3.org/2001/XMLSchema"
+    xmlns:{}="http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords">
+    {}
+</psf:PrintTicket>"#,
+            prefix, body
+        );
+        PrintTicketDocument::parse_from_bytes(xml).unwrap()
+    }
+
+    #[test]
+    fn same_document_has_no_diff() {
+        let document = sample_capabilities_document();
+        assert!(document.diff(&document).is_empty());
+    }
+
+    #[test]
+    fn prefixes_are_ignored() {
+        let a = ticket(
+            "psk",
+            r#"<psf:Feature name="psk:PageMediaSize"><psf:Option name="psk:ISOA4" /></psf:Feature>"#,
+        );
+        let b = ticket(
+            "ns0000",
+            r#"<psf:Feature name="ns0000:PageMediaSize"><psf:Option name="ns0000:ISOA4" /></psf:Feature>"#,
+        );
+        assert!(a.diff(&b).is_empty());
+    }
+
+    #[test]
+    fn changes_are_reported() {
+        let a = ticket(
+            "psk",
+            r#"<psf:ParameterInit name="psk:JobCopiesAllDocuments">
+        <psf:Value xsi:type="xsd:integer">1</psf:Value>
+    </psf:ParameterInit>
+    <psf:Feature name="psk:PageMediaSize"><psf:Option name="psk:ISOA4" /></psf:Feature>"#,
+        );
+        let b = ticket(
+            "psk",
+            r#"<psf:ParameterInit name="psk:JobCopiesAllDocuments">
+        <psf:Value xsi:type="xsd:integer">2</psf:Value>
+    </psf:ParameterInit>
+    <psf:Feature name="psk:PageMediaSize"><psf:Option name="psk:ISOA3" /></psf:Feature>"#,
+        );
+        let diff = a.diff(&b);
+        assert_eq!(diff.entries.len(), 3);
+        assert_eq!(diff.entries[0].path[0].kind, NodeKind::ParameterInit);
+        assert_eq!(
+            diff.entries[0].change,
+            DiffChange::ValueChanged {
+                old: Some(PropertyValue::Integer(1)),
+                new: Some(PropertyValue::Integer(2)),
+            }
+        );
+        assert_eq!(diff.entries[1].change, DiffChange::Removed);
+        assert_eq!(diff.entries[2].change, DiffChange::Added);
+        assert_eq!(
+            diff.to_string(),
+            "~ ParameterInit(psk:JobCopiesAllDocuments): 1 -> 2\n\
+             - Feature(psk:PageMediaSize)/Option(psk:ISOA4)\n\
+             + Feature(psk:PageMediaSize)/Option(psk:ISOA3)\n"
+        );
+    }
+
+    #[test]
+    fn unnamed_options_are_matched_by_position() {
+        let option = |pages: i32| {
+            format!(
+                r#"<psf:Option>
+            <psf:ScoredProperty name="psk:PagesPerSheet">
+                <psf:Value xsi:type="xsd:integer">{}</psf:Value>
+            </psf:ScoredProperty>
+        </psf:Option>"#,
+                pages
+            )
+        };
+        let a = ticket(
+            "psk",
+            &format!(
+                r#"<psf:Feature name="psk:JobNUpAllDocumentsContiguously">{}</psf:Feature>"#,
+                option(2)
+            ),
+        );
+        let b = ticket(
+            "psk",
+            &format!(
+                r#"<psf:Feature name="psk:JobNUpAllDocumentsContiguously">{}</psf:Feature>"#,
+                option(4)
+            ),
+        );
+        assert_eq!(
+            a.diff(&b).to_string(),
+            "~ Feature(psk:JobNUpAllDocumentsContiguously)/Option[0]/ScoredProperty(psk:PagesPerSheet): 2 -> 4\n"
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use super::{
+    print_schema::same_name, ParameterInit, PrintFeature, PrintFeatureOption, PrintTicketDocument,
+    PropertyValue,
+};
+use xml::name::OwnedName;
+
+impl PrintTicketDocument {
+    /// Get the top-level feature with the given name, compared by namespace and local name.
+    pub fn feature(&self, name: &OwnedName) -> Option<&PrintFeature> {
+        self.features.iter().find(|x| same_name(&x.name, name))
+    }
+
+    /// Get the option selected for the top-level feature with the given name.
+    pub fn selected_option(&self, feature: &OwnedName) -> Option<&PrintFeatureOption> {
+        self.feature(feature).and_then(|x| x.options.first())
+    }
+
+    /// Select the option for the top-level feature with the given name, replacing any existing option.
+    ///
+    /// Properties and sub-features of an existing feature are kept, and duplicates of the feature are removed.
+    /// The feature is appended if it does not exist.
+    pub fn set_feature_option(&mut self, feature: OwnedName, option: PrintFeatureOption) {
+        let mut found = false;
+        self.features.retain_mut(|x| {
+            if !same_name(&x.name, &feature) {
+                return true;
+            }
+            if found {
+                return false;
+            }
+            found = true;
+            x.options = vec![option.clone()];
+            true
+        });
+        if !found {
+            self.features
+                .push(PrintFeature::new(feature, vec![], vec![option], vec![]));
+        }
+    }
+
+    /// Remove the top-level feature with the given name, returning the first removed one.
+    pub fn remove_feature(&mut self, feature: &OwnedName) -> Option<PrintFeature> {
+        let mut removed = None;
+        self.features.retain(|x| {
+            if !same_name(&x.name, feature) {
+                return true;
+            }
+            if removed.is_none() {
+                removed = Some(x.clone());
+            }
+            false
+        });
+        removed
+    }
+
+    /// Get the value of the parameter with the given name.
+    pub fn parameter_value(&self, name: &OwnedName) -> Option<&PropertyValue> {
+        self.parameter_inits
+            .iter()
+            .find(|x| same_name(&x.name, name))
+            .map(|x| &x.value)
+    }
+
+    /// Set the value of the parameter with the given name, replacing any existing initialization.
+    pub fn set_parameter_init(&mut self, name: OwnedName, value: PropertyValue) {
+        self.remove_parameter_init(&name);
+        self.parameter_inits.push(ParameterInit::new(name, value));
+    }
+
+    /// Remove the initialization of the parameter with the given name, returning the first removed one.
+    pub fn remove_parameter_init(&mut self, name: &OwnedName) -> Option<ParameterInit> {
+        let mut removed = None;
+        self.parameter_inits.retain(|x| {
+            if !same_name(&x.name, name) {
+                return true;
+            }
+            if removed.is_none() {
+                removed = Some(x.clone());
+            }
+            false
+        });
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ticket::document::{PrintFeatureOption, PropertyValue, NS_PSK};
+    use crate::ticket::test_fixtures::sample_ticket_document;
+    use xml::name::OwnedName;
+
+    fn psk(name: &str) -> OwnedName {
+        OwnedName::qualified(name, NS_PSK, Some("psk"))
+    }
+
+    fn option(name: &str) -> PrintFeatureOption {
+        PrintFeatureOption::new(Some(psk(name)), vec![], vec![])
+    }
+
+    #[test]
+    fn set_and_remove_feature_option() {
+        let mut ticket = sample_ticket_document();
+        let media_size = OwnedName::qualified("PageMediaSize", NS_PSK, Some("other"));
+        let count = ticket.features.len();
+
+        ticket.set_feature_option(media_size.clone(), option("ISOA4"));
+        assert_eq!(ticket.features.len(), count);
+        let feature = ticket.feature(&media_size).unwrap();
+        assert_eq!(feature.options, vec![option("ISOA4")]);
+        assert_eq!(feature.name.prefix_ref(), Some("psk"));
+
+        let mut duplicated = ticket.feature(&media_size).unwrap().clone();
+        duplicated.options.push(option("ISOA3"));
+        ticket.features.push(duplicated);
+        ticket.set_feature_option(media_size.clone(), option("ISOA3"));
+        assert_eq!(ticket.features.len(), count);
+        assert_eq!(ticket.selected_option(&media_size), Some(&option("ISOA3")));
+
+        assert!(ticket.remove_feature(&media_size).is_some());
+        assert!(ticket.feature(&media_size).is_none());
+        assert!(ticket.remove_feature(&media_size).is_none());
+
+        ticket.set_feature_option(media_size.clone(), option("ISOA4"));
+        assert_eq!(ticket.features.len(), count);
+        assert_eq!(
+            ticket.features.last().unwrap().name.prefix_ref(),
+            Some("other")
+        );
+    }
+
+    #[test]
+    fn set_and_remove_parameter_init() {
+        let mut ticket = sample_ticket_document();
+        let copies = psk("JobCopiesAllDocuments");
+        let count = ticket.parameter_inits.len();
+
+        ticket.set_parameter_init(copies.clone(), PropertyValue::Integer(3));
+        ticket.set_parameter_init(copies.clone(), PropertyValue::Integer(5));
+        assert_eq!(
+            ticket.parameter_value(&copies),
+            Some(&PropertyValue::Integer(5))
+        );
+        assert_eq!(ticket.parameter_inits.len(), count + 1);
+
+        let width = psk("PageMediaSizeMediaSizeWidth");
+        ticket.set_parameter_init(width.clone(), PropertyValue::Integer(100000));
+        assert_eq!(ticket.parameter_inits.len(), count + 1);
+        assert_eq!(
+            ticket.parameter_value(&width),
+            Some(&PropertyValue::Integer(100000))
+        );
+
+        let removed = ticket.remove_parameter_init(&copies).unwrap();
+        assert_eq!(removed.value, PropertyValue::Integer(5));
+        assert!(ticket.parameter_value(&copies).is_none());
+    }
+}
@@ -0,0 +1,104 @@
+use xml::{attribute::OwnedAttribute, name::OwnedName};
+
+#[derive(Clone, Default, PartialEq, Eq, Hash, Debug)]
+/// Represents the XML content of an element which is not part of the Print Schema model.
+///
+/// It is only filled when the document is parsed in lossless mode (see [`ParseOptions::lossless`](super::reader::ParseOptions::lossless)),
+/// and is used by the writer to reproduce the original document.
+/// Elements created by hand have an empty one, and are written in the default layout.
+/// It is left out when the DOM structs are compared or hashed, so lossless and normal parsing give equal documents.
+pub struct XmlExtra {
+    /// Namespace declarations of the element, as `(prefix, uri)` pairs.
+    pub namespaces: Vec<(String, String)>,
+    /// All attributes of the element, in document order.
+    ///
+    /// The attributes that are part of the model, such as `name`, are written with their current value.
+    pub attributes: Vec<OwnedAttribute>,
+    /// The children of the element, in document order.
+    pub content: Vec<XmlContent>,
+    /// Comments and processing instructions before the root element. Only used by document roots.
+    pub prolog: Vec<XmlNode>,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+/// Represents a child of an element in [`XmlExtra::content`].
+pub enum XmlContent {
+    /// A placeholder for the next child of the given kind in the model.
+    Schema(SchemaElement),
+    /// A node which is not part of the model.
+    Node(XmlNode),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// Represents the kind of a Print Schema Framework element.
+pub enum SchemaElement {
+    /// `psf:ParameterDef`
+    ParameterDef,
+    /// `psf:ParameterInit`
+    ParameterInit,
+    /// `psf:Feature`
+    Feature,
+    /// `psf:Option`
+    Option,
+    /// `psf:ScoredProperty`
+    ScoredProperty,
+    /// `psf:Property`
+    Property,
+    /// `psf:ParameterRef`
+    ParameterRef,
+    /// `psf:Value`
+    Value,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+/// Represents a raw XML node.
+pub enum XmlNode {
+    /// An element, such as the ones in a driver private namespace.
+    Element(XmlElement),
+    /// Text content.
+    Characters(String),
+    /// Whitespace-only text content.
+    Whitespace(String),
+    /// A CDATA section.
+    CData(String),
+    /// A comment.
+    Comment(String),
+    /// A processing instruction.
+    ProcessingInstruction {
+        /// The target of the processing instruction.
+        name: String,
+        /// The data of the processing instruction.
+        data: Option<String>,
+    },
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+/// Represents a raw XML element.
+pub struct XmlElement {
+    /// The name of the element.
+    pub name: OwnedName,
+    /// Namespace declarations of the element, as `(prefix, uri)` pairs.
+    pub namespaces: Vec<(String, String)>,
+    /// Attributes of the element, in document order.
+    pub attributes: Vec<OwnedAttribute>,
+    /// Children of the element, in document order.
+    pub children: Vec<XmlNode>,
+}
+
+impl XmlExtra {
+    /// Check whether there is nothing to preserve.
+    pub fn is_empty(&self) -> bool {
+        self.namespaces.is_empty()
+            && self.attributes.is_empty()
+            && self.content.is_empty()
+            && self.prolog.is_empty()
+    }
+
+    /// Iterate over the nodes which are not part of the model, excluding the prolog.
+    pub fn nodes(&self) -> impl Iterator<Item = &XmlNode> {
+        self.content.iter().filter_map(|x| match x {
+            XmlContent::Node(node) => Some(node),
+            XmlContent::Schema(_) => None,
+        })
+    }
+}
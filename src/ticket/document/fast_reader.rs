@@ -0,0 +1,723 @@
+use super::{
+    reader::{
+        parse_qname_with, ParseDiagnostic, ParseOptions, ParsePrintSchemaError, SchemaBuilder,
+        SchemaStart,
+    },
+    PrintSchemaDocument, NS_PSF, NS_XSD, NS_XSI,
+};
+use quick_xml::{
+    escape::{resolve_predefined_entity, unescape},
+    events::{attributes::Attribute, BytesStart, Event},
+    name::{PrefixDeclaration, QName},
+    Reader,
+};
+use std::{borrow::Cow, ops::Range};
+use xml::{
+    attribute::OwnedAttribute,
+    common::{Position, TextPosition},
+    name::{Name, OwnedName},
+    namespace::{Namespace, NS_XML_PREFIX, NS_XML_URI},
+    reader::XmlEvent,
+};
+
+/// Parse a document, borrowing names and text from the input unless it is kept losslessly.
+///
+/// The result is the same as the [`xml`] based reader, including the diagnostics and their positions.
+/// `None` is returned for any input which is not well-formed, including input in other encodings than UTF-8,
+/// so that the caller may fall back to the [`xml`] based reader, which reports the error.
+pub(super) fn parse(
+    xml: &[u8],
+    options: &ParseOptions,
+) -> Option<Result<(PrintSchemaDocument, Vec<ParseDiagnostic>), ParsePrintSchemaError>> {
+    let xml = std::str::from_utf8(xml).ok()?;
+    let mut state = ReaderState::new(options);
+    let mut events = Events::resume(xml, &mut state);
+    let mut builder = SchemaBuilder::new(options);
+    loop {
+        let result = events
+            .next()
+            .and_then(|event| events.feed(&mut builder, event));
+        match result {
+            Ok(Some(document)) => return Some(Ok((document, builder.take_diagnostics()))),
+            Ok(None) => {}
+            Err(ReadError::Malformed) => return None,
+            Err(ReadError::Schema(e)) => return Some(Err(e)),
+        }
+    }
+}
+
+/// Read the input with the [`xml`] based reader, to report why it is not well-formed.
+pub(super) fn xml_error(xml: &[u8], options: &ParseOptions) -> ParsePrintSchemaError {
+    let mut reader = options.parser_config().create_reader(xml);
+    loop {
+        match reader.next() {
+            Ok(XmlEvent::EndDocument) => {
+                return ParsePrintSchemaError::InvalidPrintSchema {
+                    pos: reader.position(),
+                    reason: "Unsupported XML input".to_string(),
+                }
+            }
+            Ok(_) => {}
+            Err(e) => return ParsePrintSchemaError::InvalidXml(e),
+        }
+    }
+}
+
+/// An error while reading with [`Events`].
+pub(super) enum ReadError {
+    /// The input is not well-formed, which is reported by [`xml_error`].
+    Malformed,
+    Schema(ParsePrintSchemaError),
+}
+
+impl From<ParsePrintSchemaError> for ReadError {
+    fn from(value: ParsePrintSchemaError) -> Self {
+        Self::Schema(value)
+    }
+}
+
+fn malformed<E>(_: E) -> ReadError {
+    ReadError::Malformed
+}
+
+/// The state of a document read by [`Events`], which is kept when a new reader resumes at the next event.
+pub(super) struct ReaderState {
+    /// The offset where the next event starts.
+    offset: usize,
+    lossless: bool,
+    scope: Scope,
+    lines: LineCounter,
+    /// The names of the open elements, as ranges of the input, for checking the end tags.
+    open: Vec<Range<usize>>,
+    /// The text which is not handled yet, as the [`xml`] based reader coalesces text and entity references.
+    text: String,
+}
+
+impl ReaderState {
+    pub(super) fn new(options: &ParseOptions) -> Self {
+        Self {
+            offset: 0,
+            lossless: options.is_lossless(),
+            scope: Scope::default(),
+            lines: LineCounter::new(),
+            open: Vec::new(),
+            text: String::new(),
+        }
+    }
+}
+
+/// Reads the events of a UTF-8 document with quick-xml, tracking the namespaces, the open elements and the positions
+/// as the [`xml`] based reader does.
+pub(super) struct Events<'a, 's> {
+    xml: &'a str,
+    reader: Reader<&'a [u8]>,
+    state: &'s mut ReaderState,
+    /// The offset where the reader starts.
+    base: usize,
+    /// The offset where the last event starts.
+    start: usize,
+}
+
+impl<'a, 's> Events<'a, 's> {
+    /// Create a reader which continues where the last reader with the same state stopped.
+    pub(super) fn resume(xml: &'a str, state: &'s mut ReaderState) -> Self {
+        let base = state.offset;
+        let mut reader = Reader::from_str(&xml[base..]);
+        // the end tags are checked against the open elements of the state, which outlive the reader
+        let config = reader.config_mut();
+        config.check_end_names = false;
+        config.allow_unmatched_ends = true;
+        Self {
+            xml,
+            reader,
+            state,
+            base,
+            start: base,
+        }
+    }
+
+    fn offset(&self) -> usize {
+        self.base + self.reader.buffer_position() as usize
+    }
+
+    /// Get the number of open elements.
+    pub(super) fn depth(&self) -> usize {
+        self.state.open.len()
+    }
+
+    /// Get the position of the last event.
+    pub(super) fn position(&mut self) -> TextPosition {
+        self.state.lines.position(self.xml, self.start)
+    }
+
+    /// Read the next event, without decoding its content.
+    pub(super) fn next(&mut self) -> Result<Event<'a>, ReadError> {
+        self.start = self.offset();
+        let event = self.reader.read_event().map_err(malformed)?;
+        self.state.offset = self.offset();
+        match &event {
+            Event::Start(element) => {
+                let name = self.start + 1;
+                self.state
+                    .open
+                    .push(name..name + element.name().as_ref().len());
+            }
+            Event::End(element) => {
+                let name = self.state.open.pop().ok_or(ReadError::Malformed)?;
+                if self.xml.as_bytes()[name] != *element.name().as_ref() {
+                    return Err(ReadError::Malformed);
+                }
+            }
+            Event::Eof if !self.state.open.is_empty() => return Err(ReadError::Malformed),
+            _ => {}
+        }
+        Ok(event)
+    }
+
+    /// Skip the content of the element which was just started, counting the depth of the events.
+    pub(super) fn skip(&mut self) -> Result<(), ReadError> {
+        let depth = self.depth();
+        while self.depth() >= depth {
+            self.next()?;
+        }
+        Ok(())
+    }
+
+    /// Keep the namespaces declared by an element which is not handled by [`Events::feed`] in scope.
+    pub(super) fn enter(&mut self, element: &BytesStart<'_>) -> Result<(), ReadError> {
+        self.state.scope.enter(element)
+    }
+
+    /// Get the name of an element with its namespace, and its `name` attribute if it is a Print Schema element.
+    ///
+    /// The namespaces declared by the element are in scope while the name is resolved.
+    pub(super) fn element_name(
+        &mut self,
+        element: &BytesStart<'_>,
+    ) -> Result<(OwnedName, Option<OwnedName>), ReadError> {
+        self.state.scope.enter(element)?;
+        let scope = &self.state.scope;
+        let result = scope.name(element.name(), true).and_then(|name| {
+            let (schema_name, _) = match name.namespace {
+                Some(NS_PSF) => scope.schema_attributes(element)?,
+                _ => (None, None),
+            };
+            Ok((name.to_owned(), schema_name))
+        });
+        self.state.scope.exit();
+        result
+    }
+
+    /// Handle an event with the builder, and return the document when its root element is closed.
+    pub(super) fn feed(
+        &mut self,
+        builder: &mut SchemaBuilder,
+        event: Event<'a>,
+    ) -> Result<Option<PrintSchemaDocument>, ReadError> {
+        let lossless = self.state.lossless;
+        match event {
+            Event::Text(e) => {
+                let text = e.xml10_content().map_err(malformed)?;
+                self.state.text.push_str(&text);
+                return Ok(None);
+            }
+            Event::GeneralRef(e) => {
+                match e.resolve_char_ref().map_err(malformed)? {
+                    Some(c) => self.state.text.push(c),
+                    None => {
+                        let name = e.decode().map_err(malformed)?;
+                        let text = resolve_predefined_entity(&name).ok_or(ReadError::Malformed)?;
+                        self.state.text.push_str(text);
+                    }
+                }
+                return Ok(None);
+            }
+            // comments are ignored by the xml based reader, so they do not split the text
+            Event::Comment(_) if !lossless => return Ok(None),
+            _ => {}
+        }
+
+        let pos = self.position();
+        self.flush_text(builder, pos)?;
+        match event {
+            Event::Start(element) => self.start_element(builder, &element, pos)?,
+            Event::Empty(element) => {
+                // the xml based reader reports both events at the start of the element
+                self.start_element(builder, &element, pos)?;
+                return self.end_element(builder, element.name(), pos);
+            }
+            Event::End(element) => return self.end_element(builder, element.name(), pos),
+            Event::CData(e) => {
+                let text = e.xml10_content().map_err(malformed)?;
+                if lossless {
+                    builder.handle(XmlEvent::CData(text.into_owned()), pos)?;
+                } else {
+                    builder.characters(&text);
+                }
+            }
+            Event::Comment(e) => {
+                let text = e.xml10_content().map_err(malformed)?;
+                builder.handle(XmlEvent::Comment(text.into_owned()), pos)?;
+            }
+            Event::PI(e) if lossless => {
+                let name = std::str::from_utf8(e.target()).map_err(malformed)?;
+                let data = std::str::from_utf8(e.content())
+                    .map_err(malformed)?
+                    .trim_start();
+                let event = XmlEvent::ProcessingInstruction {
+                    name: name.to_string(),
+                    data: (!data.is_empty()).then(|| data.to_string()),
+                };
+                builder.handle(event, pos)?;
+            }
+            Event::Eof => return Err(ReadError::Malformed),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn flush_text(
+        &mut self,
+        builder: &mut SchemaBuilder,
+        pos: TextPosition,
+    ) -> Result<(), ReadError> {
+        if self.state.text.is_empty() {
+            return Ok(());
+        }
+        let whitespace = self
+            .state
+            .text
+            .bytes()
+            .all(|x| matches!(x, b' ' | b'\t' | b'\r' | b'\n'));
+        if self.state.lossless {
+            let text = std::mem::take(&mut self.state.text);
+            let event = if whitespace {
+                XmlEvent::Whitespace(text)
+            } else {
+                XmlEvent::Characters(text)
+            };
+            builder.handle(event, pos)?;
+            return Ok(());
+        }
+        if whitespace {
+            builder.whitespace();
+        } else {
+            builder.characters(&self.state.text);
+        }
+        self.state.text.clear();
+        Ok(())
+    }
+
+    fn start_element(
+        &mut self,
+        builder: &mut SchemaBuilder,
+        element: &BytesStart<'_>,
+        pos: TextPosition,
+    ) -> Result<(), ReadError> {
+        let scope = &mut self.state.scope;
+        scope.enter(element)?;
+        if self.state.lossless {
+            let mut attributes = Vec::new();
+            for attribute in element.attributes() {
+                let attribute = attribute.map_err(malformed)?;
+                if attribute.key.as_namespace_binding().is_none() {
+                    attributes.push(OwnedAttribute {
+                        name: scope.name(attribute.key, false)?.to_owned(),
+                        value: attribute_value(&attribute)?.into_owned(),
+                    });
+                }
+            }
+            let event = XmlEvent::StartElement {
+                name: scope.name(element.name(), true)?.to_owned(),
+                attributes,
+                namespace: scope.to_namespace(),
+            };
+            builder.handle(event, pos)?;
+            return Ok(());
+        }
+
+        let display = scope.name(element.name(), true)?;
+        if display.namespace != Some(NS_PSF) {
+            builder.start_element(None, pos)?;
+            return Ok(());
+        }
+        let (name, value_type) = scope.schema_attributes(element)?;
+        let namespace = value_type
+            .as_ref()
+            .is_some_and(|x| x.namespace_ref() == Some(NS_XSD) && x.local_name == "QName")
+            .then(|| scope.to_namespace());
+        let start = SchemaStart {
+            local_name: display.local_name,
+            display: &display,
+            name,
+            value_type,
+            namespace,
+        };
+        builder.start_element(Some(start), pos)?;
+        Ok(())
+    }
+
+    fn end_element(
+        &mut self,
+        builder: &mut SchemaBuilder,
+        name: QName<'_>,
+        pos: TextPosition,
+    ) -> Result<Option<PrintSchemaDocument>, ReadError> {
+        let scope = &mut self.state.scope;
+        let name = scope.name(name, true)?;
+        let result = if self.state.lossless {
+            let name = name.to_owned();
+            builder.handle(XmlEvent::EndElement { name }, pos)
+        } else {
+            let local_name = (name.namespace == Some(NS_PSF)).then_some(name.local_name);
+            builder.end_element(local_name, pos)
+        };
+        scope.exit();
+        Ok(result?)
+    }
+}
+
+/// Get the value of an attribute, normalised as the xml based reader does.
+///
+/// Line breaks and tabs are replaced by spaces before entity references are resolved,
+/// so the characters written as references are kept.
+fn attribute_value<'a>(attribute: &'a Attribute<'_>) -> Result<Cow<'a, str>, ReadError> {
+    let raw = std::str::from_utf8(&attribute.value).map_err(malformed)?;
+    if !raw.contains(['\t', '\r', '\n']) {
+        return unescape(raw).map_err(malformed);
+    }
+    let normalized = raw.replace("\r\n", " ").replace(['\t', '\r', '\n'], " ");
+    let value = unescape(&normalized).map_err(malformed)?;
+    Ok(Cow::Owned(value.into_owned()))
+}
+
+/// Converts byte offsets into the positions reported by the xml based reader.
+///
+/// Rows and columns start at zero, columns count characters, and a CRLF pair is a single line break.
+/// The offsets must not decrease, so that the input is only scanned once.
+struct LineCounter {
+    offset: usize,
+    after_cr: bool,
+    pos: TextPosition,
+}
+
+impl LineCounter {
+    fn new() -> Self {
+        Self {
+            offset: 0,
+            after_cr: false,
+            pos: TextPosition::new(),
+        }
+    }
+
+    fn position(&mut self, xml: &str, offset: usize) -> TextPosition {
+        let bytes = &xml.as_bytes()[self.offset..offset];
+        self.offset = offset;
+        if self.after_cr || bytes.contains(&b'\r') {
+            for &x in bytes {
+                match x {
+                    b'\n' if self.after_cr => {}
+                    b'\n' | b'\r' => {
+                        self.pos.row += 1;
+                        self.pos.column = 0;
+                    }
+                    // a continuation byte of a multi-byte character
+                    0x80..=0xBF => {}
+                    _ => self.pos.column += 1,
+                }
+                self.after_cr = x == b'\r';
+            }
+            return self.pos;
+        }
+        // without carriage returns, the skipped content is counted in bulk
+        let line = match bytes.iter().rposition(|&x| x == b'\n') {
+            Some(last) => {
+                self.pos.row += bytes.iter().filter(|&&x| x == b'\n').count() as u64;
+                self.pos.column = 0;
+                &bytes[last + 1..]
+            }
+            None => bytes,
+        };
+        self.pos.column += line
+            .iter()
+            .filter(|&&x| !(0x80..=0xBF).contains(&x))
+            .count() as u64;
+        self.pos
+    }
+}
+
+/// The namespace bindings of the open elements.
+///
+/// Declarations are rare in Print Schema documents, usually only on the root element,
+/// so they are kept in a stack instead of a map for every element.
+#[derive(Default)]
+struct Scope {
+    bindings: Vec<(String, String)>,
+    marks: Vec<usize>,
+}
+
+impl Scope {
+    fn enter(&mut self, element: &BytesStart<'_>) -> Result<(), ReadError> {
+        self.marks.push(self.bindings.len());
+        let mut prefixed = false;
+        for attribute in element.attributes() {
+            let attribute = attribute.map_err(malformed)?;
+            let prefix = match attribute.key.as_namespace_binding() {
+                Some(PrefixDeclaration::Default) => "",
+                Some(PrefixDeclaration::Named(prefix)) => {
+                    std::str::from_utf8(prefix).map_err(malformed)?
+                }
+                None => {
+                    prefixed |= attribute.key.prefix().is_some();
+                    continue;
+                }
+            };
+            let uri = attribute_value(&attribute)?;
+            self.bindings.push((prefix.to_string(), uri.into_owned()));
+        }
+        if prefixed {
+            // an attribute with an unbound prefix is not well-formed
+            for attribute in element.attributes() {
+                let attribute = attribute.map_err(malformed)?;
+                if attribute.key.as_namespace_binding().is_none() {
+                    self.name(attribute.key, false)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn exit(&mut self) {
+        if let Some(mark) = self.marks.pop() {
+            self.bindings.truncate(mark);
+        }
+    }
+
+    fn lookup(&self, prefix: &str) -> Option<&str> {
+        if prefix == NS_XML_PREFIX {
+            return Some(NS_XML_URI);
+        }
+        self.bindings
+            .iter()
+            .rev()
+            .find(|(x, _)| *x == prefix)
+            .map(|(_, uri)| uri.as_str())
+            .filter(|x| !x.is_empty())
+    }
+
+    /// Resolve the namespace of an element or attribute name, which is an error if the prefix is not bound.
+    ///
+    /// An attribute without a prefix has no namespace, while an element has the default namespace.
+    fn name<'n>(&'n self, name: QName<'n>, element: bool) -> Result<Name<'n>, ReadError> {
+        let (local_name, prefix) = name.decompose();
+        let local_name = std::str::from_utf8(local_name.into_inner()).map_err(malformed)?;
+        let (prefix, namespace) = match prefix {
+            Some(prefix) => {
+                let prefix = std::str::from_utf8(prefix.into_inner()).map_err(malformed)?;
+                let namespace = self.lookup(prefix).ok_or(ReadError::Malformed)?;
+                (Some(prefix), Some(namespace))
+            }
+            None if element => (None, self.lookup("")),
+            None => (None, None),
+        };
+        Ok(Name {
+            local_name,
+            namespace,
+            prefix,
+        })
+    }
+
+    /// Get the `name` and `xsi:type` attributes.
+    fn schema_attributes(
+        &self,
+        element: &BytesStart<'_>,
+    ) -> Result<(Option<OwnedName>, Option<OwnedName>), ReadError> {
+        let mut name = None;
+        let mut value_type = None;
+        for attribute in element.attributes() {
+            let attribute = attribute.map_err(malformed)?;
+            let (local_name, prefix) = attribute.key.decompose();
+            let target = match (prefix, local_name.as_ref()) {
+                (None, b"name") => &mut name,
+                (Some(prefix), b"type")
+                    if self.lookup(std::str::from_utf8(prefix.as_ref()).map_err(malformed)?)
+                        == Some(NS_XSI) =>
+                {
+                    &mut value_type
+                }
+                _ => continue,
+            };
+            let value = attribute_value(&attribute)?;
+            *target = Some(parse_qname_with(&value, |x| self.lookup(x)));
+        }
+        Ok((name, value_type))
+    }
+
+    /// Collect the in-scope namespaces, for resolving `xsd:QName` values later.
+    fn to_namespace(&self) -> Namespace {
+        let mut namespace = Namespace::empty();
+        // the innermost binding wins, as `put` does not replace an existing one
+        for (prefix, uri) in self.bindings.iter().rev() {
+            namespace.put(prefix.as_str(), uri.as_str());
+        }
+        namespace
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+    use crate::ticket::document::{
+        reader::{ParsableXmlDocument, ParseDiagnostic, ParseOptions, ParsePrintSchemaError},
+        writer::{SerializeOptions, XmlDocumentRoot, XmlEncoding},
+        PrintSchemaDocument, PrintTicketDocument, PropertyValue,
+    };
+
+    /// Parse with both readers, and check that the results are the same.
+    fn parse_with_both(
+        xml: &str,
+        options: &ParseOptions,
+    ) -> (PrintSchemaDocument, Vec<ParseDiagnostic>) {
+        let fast = parse(xml.as_bytes(), options).expect("the input is well-formed");
+        let mut reader = options.parser_config().create_reader(xml.as_bytes());
+        let slow = PrintSchemaDocument::parse_with_diagnostics(&mut reader, options);
+        match (fast, slow) {
+            (Ok(fast), Ok(slow)) => {
+                assert_eq!(fast, slow);
+                fast
+            }
+            (Err(fast), Err(slow)) => {
+                assert_eq!(fast.to_string(), slow.to_string());
+                panic!("both readers fail: {}", fast);
+            }
+            (fast, slow) => panic!("different results: {:?} and {:?}", fast, slow),
+        }
+    }
+
+    #[test]
+    fn same_as_event_reader() {
+        for xml in [
+            include_str!("../../../test_data/print_ticket.xml"),
+            include_str!("../../../test_data/print_capabilities.xml"),
+        ] {
+            parse_with_both(xml, &ParseOptions::default());
+            parse_with_both(xml, &ParseOptions::new().lenient(true));
+        }
+    }
+
+    #[test]
+    fn text_and_namespaces() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<f:PrintTicket version="1"
+    xmlns:f="http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework"
+    xmlns:xsd="http://www.w3.org/2001/XMLSchema"
+    xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
+    <f:ParameterInit name="k:Name" xmlns:k="http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords">
+        <f:Value xsi:type="xsd:string"> A &amp; B&#x21; <![CDATA[<c>]]><!-- note --> </f:Value>
+    </f:ParameterInit>
+    <f:ParameterInit name="k:Kind" xmlns:k="http://vendor">
+        <f:Value xsi:type="xsd:QName">k:Fast</f:Value>
+    </f:ParameterInit>
+    <vendor:Private xmlns:vendor="http://vendor"><vendor:Inner /></vendor:Private>
+</f:PrintTicket>"#;
+        let (document, _) = parse_with_both(xml, &ParseOptions::default());
+        let PrintSchemaDocument::PrintTicket(ticket) = document else {
+            panic!("not a print ticket");
+        };
+        assert!(matches!(
+            &ticket.parameter_inits[1].value,
+            PropertyValue::QName(x) if x.namespace_ref() == Some("http://vendor")
+        ));
+    }
+
+    #[test]
+    fn attribute_values_and_line_breaks() {
+        let xml = "<psf:PrintTicket version=\"1\"\r\n    \
+            xmlns:psf=\"http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework\"\r\n    \
+            xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\"\r\n    \
+            xmlns:xsd=\"http://www.w3.org/2001/XMLSchema\"\r\n    \
+            xmlns:ns0000=\"http://vendor/&#x70;rivate\">\r\n    \
+            <psf:ParameterInit name=\"ns0000:A\r\n\tB&#9;C&#10;D &amp; E\">\r\n        \
+            <psf:Value xsi:type=\"xsd:string\">line\r\nbreak\rand &lt;&#x2603;&gt;</psf:Value>\r\n    \
+            </psf:ParameterInit>\r\n\
+            </psf:PrintTicket>";
+        let (document, _) = parse_with_both(xml, &ParseOptions::default());
+        let PrintSchemaDocument::PrintTicket(ticket) = document else {
+            panic!("not a print ticket");
+        };
+        let parameter = &ticket.parameter_inits[0];
+        assert_eq!(
+            parameter.name.namespace_ref(),
+            Some("http://vendor/private")
+        );
+        assert_eq!(parameter.name.local_name, "A  B\tC\nD & E");
+        assert_eq!(
+            parameter.value,
+            PropertyValue::String("line\nbreak\nand <\u{2603}>".to_string())
+        );
+    }
+
+    const BUGGY_TICKET: &str = "<psf:PrintTicket version=\"1\"\r\n    \
+        xmlns:psf=\"http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework\"\r\n    \
+        xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" xmlns:xsd=\"http://www.w3.org/2001/XMLSchema\"\r\n    \
+        xmlns:psk=\"http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords\">\r\n    \
+        <psf:Feature><psf:Option name=\"psk:ISOA4\" /></psf:Feature>\r\n    \
+        <psf:Feature name=\"psk:PageOrientation\"><!-- \u{00e9}t\u{00e9} -->\r\n        \
+        <psf:Option>\r\n            \
+        <psf:Property name=\"psk:Angle\"><psf:Value xsi:type=\"xsd:integer\">9O</psf:Value></psf:Property>\r\n        \
+        </psf:Option>\r\n        \
+        <psf:Unknown><psf:Option name=\"psk:Landscape\" /></psf:Unknown>\r\n    \
+        </psf:Feature>\r\n    \
+        <psf:ParameterInit name=\"psk:JobCopiesAllDocuments\" />\r\n    \
+        <psf:ParameterInit><psf:Value xsi:type=\"xsd:integer\">1</psf:Value></psf:ParameterInit>\r\n\
+        </psf:PrintTicket>";
+
+    #[test]
+    fn same_diagnostics_and_errors() {
+        let (_, diagnostics) = parse_with_both(BUGGY_TICKET, &ParseOptions::new().lenient(true));
+        assert_eq!(diagnostics.len(), 5);
+
+        let fast = parse(BUGGY_TICKET.as_bytes(), &ParseOptions::default()).unwrap();
+        let slow = PrintTicketDocument::parse(&mut xml::EventReader::new(BUGGY_TICKET.as_bytes()));
+        match (fast, slow) {
+            (
+                Err(ParsePrintSchemaError::InvalidPrintSchema { pos, reason }),
+                Err(ParsePrintSchemaError::InvalidPrintSchema {
+                    pos: expected_pos,
+                    reason: expected_reason,
+                }),
+            ) => {
+                assert_eq!(pos, expected_pos);
+                assert_eq!(reason, expected_reason);
+            }
+            (fast, slow) => panic!("different results: {:?} and {:?}", fast, slow),
+        }
+    }
+
+    #[test]
+    fn fall_back_to_event_reader() {
+        let ticket = PrintTicketDocument::parse_from_bytes(include_bytes!(
+            "../../../test_data/print_ticket.xml"
+        ))
+        .unwrap();
+        let utf16 = ticket
+            .try_to_xml_with_options(&SerializeOptions::new().encoding(XmlEncoding::Utf16Le))
+            .unwrap();
+        assert!(parse(&utf16, &ParseOptions::default()).is_none());
+        assert_eq!(
+            PrintTicketDocument::parse_from_bytes(&utf16).unwrap(),
+            ticket
+        );
+
+        for unbound in [
+            &br#"<psf:PrintTicket version="1" />"#[..],
+            br#"<PrintTicket psf:version="1" />"#,
+        ] {
+            assert!(parse(unbound, &ParseOptions::default()).is_none());
+            assert!(matches!(
+                PrintTicketDocument::parse_from_bytes(unbound),
+                Err(ParsePrintSchemaError::InvalidXml(_))
+            ));
+        }
+    }
+}
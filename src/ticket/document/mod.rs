@@ -1,14 +1,40 @@
+/// Canonical form and semantic equality of Print Schema documents.
+pub mod canonical;
+mod device_capabilities;
+mod device_reader;
+mod device_resources;
+/// Structural comparison of Print Schema documents.
+pub mod diff;
+mod edit;
+mod extra;
+mod fast_reader;
+mod parameter;
 mod print_schema;
+/// Path queries over Print Schema documents.
+pub mod query;
 /// Reader for parse XML bytes as Print Schema.
 pub mod reader;
+/// Streaming reader for pull features from Print Schema XML on demand.
+pub mod stream;
+/// Visitors over the nodes of Print Schema documents.
+pub mod visit;
 /// Writer for serialize Print Schema as XML bytes.
 pub mod writer;
+mod xsd;
 
+pub use device_capabilities::{DeviceFeature, DeviceOption, PrintDeviceCapabilitiesDocument};
+pub use device_resources::*;
+pub use extra::*;
+pub use parameter::*;
 pub use print_schema::*;
+pub use xsd::{ParseDateTimeError, XsdDateTime};
 
 /// The namespace URI for the Print Schema Framework.
 pub const NS_PSF: &str =
     "http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework";
+/// The namespace URI for the Print Schema Framework version 2, used by PrintDeviceCapabilities documents.
+pub const NS_PSF2: &str =
+    "http://schemas.microsoft.com/windows/2013/12/printing/printschemaframework2";
 /// The namespace URI for the Print Schema Keywords.
 pub const NS_PSK: &str =
     "http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords";
@@ -0,0 +1,344 @@
+use super::{
+    print_schema::same_name, ParameterDef, ParameterInit, PropertyValue, WithProperties, NS_PSF,
+    NS_PSK, NS_XSD,
+};
+use thiserror::Error;
+use xml::name::OwnedName;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// Represents whether a parameter must be initialized in a print ticket, as the `psf:Mandatory` property.
+pub enum ParameterMandatory {
+    /// The parameter must always be initialized.
+    Unconditional,
+    /// The parameter must be initialized if an option referencing it is selected.
+    Conditional,
+    /// The parameter may be omitted, in which case the default value is used.
+    Optional,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Represents the typed view of a [`ParameterDef`].
+///
+/// The numeric constraints are read as integers, which is how the Print Schema keywords define them.
+pub struct ParameterDefinition {
+    /// The name of the parameter.
+    pub name: OwnedName,
+    /// The type of the value, as the `psf:DataType` property.
+    pub data_type: Option<OwnedName>,
+    /// The default value, as the `psf:DefaultValue` property.
+    pub default_value: Option<PropertyValue>,
+    /// The minimum of an integer value, as the `psf:MinValue` property.
+    pub min_value: Option<i64>,
+    /// The maximum of an integer value, as the `psf:MaxValue` property.
+    pub max_value: Option<i64>,
+    /// The number which an integer value must be a multiple of, as the `psf:Multiple` property.
+    pub multiple: Option<i64>,
+    /// The minimum length of a string value, as the `psf:MinLength` property.
+    pub min_length: Option<usize>,
+    /// The maximum length of a string value, as the `psf:MaxLength` property.
+    pub max_length: Option<usize>,
+    /// Whether the parameter must be initialized, as the `psf:Mandatory` property.
+    pub mandatory: Option<ParameterMandatory>,
+    /// The unit of the value, like `copies` or `microns`, as the `psf:UnitType` property.
+    pub unit_type: Option<String>,
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+/// Represents an error occurred while checking a value against a [`ParameterDefinition`].
+pub enum ParameterValueError {
+    /// The type of the value differs from the data type of the parameter.
+    #[error("Type mismatch: expected {expected} but found {found}")]
+    TypeMismatch {
+        /// The data type of the parameter.
+        expected: Box<OwnedName>,
+        /// The type of the value.
+        found: Box<OwnedName>,
+    },
+    /// The value is kept as raw text, as it is invalid for its type.
+    #[error("Invalid value of type {0}")]
+    InvalidValue(OwnedName),
+    /// The value is less than the minimum.
+    #[error("Value {value} is less than the minimum {min}")]
+    BelowMinimum {
+        /// The value, saturated to the range of [`i32`].
+        value: i64,
+        /// The minimum.
+        min: i64,
+    },
+    /// The value is greater than the maximum.
+    #[error("Value {value} is greater than the maximum {max}")]
+    AboveMaximum {
+        /// The value, saturated to the range of [`i32`].
+        value: i64,
+        /// The maximum.
+        max: i64,
+    },
+    /// The value is not a multiple of the required number.
+    #[error("Value {value} is not a multiple of {multiple}")]
+    NotMultiple {
+        /// The value.
+        value: i64,
+        /// The required number.
+        multiple: i64,
+    },
+    /// The string is shorter than the minimum length.
+    #[error("Length {length} is less than the minimum length {min}")]
+    TooShort {
+        /// The length in characters.
+        length: usize,
+        /// The minimum length.
+        min: usize,
+    },
+    /// The string is longer than the maximum length.
+    #[error("Length {length} is greater than the maximum length {max}")]
+    TooLong {
+        /// The length in characters.
+        length: usize,
+        /// The maximum length.
+        max: usize,
+    },
+}
+
+impl ParameterDef {
+    /// Get the typed view of this parameter definition.
+    pub fn definition(&self) -> ParameterDefinition {
+        ParameterDefinition::from(self)
+    }
+}
+
+impl From<&ParameterDef> for ParameterDefinition {
+    fn from(def: &ParameterDef) -> Self {
+        let value = |name: &str| {
+            def.get_property(name, Some(NS_PSF))
+                .and_then(|x| x.value.as_ref())
+        };
+        let integer = |name: &str| {
+            value(name)
+                .and_then(|x| x.saturating_integer())
+                .map(i64::from)
+        };
+        let length = |name: &str| {
+            value(name)
+                .and_then(|x| x.integer())
+                .and_then(|x| usize::try_from(x).ok())
+        };
+        let mandatory = value("Mandatory")
+            .and_then(|x| x.qualified_name())
+            .filter(|x| x.namespace_ref() == Some(NS_PSK))
+            .and_then(|x| match x.local_name.as_str() {
+                "Unconditional" => Some(ParameterMandatory::Unconditional),
+                "Conditional" => Some(ParameterMandatory::Conditional),
+                "Optional" => Some(ParameterMandatory::Optional),
+                _ => None,
+            });
+        Self {
+            name: def.name.clone(),
+            data_type: value("DataType").and_then(|x| x.qualified_name()).cloned(),
+            default_value: def.default_value().cloned(),
+            min_value: integer("MinValue"),
+            max_value: integer("MaxValue"),
+            multiple: integer("Multiple"),
+            min_length: length("MinLength"),
+            max_length: length("MaxLength"),
+            mandatory,
+            unit_type: value("UnitType")
+                .and_then(|x| x.string())
+                .map(|x| x.trim().to_string()),
+        }
+    }
+}
+
+impl ParameterDefinition {
+    fn xsd_type(&self) -> Option<&str> {
+        self.data_type
+            .as_ref()
+            .filter(|x| x.namespace_ref() == Some(NS_XSD))
+            .map(|x| x.local_name.as_str())
+    }
+
+    fn effective_multiple(&self) -> Option<i64> {
+        self.multiple.filter(|x| *x > 1)
+    }
+
+    /// Check the value against the data type, range, multiple and length of the parameter.
+    ///
+    /// The type is not checked if the definition has no data type.
+    pub fn check_value(&self, value: &PropertyValue) -> Result<(), ParameterValueError> {
+        if let Some(expected) = &self.data_type {
+            let found = value.xsi_type();
+            if !same_name(expected, &found) {
+                return Err(ParameterValueError::TypeMismatch {
+                    expected: Box::new(expected.clone()),
+                    found: Box::new(found),
+                });
+            }
+        }
+        if let PropertyValue::Unknown(value_type, _) = value {
+            if value_type.namespace_ref() == Some(NS_XSD) {
+                return Err(ParameterValueError::InvalidValue(value_type.clone()));
+            }
+        }
+        match self.xsd_type() {
+            Some("integer") => {
+                let Some(value) = value.saturating_integer().map(i64::from) else {
+                    return Ok(());
+                };
+                if let Some(min) = self.min_value.filter(|x| value < *x) {
+                    return Err(ParameterValueError::BelowMinimum { value, min });
+                }
+                if let Some(max) = self.max_value.filter(|x| value > *x) {
+                    return Err(ParameterValueError::AboveMaximum { value, max });
+                }
+                if let Some(multiple) = self
+                    .effective_multiple()
+                    .filter(|x| value.rem_euclid(*x) != 0)
+                {
+                    return Err(ParameterValueError::NotMultiple { value, multiple });
+                }
+            }
+            Some("string") => {
+                let Some(value) = value.string() else {
+                    return Ok(());
+                };
+                let length = value.chars().count();
+                if let Some(min) = self.min_length.filter(|x| length < *x) {
+                    return Err(ParameterValueError::TooShort { length, min });
+                }
+                if let Some(max) = self.max_length.filter(|x| length > *x) {
+                    return Err(ParameterValueError::TooLong { length, max });
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Create a parameter initialization with the value, after checking it by [`ParameterDefinition::check_value`].
+    pub fn init(&self, value: PropertyValue) -> Result<ParameterInit, ParameterValueError> {
+        self.check_value(&value)?;
+        Ok(ParameterInit::new(self.name.clone(), value))
+    }
+
+    /// Adjust an integer value into the range and to the multiple of the parameter.
+    /// Return `None` if the value cannot be used, including when no multiple lies within the range.
+    pub(crate) fn adjust_value(&self, value: &PropertyValue) -> Option<PropertyValue> {
+        match self.xsd_type() {
+            Some("integer") => {
+                let mut v = i64::from(value.saturating_integer()?);
+                if let Some(max_value) = self.max_value {
+                    v = v.min(max_value);
+                }
+                if let Some(min_value) = self.min_value {
+                    v = v.max(min_value);
+                }
+                if let Some(multiple) = self.effective_multiple() {
+                    v -= v.rem_euclid(multiple);
+                    if self.min_value.is_some_and(|x| v < x) {
+                        v += multiple;
+                        if self.max_value.is_some_and(|x| v > x) {
+                            return None;
+                        }
+                    }
+                }
+                i32::try_from(v).ok().map(PropertyValue::Integer)
+            }
+            Some("string") => {
+                value.string()?;
+                self.check_value(value).ok().map(|_| value.clone())
+            }
+            _ => Some(value.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParameterDefinition, ParameterMandatory, ParameterValueError};
+    use crate::ticket::document::{PropertyValue, NS_XSD};
+    use crate::ticket::test_fixtures::sample_capabilities_document;
+    use xml::name::OwnedName;
+
+    fn definition(local_name: &str) -> ParameterDefinition {
+        sample_capabilities_document()
+            .parameter_defs
+            .iter()
+            .find(|x| x.name.local_name == local_name)
+            .unwrap()
+            .definition()
+    }
+
+    #[test]
+    fn read_definition() {
+        let copies = definition("JobCopiesAllDocuments");
+        assert_eq!(
+            copies.data_type,
+            Some(OwnedName::qualified("integer", NS_XSD, Some("xsd")))
+        );
+        assert_eq!(copies.min_value, Some(1));
+        assert_eq!(copies.max_value, Some(9999));
+        assert_eq!(copies.multiple, Some(1));
+        assert_eq!(copies.unit_type.as_deref(), Some("copies"));
+        assert_eq!(copies.default_value, Some(PropertyValue::Integer(1)));
+
+        let snapshot = definition("PageDevmodeSnapshot");
+        assert_eq!(snapshot.mandatory, Some(ParameterMandatory::Optional));
+        assert_eq!(snapshot.min_value, None);
+    }
+
+    #[test]
+    fn adjust_value_into_range() {
+        let mut def = definition("JobCopiesAllDocuments");
+        def.min_value = Some(5);
+        def.max_value = Some(9);
+        def.multiple = Some(4);
+        let adjust = |def: &ParameterDefinition, value| {
+            def.adjust_value(&PropertyValue::Integer(value))
+                .and_then(|x| x.integer())
+        };
+        assert_eq!(adjust(&def, 7), Some(8));
+        assert_eq!(adjust(&def, 100), Some(8));
+        assert_eq!(adjust(&def, 1), Some(8));
+
+        def.max_value = Some(7);
+        assert_eq!(adjust(&def, 7), None);
+        assert_eq!(adjust(&def, 5), None);
+    }
+
+    #[test]
+    fn init_with_checked_value() {
+        let copies = definition("JobCopiesAllDocuments");
+        let init = copies.init(PropertyValue::Integer(2)).unwrap();
+        assert_eq!(init.name, copies.name);
+        assert_eq!(
+            copies.init(PropertyValue::Integer(0)),
+            Err(ParameterValueError::BelowMinimum { value: 0, min: 1 })
+        );
+        assert_eq!(
+            copies.init(PropertyValue::BigInteger("99999999999".to_string())),
+            Err(ParameterValueError::AboveMaximum {
+                value: i32::MAX as i64,
+                max: 9999
+            })
+        );
+        assert!(matches!(
+            copies.init(PropertyValue::String("2".to_string())),
+            Err(ParameterValueError::TypeMismatch { .. })
+        ));
+
+        let mut even = copies.clone();
+        even.multiple = Some(2);
+        assert_eq!(
+            even.init(PropertyValue::Integer(3))
+                .unwrap_err()
+                .to_string(),
+            "Value 3 is not a multiple of 2"
+        );
+
+        let mut name = definition("PageDevmodeSnapshot");
+        name.max_length = Some(3);
+        assert_eq!(
+            name.init(PropertyValue::String("abcd".to_string())),
+            Err(ParameterValueError::TooLong { length: 4, max: 3 })
+        );
+    }
+}
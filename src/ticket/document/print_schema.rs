@@ -1,4 +1,12 @@
-use std::fmt;
+use super::{
+    visit::{self, Visit},
+    xsd::normalize_decimal,
+    XmlExtra, XsdDateTime,
+};
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+};
 use xml::name::OwnedName;
 
 #[derive(Clone, PartialEq, Eq, Hash, fmt_derive::Debug)]
@@ -10,7 +18,8 @@ pub enum PrintSchemaDocument {
     PrintTicket(PrintTicketDocument),
 }
 
-#[derive(Clone, PartialEq, Eq, Hash, fmt_derive::Debug)]
+#[derive(Clone, fmt_derive::Debug)]
+#[non_exhaustive]
 /// Represents a PrintCapabilities document.
 pub struct PrintCapabilitiesDocument {
     /// Properties of the document
@@ -19,9 +28,13 @@ pub struct PrintCapabilitiesDocument {
     pub parameter_defs: Vec<ParameterDef>,
     /// Features
     pub features: Vec<PrintFeature>,
+    /// Unrecognised XML content, kept in lossless mode.
+    #[fmt(ignore)]
+    pub extra: XmlExtra,
 }
 
-#[derive(Clone, PartialEq, Eq, Hash, fmt_derive::Debug)]
+#[derive(Clone, fmt_derive::Debug)]
+#[non_exhaustive]
 /// Represents a PrintTicket document.
 pub struct PrintTicketDocument {
     /// Properties of the document
@@ -30,9 +43,13 @@ pub struct PrintTicketDocument {
     pub parameter_inits: Vec<ParameterInit>,
     /// Features
     pub features: Vec<PrintFeature>,
+    /// Unrecognised XML content, kept in lossless mode.
+    #[fmt(ignore)]
+    pub extra: XmlExtra,
 }
 
-#[derive(Clone, PartialEq, Eq, Hash, fmt_derive::Debug)]
+#[derive(Clone, fmt_derive::Debug)]
+#[non_exhaustive]
 /// Represents a Print Feature.
 pub struct PrintFeature {
     /// The name of the feature.
@@ -44,9 +61,13 @@ pub struct PrintFeature {
     pub options: Vec<PrintFeatureOption>,
     /// Sub-features of the feature
     pub features: Vec<PrintFeature>,
+    /// Unrecognised XML content, kept in lossless mode.
+    #[fmt(ignore)]
+    pub extra: XmlExtra,
 }
 
-#[derive(Clone, PartialEq, Eq, Hash, fmt_derive::Debug)]
+#[derive(Clone, fmt_derive::Debug)]
+#[non_exhaustive]
 /// Represents a parameter initialization used in a [`PrintTicketDocument`].
 pub struct ParameterInit {
     /// The name of the parameter.
@@ -55,9 +76,13 @@ pub struct ParameterInit {
     /// The value of the parameter.
     #[fmt("{:?}", self.value)]
     pub value: PropertyValue,
+    /// Unrecognised XML content, kept in lossless mode.
+    #[fmt(ignore)]
+    pub extra: XmlExtra,
 }
 
-#[derive(Clone, PartialEq, Eq, Hash, fmt_derive::Debug)]
+#[derive(Clone, fmt_derive::Debug)]
+#[non_exhaustive]
 /// Represents a parameter definition used in a [`PrintCapabilitiesDocument`].
 pub struct ParameterDef {
     /// The name of the parameter.
@@ -65,9 +90,13 @@ pub struct ParameterDef {
     pub name: OwnedName,
     /// Properties of the parameter
     pub properties: Vec<Property>,
+    /// Unrecognised XML content, kept in lossless mode.
+    #[fmt(ignore)]
+    pub extra: XmlExtra,
 }
 
-#[derive(Clone, PartialEq, Eq, Hash, fmt_derive::Debug)]
+#[derive(Clone, fmt_derive::Debug)]
+#[non_exhaustive]
 /// Represents a possible option for a [`PrintFeature`].
 pub struct PrintFeatureOption {
     /// The name of the option.
@@ -77,9 +106,13 @@ pub struct PrintFeatureOption {
     pub scored_properties: Vec<ScoredProperty>,
     /// Properties of the option
     pub properties: Vec<Property>,
+    /// Unrecognised XML content, kept in lossless mode.
+    #[fmt(ignore)]
+    pub extra: XmlExtra,
 }
 
-#[derive(Clone, PartialEq, Eq, Hash, fmt_derive::Debug)]
+#[derive(Clone, fmt_derive::Debug)]
+#[non_exhaustive]
 /// Represents a scored-property.
 /// A [`ScoredProperty`] declares a property that is intrinsic to an [Option](PrintFeatureOption).
 /// Such properties should be compared when evaluating how closely a requested Option matches a device-supported Option.
@@ -97,9 +130,13 @@ pub struct ScoredProperty {
     pub scored_properties: Vec<ScoredProperty>,
     /// Properties of the scored-property
     pub properties: Vec<Property>,
+    /// Unrecognised XML content, kept in lossless mode.
+    #[fmt(ignore)]
+    pub extra: XmlExtra,
 }
 
-#[derive(Clone, PartialEq, Eq, Hash, fmt_derive::Debug)]
+#[derive(Clone, fmt_derive::Debug)]
+#[non_exhaustive]
 /// Represents a property.
 pub struct Property {
     /// The name of the property.
@@ -110,6 +147,9 @@ pub struct Property {
     pub value: Option<PropertyValue>,
     /// Sub-properties of the property
     pub properties: Vec<Property>,
+    /// Unrecognised XML content, kept in lossless mode.
+    #[fmt(ignore)]
+    pub extra: XmlExtra,
 }
 
 #[derive(Clone, PartialEq, Eq, Hash)]
@@ -119,23 +159,233 @@ pub enum PropertyValue {
     String(String),
     /// An integer value.
     Integer(i32),
+    /// An integer value beyond the range of [`i32`], in its canonical decimal form.
+    BigInteger(String),
+    /// A decimal value, in its lexical form.
+    Decimal(String),
+    /// A boolean value.
+    Boolean(bool),
+    /// A binary value, encoded as base64 in XML.
+    Base64Binary(Vec<u8>),
+    /// A date and time value.
+    DateTime(XsdDateTime),
     /// A qualified name value.
     QName(OwnedName),
     /// An unknown-typed value.
     Unknown(OwnedName, String),
 }
 
+/// Implement [`PartialEq`], [`Eq`] and [`Hash`] by the given fields, leaving out the `extra` field,
+/// so that a document compares the same whether it is parsed in lossless mode or not.
+macro_rules! impl_eq_without_extra {
+    ($name:ident { $($field:ident),* }) => {
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                $(self.$field == other.$field)&&*
+            }
+        }
+
+        impl Eq for $name {}
+
+        impl Hash for $name {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                $(self.$field.hash(state);)*
+            }
+        }
+    };
+}
+
+impl_eq_without_extra!(PrintCapabilitiesDocument {
+    properties,
+    parameter_defs,
+    features
+});
+impl_eq_without_extra!(PrintTicketDocument {
+    properties,
+    parameter_inits,
+    features
+});
+impl_eq_without_extra!(PrintFeature {
+    name,
+    properties,
+    options,
+    features
+});
+impl_eq_without_extra!(ParameterInit { name, value });
+impl_eq_without_extra!(ParameterDef { name, properties });
+impl_eq_without_extra!(PrintFeatureOption {
+    name,
+    scored_properties,
+    properties
+});
+impl_eq_without_extra!(ScoredProperty {
+    name,
+    parameter_ref,
+    value,
+    scored_properties,
+    properties
+});
+impl_eq_without_extra!(Property {
+    name,
+    value,
+    properties
+});
+
+impl PrintCapabilitiesDocument {
+    /// Create a PrintCapabilities document without lossless data.
+    pub fn new(
+        properties: Vec<Property>,
+        parameter_defs: Vec<ParameterDef>,
+        features: Vec<PrintFeature>,
+    ) -> Self {
+        Self {
+            properties,
+            parameter_defs,
+            features,
+            extra: XmlExtra::default(),
+        }
+    }
+}
+
+impl PrintTicketDocument {
+    /// Create a PrintTicket document without lossless data.
+    pub fn new(
+        properties: Vec<Property>,
+        parameter_inits: Vec<ParameterInit>,
+        features: Vec<PrintFeature>,
+    ) -> Self {
+        Self {
+            properties,
+            parameter_inits,
+            features,
+            extra: XmlExtra::default(),
+        }
+    }
+}
+
+impl PrintFeature {
+    /// Create a feature without lossless data.
+    pub fn new(
+        name: OwnedName,
+        properties: Vec<Property>,
+        options: Vec<PrintFeatureOption>,
+        features: Vec<PrintFeature>,
+    ) -> Self {
+        Self {
+            name,
+            properties,
+            options,
+            features,
+            extra: XmlExtra::default(),
+        }
+    }
+}
+
+impl ParameterInit {
+    /// Create a parameter initialization without lossless data.
+    pub fn new(name: OwnedName, value: PropertyValue) -> Self {
+        Self {
+            name,
+            value,
+            extra: XmlExtra::default(),
+        }
+    }
+}
+
+impl ParameterDef {
+    /// Create a parameter definition without lossless data.
+    pub fn new(name: OwnedName, properties: Vec<Property>) -> Self {
+        Self {
+            name,
+            properties,
+            extra: XmlExtra::default(),
+        }
+    }
+}
+
+impl PrintFeatureOption {
+    /// Create an option without lossless data.
+    pub fn new(
+        name: Option<OwnedName>,
+        scored_properties: Vec<ScoredProperty>,
+        properties: Vec<Property>,
+    ) -> Self {
+        Self {
+            name,
+            scored_properties,
+            properties,
+            extra: XmlExtra::default(),
+        }
+    }
+}
+
+impl ScoredProperty {
+    /// Create a scored-property without lossless data.
+    pub fn new(
+        name: Option<OwnedName>,
+        parameter_ref: Option<OwnedName>,
+        value: Option<PropertyValue>,
+        scored_properties: Vec<ScoredProperty>,
+        properties: Vec<Property>,
+    ) -> Self {
+        Self {
+            name,
+            parameter_ref,
+            value,
+            scored_properties,
+            properties,
+            extra: XmlExtra::default(),
+        }
+    }
+}
+
+impl Property {
+    /// Create a property without lossless data.
+    pub fn new(name: OwnedName, value: Option<PropertyValue>, properties: Vec<Property>) -> Self {
+        Self {
+            name,
+            value,
+            properties,
+            extra: XmlExtra::default(),
+        }
+    }
+}
+
 impl fmt::Debug for PropertyValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             PropertyValue::String(s) => write!(f, "String({:?})", s),
             PropertyValue::Integer(i) => write!(f, "Integer({})", i),
+            PropertyValue::BigInteger(i) => write!(f, "BigInteger({})", i),
+            PropertyValue::Decimal(d) => write!(f, "Decimal({})", d),
+            PropertyValue::Boolean(b) => write!(f, "Boolean({})", b),
+            PropertyValue::Base64Binary(b) => write!(f, "Base64Binary({} bytes)", b.len()),
+            PropertyValue::DateTime(d) => write!(f, "DateTime({})", d),
             PropertyValue::QName(q) => write!(f, "QName({})", q),
             PropertyValue::Unknown(n, s) => write!(f, "Unknown({}, {:?})", n, s),
         }
     }
 }
 
+/// Compare two names by namespace URI and local name, ignoring the prefix.
+pub(crate) fn same_name(a: &OwnedName, b: &OwnedName) -> bool {
+    a.local_name == b.local_name && a.namespace_ref() == b.namespace_ref()
+}
+
+/// Compare two values, ignoring the prefixes of qualified names and the lexical form of decimals.
+pub(crate) fn same_value(a: &PropertyValue, b: &PropertyValue) -> bool {
+    match (a, b) {
+        (PropertyValue::QName(a), PropertyValue::QName(b)) => same_name(a, b),
+        (PropertyValue::Decimal(a), PropertyValue::Decimal(b)) => {
+            normalize_decimal(a) == normalize_decimal(b)
+        }
+        (PropertyValue::Unknown(a_type, a), PropertyValue::Unknown(b_type, b)) => {
+            same_name(a_type, b_type) && a == b
+        }
+        _ => a == b,
+    }
+}
+
 impl ParameterDef {
     /// Get the default value of this parameter.
     pub fn default_value(&self) -> Option<&PropertyValue> {
@@ -153,9 +403,21 @@ impl PropertyValue {
     pub fn xsi_type(&self) -> OwnedName {
         match self {
             PropertyValue::String(_) => OwnedName::qualified("string", super::NS_XSD, Some("xsd")),
-            PropertyValue::Integer(_) => {
+            PropertyValue::Integer(_) | PropertyValue::BigInteger(_) => {
                 OwnedName::qualified("integer", super::NS_XSD, Some("xsd"))
             }
+            PropertyValue::Decimal(_) => {
+                OwnedName::qualified("decimal", super::NS_XSD, Some("xsd"))
+            }
+            PropertyValue::Boolean(_) => {
+                OwnedName::qualified("boolean", super::NS_XSD, Some("xsd"))
+            }
+            PropertyValue::Base64Binary(_) => {
+                OwnedName::qualified("base64Binary", super::NS_XSD, Some("xsd"))
+            }
+            PropertyValue::DateTime(_) => {
+                OwnedName::qualified("dateTime", super::NS_XSD, Some("xsd"))
+            }
             PropertyValue::QName(_) => OwnedName::qualified("QName", super::NS_XSD, Some("xsd")),
             PropertyValue::Unknown(n, _) => n.clone(),
         }
@@ -174,6 +436,50 @@ impl PropertyValue {
             _ => None,
         }
     }
+    /// Try as [`PropertyValue::Integer`] value, saturated to the range of [`i32`] for [`PropertyValue::BigInteger`] values.
+    pub fn saturating_integer(&self) -> Option<i32> {
+        match self {
+            PropertyValue::Integer(i) => Some(*i),
+            PropertyValue::BigInteger(i) if i.starts_with('-') => Some(i32::MIN),
+            PropertyValue::BigInteger(_) => Some(i32::MAX),
+            _ => None,
+        }
+    }
+    /// Try as [`PropertyValue::BigInteger`] value.
+    pub fn big_integer(&self) -> Option<&str> {
+        match self {
+            PropertyValue::BigInteger(i) => Some(i),
+            _ => None,
+        }
+    }
+    /// Try as [`PropertyValue::Decimal`] value.
+    pub fn decimal(&self) -> Option<f64> {
+        match self {
+            PropertyValue::Decimal(d) => d.parse().ok(),
+            _ => None,
+        }
+    }
+    /// Try as [`PropertyValue::Boolean`] value.
+    pub fn boolean(&self) -> Option<bool> {
+        match self {
+            PropertyValue::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+    /// Try as [`PropertyValue::Base64Binary`] value.
+    pub fn base64_binary(&self) -> Option<&[u8]> {
+        match self {
+            PropertyValue::Base64Binary(b) => Some(b),
+            _ => None,
+        }
+    }
+    /// Try as [`PropertyValue::DateTime`] value.
+    pub fn date_time(&self) -> Option<&XsdDateTime> {
+        match self {
+            PropertyValue::DateTime(d) => Some(d),
+            _ => None,
+        }
+    }
     /// Try as [`PropertyValue::QName`] value.
     pub fn qualified_name(&self) -> Option<&OwnedName> {
         match self {
@@ -183,28 +489,36 @@ impl PropertyValue {
     }
 }
 
+/// Collects the parameter references of the visited scored-properties.
+#[derive(Default)]
+struct ParameterRefs(Vec<OwnedName>);
+
+impl<'a> Visit<'a> for ParameterRefs {
+    fn visit_scored_property(
+        &mut self,
+        scored_property: &'a ScoredProperty,
+        path: &mut Vec<&'a OwnedName>,
+    ) {
+        self.0.extend(scored_property.parameter_ref.iter().cloned());
+        visit::visit_scored_property(self, scored_property, path);
+    }
+}
+
 impl PrintFeatureOption {
     /// Collect all parameters that this option depends on.
     pub fn parameters_dependent(&self) -> Vec<OwnedName> {
-        let mut result = vec![];
-        for scored_property in &self.scored_properties {
-            result.extend(scored_property.parameters_dependent());
-        }
-        result
+        let mut result = ParameterRefs::default();
+        result.visit_option(self, &mut vec![]);
+        result.0
     }
 }
 
 impl ScoredProperty {
     /// Collect all parameters that this scored-property depends on.
     pub fn parameters_dependent(&self) -> Vec<OwnedName> {
-        let mut result = vec![];
-        if let Some(ref parameter_ref) = self.parameter_ref {
-            result.push(parameter_ref.clone());
-        }
-        for scored_property in &self.scored_properties {
-            result.extend(scored_property.parameters_dependent());
-        }
-        result
+        let mut result = ParameterRefs::default();
+        result.visit_scored_property(self, &mut vec![]);
+        result.0
     }
 
     /// Get the value of this scored-property, or the value of the parameter it references.
@@ -212,7 +526,7 @@ impl ScoredProperty {
         if let Some(ref parameter_ref) = self.parameter_ref {
             parameters
                 .iter()
-                .find(|x| x.name == *parameter_ref)
+                .find(|x| same_name(&x.name, parameter_ref))
                 .map(|x| &x.value)
         } else {
             self.value.as_ref()
@@ -240,9 +554,9 @@ pub trait WithScoredProperties {
     /// Get the scored-property with the given name and namespace.
     fn get_scored_property(&self, name: &str, namespace: Option<&str>) -> Option<&ScoredProperty> {
         self.scored_properties().iter().find(|x| {
-            x.name.as_ref().map_or(false, |x| {
-                x.local_name == name && x.namespace_ref() == namespace
-            })
+            x.name
+                .as_ref()
+                .is_some_and(|x| x.local_name == name && x.namespace_ref() == namespace)
         })
     }
 }
@@ -333,16 +647,16 @@ mod tests {
 
     fn new_test_properties() -> Vec<Property> {
         vec![
-            Property {
-                name: OwnedName::local("Property1"),
-                value: Some(PropertyValue::String("Value1".to_string())),
-                properties: vec![],
-            },
-            Property {
-                name: OwnedName::qualified("Property2", "http://test.namespace/", Some("test")),
-                value: Some(PropertyValue::Integer(2)),
-                properties: vec![],
-            },
+            Property::new(
+                OwnedName::local("Property1"),
+                Some(PropertyValue::String("Value1".to_string())),
+                vec![],
+            ),
+            Property::new(
+                OwnedName::qualified("Property2", "http://test.namespace/", Some("test")),
+                Some(PropertyValue::Integer(2)),
+                vec![],
+            ),
         ]
     }
 
@@ -378,75 +692,52 @@ mod tests {
 
     #[test]
     fn get_properties_from_ticket() {
-        let document1: PrintSchemaDocument = PrintTicketDocument {
-            properties: new_test_properties(),
-            parameter_inits: vec![],
-            features: vec![],
-        }
-        .into();
+        let document1: PrintSchemaDocument =
+            PrintTicketDocument::new(new_test_properties(), vec![], vec![]).into();
         check_test_properties(&document1);
     }
 
     #[test]
     fn get_properties_from_capabilities() {
-        let document1: PrintSchemaDocument = PrintCapabilitiesDocument {
-            properties: new_test_properties(),
-            parameter_defs: vec![],
-            features: vec![],
-        }
-        .into();
+        let document1: PrintSchemaDocument =
+            PrintCapabilitiesDocument::new(new_test_properties(), vec![], vec![]).into();
         check_test_properties(&document1);
     }
 
     #[test]
     fn get_properties_from_parameter_def() {
-        let parameter_def = super::ParameterDef {
-            name: OwnedName::local("Test"),
-            properties: new_test_properties(),
-        };
+        let parameter_def =
+            super::ParameterDef::new(OwnedName::local("Test"), new_test_properties());
         check_test_properties(&parameter_def);
     }
 
     #[test]
     fn get_properties_from_option() {
-        let option = super::PrintFeatureOption {
-            name: None,
-            scored_properties: vec![],
-            properties: new_test_properties(),
-        };
+        let option = super::PrintFeatureOption::new(None, vec![], new_test_properties());
         check_test_properties(&option);
     }
 
     #[test]
     fn get_properties_from_feature() {
-        let feature = super::PrintFeature {
-            name: OwnedName::local("Test"),
-            properties: new_test_properties(),
-            options: vec![],
-            features: vec![],
-        };
+        let feature = super::PrintFeature::new(
+            OwnedName::local("Test"),
+            new_test_properties(),
+            vec![],
+            vec![],
+        );
         check_test_properties(&feature);
     }
 
     #[test]
     fn get_properties_from_scored_property() {
-        let scored_property = super::ScoredProperty {
-            name: None,
-            parameter_ref: None,
-            value: None,
-            scored_properties: vec![],
-            properties: new_test_properties(),
-        };
+        let scored_property =
+            super::ScoredProperty::new(None, None, None, vec![], new_test_properties());
         check_test_properties(&scored_property);
     }
 
     #[test]
     fn get_properties_from_property() {
-        let property = Property {
-            name: OwnedName::local("Test"),
-            value: None,
-            properties: new_test_properties(),
-        };
+        let property = Property::new(OwnedName::local("Test"), None, new_test_properties());
         check_test_properties(&property);
     }
 }
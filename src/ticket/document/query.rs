@@ -0,0 +1,466 @@
+use super::{
+    print_schema::same_name, writer::is_ncname, ParameterDef, ParameterInit,
+    PrintCapabilitiesDocument, PrintFeature, PrintFeatureOption, PrintSchemaDocument,
+    PrintTicketDocument, Property, PropertyValue, ScoredProperty, NS_PSF, NS_PSK, NS_XSD, NS_XSI,
+};
+use std::{fmt, str::FromStr};
+use thiserror::Error;
+use xml::{name::OwnedName, namespace::Namespace};
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+/// Represents an error occurred while parsing a [`SchemaPath`].
+pub enum ParseSchemaPathError {
+    /// The path or one of its steps is empty.
+    #[error("Empty step in path")]
+    EmptyStep,
+    /// The step is not a valid name.
+    #[error("Invalid name: {0}")]
+    InvalidName(String),
+    /// The prefix of the step is not bound in the namespace map.
+    #[error("Unbound prefix: {0}")]
+    UnboundPrefix(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum PathStep {
+    Any,
+    Name(OwnedName),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Represents a path to nodes of a Print Schema document, like `psk:PageMediaSize/psk:ISOA4/psk:MediaSizeWidth`.
+///
+/// Each step separated by `/` matches the children of the previous nodes by namespace and local name,
+/// or any child for `*`. Children of a document are its features, properties, parameter definitions and initializations;
+/// children of a feature are its options, sub-features and properties;
+/// children of an option or a scored-property are its scored-properties and properties.
+///
+/// # Examples
+/// ```
+/// use winprint::ticket::document::{query::SchemaPath, reader::ParsableXmlDocument, PrintTicketDocument};
+///
+/// let ticket = PrintTicketDocument::parse_from_bytes(br#"<psf:PrintTicket version="1"
+///     xmlns:psf="http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework"
+///     xmlns:psk="http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords"
+///     xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+///     xmlns:xsd="http://www.w3.org/2001/XMLSchema">
+///     <psf:Feature name="psk:PageMediaSize">
+///         <psf:Option name="psk:ISOA4">
+///             <psf:ScoredProperty name="psk:MediaSizeWidth">
+///                 <psf:Value xsi:type="xsd:integer">210000</psf:Value>
+///             </psf:ScoredProperty>
+///         </psf:Option>
+///     </psf:Feature>
+/// </psf:PrintTicket>"#).unwrap();
+/// let path = "psk:PageMediaSize/*/psk:MediaSizeWidth".parse::<SchemaPath>().unwrap();
+/// let width = ticket.query_value(&path).and_then(|x| x.integer());
+/// assert_eq!(width, Some(210000));
+/// ```
+pub struct SchemaPath {
+    steps: Vec<PathStep>,
+}
+
+impl SchemaPath {
+    /// Parse the path, with the `psf`, `psk`, `xsd` and `xsi` prefixes bound to their standard namespaces.
+    pub fn parse(path: &str) -> Result<Self, ParseSchemaPathError> {
+        let mut namespace = Namespace::empty();
+        namespace.put("psf", NS_PSF);
+        namespace.put("psk", NS_PSK);
+        namespace.put("xsd", NS_XSD);
+        namespace.put("xsi", NS_XSI);
+        Self::parse_with_namespace(path, &namespace)
+    }
+
+    /// Parse the path, resolving the prefixes with the given namespace map.
+    ///
+    /// A step without prefix matches names in the default namespace of the map, if any.
+    pub fn parse_with_namespace(
+        path: &str,
+        namespace: &Namespace,
+    ) -> Result<Self, ParseSchemaPathError> {
+        let steps = path
+            .split('/')
+            .map(|step| {
+                if step.is_empty() {
+                    return Err(ParseSchemaPathError::EmptyStep);
+                }
+                if step == "*" {
+                    return Ok(PathStep::Any);
+                }
+                let (prefix, local_name) = match step.split_once(':') {
+                    Some((prefix, local_name)) => (Some(prefix), local_name),
+                    None => (None, step),
+                };
+                if !is_ncname(local_name) || prefix.is_some_and(|x| !is_ncname(x)) {
+                    return Err(ParseSchemaPathError::InvalidName(step.to_string()));
+                }
+                let uri = namespace.get(prefix.unwrap_or(""));
+                if prefix.is_some() && uri.is_none() {
+                    return Err(ParseSchemaPathError::UnboundPrefix(step.to_string()));
+                }
+                Ok(PathStep::Name(OwnedName {
+                    local_name: local_name.to_string(),
+                    namespace: uri.map(str::to_string),
+                    prefix: prefix.map(str::to_string),
+                }))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { steps })
+    }
+
+    /// Create a path from the names of each step.
+    pub fn from_names(names: impl IntoIterator<Item = OwnedName>) -> Self {
+        Self {
+            steps: names.into_iter().map(PathStep::Name).collect(),
+        }
+    }
+
+    /// Select the nodes under the given node, resolving parameter references with the given parameter initializations.
+    pub fn select<'a>(
+        &self,
+        root: SchemaNode<'a>,
+        parameters: &'a [ParameterInit],
+    ) -> Vec<SchemaMatch<'a>> {
+        self.resolve(root, Parameters::Inits(parameters))
+    }
+
+    fn resolve<'a>(
+        &self,
+        root: SchemaNode<'a>,
+        parameters: Parameters<'a>,
+    ) -> Vec<SchemaMatch<'a>> {
+        let mut nodes = vec![root];
+        for step in &self.steps {
+            nodes = nodes
+                .into_iter()
+                .flat_map(|x| x.children())
+                .filter(|x| match step {
+                    PathStep::Any => true,
+                    PathStep::Name(name) => x.name().is_some_and(|x| same_name(x, name)),
+                })
+                .collect();
+        }
+        nodes
+            .into_iter()
+            .map(|node| SchemaMatch {
+                node,
+                value: parameters.value_of(node),
+            })
+            .collect()
+    }
+}
+
+impl FromStr for SchemaPath {
+    type Err = ParseSchemaPathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for SchemaPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, step) in self.steps.iter().enumerate() {
+            if i > 0 {
+                f.write_str("/")?;
+            }
+            match step {
+                PathStep::Any => f.write_str("*")?,
+                PathStep::Name(name) => match name.prefix_ref() {
+                    Some(prefix) => write!(f, "{}:{}", prefix, name.local_name)?,
+                    None => f.write_str(&name.local_name)?,
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Represents a node of a Print Schema document.
+pub enum SchemaNode<'a> {
+    /// A print capabilities document.
+    PrintCapabilities(&'a PrintCapabilitiesDocument),
+    /// A print ticket document.
+    PrintTicket(&'a PrintTicketDocument),
+    /// A feature.
+    Feature(&'a PrintFeature),
+    /// An option.
+    Option(&'a PrintFeatureOption),
+    /// A scored-property.
+    ScoredProperty(&'a ScoredProperty),
+    /// A property.
+    Property(&'a Property),
+    /// A parameter definition.
+    ParameterDef(&'a ParameterDef),
+    /// A parameter initialization.
+    ParameterInit(&'a ParameterInit),
+}
+
+impl<'a> SchemaNode<'a> {
+    /// Get the name of the node, which is `None` for documents and unnamed options and scored-properties.
+    pub fn name(&self) -> Option<&'a OwnedName> {
+        match *self {
+            SchemaNode::PrintCapabilities(_) | SchemaNode::PrintTicket(_) => None,
+            SchemaNode::Feature(x) => Some(&x.name),
+            SchemaNode::Option(x) => x.name.as_ref(),
+            SchemaNode::ScoredProperty(x) => x.name.as_ref(),
+            SchemaNode::Property(x) => Some(&x.name),
+            SchemaNode::ParameterDef(x) => Some(&x.name),
+            SchemaNode::ParameterInit(x) => Some(&x.name),
+        }
+    }
+
+    /// Get the child nodes, in the order of parameters, features, options, scored-properties and properties.
+    pub fn children(&self) -> Vec<SchemaNode<'a>> {
+        fn nodes<'a, T>(
+            items: &'a [T],
+            node: impl Fn(&'a T) -> SchemaNode<'a>,
+        ) -> impl Iterator<Item = SchemaNode<'a>> {
+            items.iter().map(node)
+        }
+
+        match *self {
+            SchemaNode::PrintCapabilities(x) => nodes(&x.parameter_defs, SchemaNode::ParameterDef)
+                .chain(nodes(&x.features, SchemaNode::Feature))
+                .chain(nodes(&x.properties, SchemaNode::Property))
+                .collect(),
+            SchemaNode::PrintTicket(x) => nodes(&x.parameter_inits, SchemaNode::ParameterInit)
+                .chain(nodes(&x.features, SchemaNode::Feature))
+                .chain(nodes(&x.properties, SchemaNode::Property))
+                .collect(),
+            SchemaNode::Feature(x) => nodes(&x.features, SchemaNode::Feature)
+                .chain(nodes(&x.options, SchemaNode::Option))
+                .chain(nodes(&x.properties, SchemaNode::Property))
+                .collect(),
+            SchemaNode::Option(x) => nodes(&x.scored_properties, SchemaNode::ScoredProperty)
+                .chain(nodes(&x.properties, SchemaNode::Property))
+                .collect(),
+            SchemaNode::ScoredProperty(x) => {
+                nodes(&x.scored_properties, SchemaNode::ScoredProperty)
+                    .chain(nodes(&x.properties, SchemaNode::Property))
+                    .collect()
+            }
+            SchemaNode::Property(x) => nodes(&x.properties, SchemaNode::Property).collect(),
+            SchemaNode::ParameterDef(x) => nodes(&x.properties, SchemaNode::Property).collect(),
+            SchemaNode::ParameterInit(_) => vec![],
+        }
+    }
+}
+
+macro_rules! impl_from_for_schema_node {
+    ($($variant:ident($ty:ty)),* $(,)?) => {
+        $(
+            impl<'a> From<&'a $ty> for SchemaNode<'a> {
+                fn from(value: &'a $ty) -> Self {
+                    SchemaNode::$variant(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_for_schema_node!(
+    PrintCapabilities(PrintCapabilitiesDocument),
+    PrintTicket(PrintTicketDocument),
+    Feature(PrintFeature),
+    Option(PrintFeatureOption),
+    ScoredProperty(ScoredProperty),
+    Property(Property),
+    ParameterDef(ParameterDef),
+    ParameterInit(ParameterInit),
+);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Represents a node selected by a [`SchemaPath`].
+pub struct SchemaMatch<'a> {
+    /// The selected node.
+    pub node: SchemaNode<'a>,
+    /// The value of the node, with the parameter reference of a scored-property resolved.
+    ///
+    /// For a parameter definition, it is the default value.
+    pub value: Option<&'a PropertyValue>,
+}
+
+#[derive(Clone, Copy)]
+enum Parameters<'a> {
+    Inits(&'a [ParameterInit]),
+    Defs(&'a [ParameterDef]),
+}
+
+impl<'a> Parameters<'a> {
+    fn value_of(&self, node: SchemaNode<'a>) -> Option<&'a PropertyValue> {
+        match node {
+            SchemaNode::ScoredProperty(x) => match &x.parameter_ref {
+                Some(parameter_ref) => self.value_of_parameter(parameter_ref),
+                None => x.value.as_ref(),
+            },
+            SchemaNode::Property(x) => x.value.as_ref(),
+            SchemaNode::ParameterDef(x) => x.default_value(),
+            SchemaNode::ParameterInit(x) => Some(&x.value),
+            _ => None,
+        }
+    }
+
+    fn value_of_parameter(&self, name: &OwnedName) -> Option<&'a PropertyValue> {
+        match *self {
+            Parameters::Inits(inits) => inits
+                .iter()
+                .find(|x| same_name(&x.name, name))
+                .map(|x| &x.value),
+            Parameters::Defs(defs) => defs
+                .iter()
+                .find(|x| same_name(&x.name, name))
+                .and_then(|x| x.default_value()),
+        }
+    }
+}
+
+impl PrintTicketDocument {
+    /// Select the nodes matching the path, resolving parameter references with the parameter initializations of the ticket.
+    pub fn query(&self, path: &SchemaPath) -> Vec<SchemaMatch<'_>> {
+        path.resolve(self.into(), Parameters::Inits(&self.parameter_inits))
+    }
+
+    /// Get the value of the first node matching the path, see [`PrintTicketDocument::query`].
+    pub fn query_value(&self, path: &SchemaPath) -> Option<&PropertyValue> {
+        self.query(path).into_iter().find_map(|x| x.value)
+    }
+}
+
+impl PrintCapabilitiesDocument {
+    /// Select the nodes matching the path, resolving parameter references with the default values of the parameter definitions.
+    pub fn query(&self, path: &SchemaPath) -> Vec<SchemaMatch<'_>> {
+        path.resolve(self.into(), Parameters::Defs(&self.parameter_defs))
+    }
+
+    /// Get the value of the first node matching the path, see [`PrintCapabilitiesDocument::query`].
+    pub fn query_value(&self, path: &SchemaPath) -> Option<&PropertyValue> {
+        self.query(path).into_iter().find_map(|x| x.value)
+    }
+}
+
+impl PrintSchemaDocument {
+    /// Select the nodes matching the path, see [`PrintTicketDocument::query`] and [`PrintCapabilitiesDocument::query`].
+    pub fn query(&self, path: &SchemaPath) -> Vec<SchemaMatch<'_>> {
+        match self {
+            PrintSchemaDocument::PrintCapabilities(x) => x.query(path),
+            PrintSchemaDocument::PrintTicket(x) => x.query(path),
+        }
+    }
+
+    /// Get the value of the first node matching the path.
+    pub fn query_value(&self, path: &SchemaPath) -> Option<&PropertyValue> {
+        self.query(path).into_iter().find_map(|x| x.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParseSchemaPathError, SchemaNode, SchemaPath};
+    use crate::ticket::document::{
+        reader::ParsableXmlDocument, PrintSchemaDocument, PrintTicketDocument, PropertyValue,
+    };
+    use crate::ticket::test_fixtures::sample_capabilities_document;
+    use xml::namespace::Namespace;
+
+    fn path(path: &str) -> SchemaPath {
+        path.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_path() {
+        assert_eq!(
+            path("psk:PageMediaSize/*/psk:MediaSizeWidth").to_string(),
+            "psk:PageMediaSize/*/psk:MediaSizeWidth"
+        );
+        assert_eq!(
+            SchemaPath::parse("psk:PageMediaSize//psk:MediaSizeWidth"),
+            Err(ParseSchemaPathError::EmptyStep)
+        );
+        assert_eq!(SchemaPath::parse(""), Err(ParseSchemaPathError::EmptyStep));
+        assert_eq!(
+            SchemaPath::parse("vendor:Feature"),
+            Err(ParseSchemaPathError::UnboundPrefix(
+                "vendor:Feature".to_string()
+            ))
+        );
+        assert_eq!(
+            SchemaPath::parse("psk:Bad Name"),
+            Err(ParseSchemaPathError::InvalidName(
+                "psk:Bad Name".to_string()
+            ))
+        );
+
+        let mut namespace = Namespace::empty();
+        namespace.put("k", crate::ticket::document::NS_PSK);
+        let k_path = SchemaPath::parse_with_namespace("k:PageMediaSize", &namespace).unwrap();
+        assert_eq!(sample_capabilities_document().query(&k_path).len(), 1);
+    }
+
+    #[test]
+    fn query_capabilities() {
+        let capabilities = sample_capabilities_document();
+        let width = capabilities
+            .query_value(&path("psk:PageMediaSize/psk:ISOA4/psk:MediaSizeWidth"))
+            .and_then(PropertyValue::integer);
+        assert_eq!(width, Some(210000));
+
+        let origin = capabilities
+            .query_value(&path(
+                "psk:PageImageableSize/psk:ImageableArea/psk:OriginWidth",
+            ))
+            .and_then(PropertyValue::integer);
+        assert_eq!(origin, Some(0));
+
+        let options = capabilities.query(&path("psk:PageMediaSize/*"));
+        assert!(options.len() > 1);
+        assert!(options
+            .iter()
+            .all(|x| matches!(x.node, SchemaNode::Option(_) | SchemaNode::Property(_))));
+
+        assert!(capabilities
+            .query(&path("psk:PageMediaSize/psk:NotExisting"))
+            .is_empty());
+    }
+
+    #[test]
+    fn resolve_parameter_references() {
+        let capabilities = sample_capabilities_document();
+        let width = path("psk:PageMediaSize/psk:CustomMediaSize/psk:MediaSizeWidth");
+        let default_width = capabilities
+            .query_value(&width)
+            .and_then(PropertyValue::integer);
+        assert!(default_width.is_some());
+
+        let ticket = PrintTicketDocument::parse_from_bytes(
+            r#"<psf:PrintTicket version="1"
+    xmlns:psf="http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework"
+    xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+    xmlns:xsd="http://www.w3.org/2001/XMLSchema"
+    xmlns:k="http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords">
+    <psf:ParameterInit name="k:PageMediaSizeMediaSizeWidth"><psf:Value xsi:type="xsd:integer">100000</psf:Value></psf:ParameterInit>
+    <psf:Feature name="k:PageMediaSize">
+        <psf:Option name="k:CustomMediaSize">
+            <psf:ScoredProperty name="k:MediaSizeWidth"><psf:ParameterRef name="k:PageMediaSizeMediaSizeWidth" /></psf:ScoredProperty>
+        </psf:Option>
+    </psf:Feature>
+</psf:PrintTicket>"#,
+        )
+        .unwrap();
+        let document = PrintSchemaDocument::PrintTicket(ticket.clone());
+        assert_eq!(
+            document.query_value(&width),
+            Some(&PropertyValue::Integer(100000))
+        );
+
+        let option = &ticket.features[0].options[0];
+        let matches = path("psk:MediaSizeWidth").select(option.into(), &ticket.parameter_inits);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].value, Some(&PropertyValue::Integer(100000)));
+        assert!(path("psk:MediaSizeWidth")
+            .select(option.into(), &[])
+            .iter()
+            .all(|x| x.value.is_none()));
+    }
+}
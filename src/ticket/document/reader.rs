@@ -1,16 +1,22 @@
 use super::{
+    fast_reader,
+    xsd::{decode_base64, is_decimal, normalize_integer, parse_boolean},
     ParameterDef, ParameterInit, PrintCapabilitiesDocument, PrintFeature, PrintFeatureOption,
-    PrintSchemaDocument, PrintTicketDocument, Property, PropertyValue, ScoredProperty, NS_PSF,
-    NS_XSD, NS_XSI,
+    PrintSchemaDocument, PrintTicketDocument, Property, PropertyValue, SchemaElement,
+    ScoredProperty, XmlContent, XmlElement, XmlExtra, XmlNode, NS_PSF, NS_XSD, NS_XSI,
+};
+use std::{
+    fmt::{Debug, Display},
+    io::Cursor,
 };
-use std::{fmt::Debug, io::Cursor};
 use thiserror::Error;
 use xml::{
+    attribute::OwnedAttribute,
     common::{Position, TextPosition},
     name::OwnedName,
-    namespace::Namespace,
+    namespace::{Namespace, NS_EMPTY_URI, NS_NO_PREFIX, NS_XMLNS_PREFIX, NS_XML_PREFIX},
     reader::XmlEvent,
-    EventReader,
+    EventReader, ParserConfig,
 };
 
 #[derive(Error, Debug)]
@@ -37,6 +43,82 @@ pub enum ParsePrintSchemaError {
     },
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Represents an invalid node which is skipped or repaired in lenient mode.
+pub struct ParseDiagnostic {
+    /// Position in the document.
+    pub pos: TextPosition,
+    /// Reason of the diagnostic.
+    pub reason: String,
+    /// What is done with the invalid node.
+    pub action: DiagnosticAction,
+}
+
+impl Display for ParseDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let action = match self.action {
+            DiagnosticAction::Skipped => "skipped",
+            DiagnosticAction::KeptRaw => "kept as raw value",
+        };
+        write!(f, "(at {}) {}, {}", self.pos, self.reason, action)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Represents what is done with an invalid node in lenient mode.
+pub enum DiagnosticAction {
+    /// The element and its content are dropped.
+    Skipped,
+    /// The value is kept as [`PropertyValue::Unknown`] with its raw text.
+    KeptRaw,
+}
+
+#[derive(Clone, Debug, Default)]
+/// Represents options for parsing print schema.
+pub struct ParseOptions {
+    lossless: bool,
+    lenient: bool,
+}
+
+impl ParseOptions {
+    /// Create the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep the XML content which is not part of the Print Schema model, in [`XmlExtra`].
+    ///
+    /// Comments, processing instructions, elements in other namespaces, namespace declarations and attribute order
+    /// are then preserved when the document is written again.
+    /// When the reader is created by the caller, it should be configured with `ignore_comments(false)` to keep comments.
+    pub fn lossless(mut self, value: bool) -> Self {
+        self.lossless = value;
+        self
+    }
+
+    /// Skip or repair invalid Print Schema nodes instead of failing, and report them as [`ParseDiagnostic`]s.
+    ///
+    /// Elements without a required name, unknown elements in the PSF namespace and misplaced elements are skipped
+    /// with their content. Values which are invalid for their type are kept as [`PropertyValue::Unknown`].
+    /// Malformed XML is still an error.
+    pub fn lenient(mut self, value: bool) -> Self {
+        self.lenient = value;
+        self
+    }
+
+    pub(super) fn parser_config(&self) -> ParserConfig {
+        ParserConfig::new().ignore_comments(!self.lossless)
+    }
+
+    pub(super) fn is_lossless(&self) -> bool {
+        self.lossless
+    }
+
+    pub(super) fn is_lenient(&self) -> bool {
+        self.lenient
+    }
+}
+
 /// Represents a root element which can be parsed from XML.
 pub trait ParsableXmlDocument: Sized {
     /// The error type that can be returned when parsing fails.
@@ -47,21 +129,68 @@ pub trait ParsableXmlDocument: Sized {
     where
         R: std::io::Read;
 
+    /// Parse the XML document from the given XML reader, with the given options.
+    fn parse_with_options<R>(
+        reader: &mut EventReader<R>,
+        options: &ParseOptions,
+    ) -> Result<Self, Self::Error>
+    where
+        R: std::io::Read,
+    {
+        Self::parse_with_diagnostics(reader, options).map(|(document, _)| document)
+    }
+
+    /// Parse the XML document from the given XML reader, with the given options.
+    /// Return the diagnostics of the lenient mode as well, which are always empty otherwise.
+    ///
+    /// The default implementation ignores the options and parses with [`ParsableXmlDocument::parse`].
+    fn parse_with_diagnostics<R>(
+        reader: &mut EventReader<R>,
+        _options: &ParseOptions,
+    ) -> Result<(Self, Vec<ParseDiagnostic>), Self::Error>
+    where
+        R: std::io::Read,
+    {
+        Self::parse(reader).map(|document| (document, Vec::new()))
+    }
+
     /// Parse the XML document from the given bytes.
     fn parse_from_bytes(xml: impl AsRef<[u8]>) -> Result<Self, Self::Error> {
-        let mut reader = EventReader::new(Cursor::new(xml));
-        Self::parse(&mut reader)
+        Self::parse_from_bytes_with_options(xml, &ParseOptions::default())
+    }
+
+    /// Parse the XML document from the given bytes, with the given options.
+    fn parse_from_bytes_with_options(
+        xml: impl AsRef<[u8]>,
+        options: &ParseOptions,
+    ) -> Result<Self, Self::Error> {
+        Self::parse_from_bytes_with_diagnostics(xml, options).map(|(document, _)| document)
+    }
+
+    /// Parse the XML document from the given bytes, with the given options.
+    /// Return the diagnostics of the lenient mode as well, which are always empty otherwise.
+    fn parse_from_bytes_with_diagnostics(
+        xml: impl AsRef<[u8]>,
+        options: &ParseOptions,
+    ) -> Result<(Self, Vec<ParseDiagnostic>), Self::Error> {
+        let mut reader = options.parser_config().create_reader(Cursor::new(xml));
+        Self::parse_with_diagnostics(&mut reader, options)
     }
 }
 
-fn parse_qname(namespace: &Namespace, value: &str) -> OwnedName {
-    let prefix_index = value.find(':');
-    if let Some(prefix_index) = prefix_index {
-        let (prefix, local_name) = value.split_at(prefix_index);
-        let local_name = &local_name[1..];
+pub(super) fn parse_qname(namespace: &Namespace, value: &str) -> OwnedName {
+    parse_qname_with(value, |prefix| namespace.get(prefix))
+}
+
+/// Parse a QName value, resolving its prefix with the given function.
+pub(super) fn parse_qname_with<'a>(
+    value: &str,
+    resolve: impl FnOnce(&str) -> Option<&'a str>,
+) -> OwnedName {
+    if let Some((prefix, local_name)) = value.split_once(':') {
         OwnedName {
             local_name: local_name.to_string(),
-            namespace: namespace.get(prefix).map(str::to_string),
+            namespace: resolve(prefix).map(str::to_string),
             prefix: Some(prefix.to_string()),
         }
     } else {
@@ -73,7 +202,7 @@ fn parse_qname(namespace: &Namespace, value: &str) -> OwnedName {
     }
 }
 
-fn parse_name_attribute(
+pub(super) fn parse_name_attribute(
     attributes: &[xml::attribute::OwnedAttribute],
     namespace: &Namespace,
 ) -> Option<OwnedName> {
@@ -84,7 +213,7 @@ fn parse_name_attribute(
     attribute_value.map(|x| parse_qname(namespace, &x))
 }
 
-fn parse_type_attribute(
+pub(super) fn parse_type_attribute(
     attributes: &[xml::attribute::OwnedAttribute],
     namespace: &Namespace,
 ) -> Option<OwnedName> {
@@ -103,24 +232,51 @@ struct PsfValueContext {
 }
 
 impl PsfValueContext {
+    fn invalid(&self, type_name: &str) -> ParsePrintSchemaError {
+        ParsePrintSchemaError::InvalidPrintSchema {
+            pos: self.pos,
+            reason: format!("Invalid {}", type_name),
+        }
+    }
+
     fn parse(self) -> Result<PropertyValue, ParsePrintSchemaError> {
         if self.value_type.namespace_ref() == Some(NS_XSD) {
+            // values of these types are whitespace-collapsed
+            let value = self.value.trim();
             match self.value_type.local_name.as_str() {
                 "string" => return Ok(PropertyValue::String(self.value)),
                 "integer" => {
-                    return self.value.parse().map(PropertyValue::Integer).map_err(|_| {
-                        ParsePrintSchemaError::InvalidPrintSchema {
-                            pos: self.pos,
-                            reason: "Invalid integer".to_string(),
-                        }
-                    })
+                    let normalized =
+                        normalize_integer(value).ok_or_else(|| self.invalid("integer"))?;
+                    return Ok(match normalized.parse() {
+                        Ok(i) => PropertyValue::Integer(i),
+                        Err(_) => PropertyValue::BigInteger(normalized),
+                    });
                 }
-                "QName" => {
-                    return Ok(PropertyValue::QName(parse_qname(
-                        &self.namespace,
-                        &self.value,
-                    )))
+                "decimal" => {
+                    return if is_decimal(value) {
+                        Ok(PropertyValue::Decimal(value.to_string()))
+                    } else {
+                        Err(self.invalid("decimal"))
+                    }
                 }
+                "boolean" => {
+                    return parse_boolean(value)
+                        .map(PropertyValue::Boolean)
+                        .ok_or_else(|| self.invalid("boolean"))
+                }
+                "base64Binary" => {
+                    return decode_base64(value)
+                        .map(PropertyValue::Base64Binary)
+                        .ok_or_else(|| self.invalid("base64Binary"))
+                }
+                "dateTime" => {
+                    return value
+                        .parse()
+                        .map(PropertyValue::DateTime)
+                        .map_err(|_| self.invalid("dateTime"))
+                }
+                "QName" => return Ok(PropertyValue::QName(parse_qname(&self.namespace, value))),
                 _ => {}
             };
         }
@@ -128,320 +284,669 @@ impl PsfValueContext {
     }
 }
 
-impl ParsableXmlDocument for PrintSchemaDocument {
-    type Error = ParsePrintSchemaError;
-    fn parse<R>(reader: &mut EventReader<R>) -> Result<Self, Self::Error>
-    where
-        R: std::io::Read,
-    {
-        let mut depth: usize = 0;
+/// Check if an invalid value of the given type is kept as [`PropertyValue::Unknown`] even in strict mode.
+///
+/// The values of these types used to be kept as raw text without any check,
+/// so an invalid one is left to the validation instead of failing the whole document.
+pub(super) fn keeps_invalid_value(value_type: &OwnedName) -> bool {
+    value_type.namespace_ref() == Some(NS_XSD)
+        && matches!(
+            value_type.local_name.as_str(),
+            "decimal" | "boolean" | "base64Binary" | "dateTime"
+        )
+}
+
+/// Parse the text of a value of the given type, resolving QName prefixes with the given namespace.
+pub(super) fn parse_value(
+    value: String,
+    value_type: OwnedName,
+    namespace: Namespace,
+    pos: TextPosition,
+) -> Result<PropertyValue, ParsePrintSchemaError> {
+    PsfValueContext {
+        pos,
+        value,
+        value_type,
+        namespace,
+    }
+    .parse()
+}
+
+/// Tracks the content which is not part of the model while parsing in lossless mode.
+#[derive(Default)]
+struct LosslessContext {
+    /// In-scope namespaces of the open elements.
+    scopes: Vec<Namespace>,
+    /// Extra content of the open Print Schema elements.
+    extras: Vec<XmlExtra>,
+    /// Open elements which are not part of the model.
+    raw_elements: Vec<XmlElement>,
+    /// Nodes before the root element.
+    prolog: Vec<XmlNode>,
+}
+
+impl LosslessContext {
+    fn in_raw_element(&self) -> bool {
+        !self.raw_elements.is_empty()
+    }
+
+    /// Push the namespace scope of a new element and return the namespaces declared by it.
+    fn enter_scope(&mut self, namespace: Namespace) -> Vec<(String, String)> {
+        let declared = namespace
+            .into_iter()
+            .filter(|(prefix, uri)| {
+                let builtin = matches!(*prefix, NS_XML_PREFIX | NS_XMLNS_PREFIX)
+                    || (*prefix == NS_NO_PREFIX && *uri == NS_EMPTY_URI);
+                !builtin && self.scopes.last().and_then(|x| x.get(*prefix)) != Some(*uri)
+            })
+            .map(|(prefix, uri)| (prefix.to_string(), uri.to_string()))
+            .collect();
+        self.scopes.push(namespace);
+        declared
+    }
+
+    fn start_schema_element(&mut self, attributes: &[OwnedAttribute], namespace: &Namespace) {
+        let namespaces = self.enter_scope(namespace.clone());
+        self.extras.push(XmlExtra {
+            namespaces,
+            attributes: attributes.to_vec(),
+            ..Default::default()
+        });
+    }
 
-        let mut option_name: Option<OwnedName> = None;
-        let mut parameter_ref: Option<OwnedName> = None;
+    /// Mark the place of an element which is accepted into the model, in the content of its parent.
+    fn accept_schema_element(&mut self, kind: SchemaElement) {
+        let mut extras = self.extras.iter_mut().rev();
+        if let (Some(_), Some(parent)) = (extras.next(), extras.next()) {
+            parent.content.push(XmlContent::Schema(kind));
+        }
+    }
 
-        let mut parameter_def_name: Option<OwnedName> = None;
-        let mut parameter_def_container: Option<Vec<ParameterDef>> = None;
+    fn end_schema_element(&mut self) -> XmlExtra {
+        self.scopes.pop();
+        let mut extra = self.extras.pop().unwrap_or_default();
+        if self.extras.is_empty() {
+            extra.prolog = std::mem::take(&mut self.prolog);
+        }
+        extra
+    }
 
-        let mut parameter_init_name: Option<OwnedName> = None;
-        let mut parameter_init_container: Option<Vec<ParameterInit>> = None;
+    fn start_raw_element(
+        &mut self,
+        name: OwnedName,
+        attributes: Vec<OwnedAttribute>,
+        namespace: Namespace,
+    ) {
+        let namespaces = self.enter_scope(namespace);
+        self.raw_elements.push(XmlElement {
+            name,
+            namespaces,
+            attributes,
+            children: Vec::new(),
+        });
+    }
 
-        let mut feature_name: Vec<OwnedName> = Vec::new();
-        let mut feature_containers: Vec<Vec<PrintFeature>> = Vec::new();
+    fn end_raw_element(&mut self) {
+        self.scopes.pop();
+        if let Some(element) = self.raw_elements.pop() {
+            self.push_node(XmlNode::Element(element));
+        }
+    }
 
-        let mut option_containers: Vec<Vec<PrintFeatureOption>> = Vec::new();
+    fn push_node(&mut self, node: XmlNode) {
+        if let Some(parent) = self.raw_elements.last_mut() {
+            parent.children.push(node);
+        } else if let Some(parent) = self.extras.last_mut() {
+            parent.content.push(XmlContent::Node(node));
+        } else if !matches!(node, XmlNode::Whitespace(_)) {
+            self.prolog.push(node);
+        }
+    }
+}
 
-        let mut property_name: Vec<OwnedName> = Vec::new();
-        let mut property_containers: Vec<Vec<Property>> = Vec::new();
+fn schema_element(local_name: &str) -> Option<SchemaElement> {
+    match local_name {
+        "ParameterDef" => Some(SchemaElement::ParameterDef),
+        "ParameterInit" => Some(SchemaElement::ParameterInit),
+        "Feature" => Some(SchemaElement::Feature),
+        "Option" => Some(SchemaElement::Option),
+        "ScoredProperty" => Some(SchemaElement::ScoredProperty),
+        "Property" => Some(SchemaElement::Property),
+        "ParameterRef" => Some(SchemaElement::ParameterRef),
+        "Value" => Some(SchemaElement::Value),
+        _ => None,
+    }
+}
 
-        let mut scored_property_name: Vec<Option<OwnedName>> = Vec::new();
-        let mut scored_property_containers: Vec<Vec<ScoredProperty>> = Vec::new();
+/// The start of an element in the PSF namespace, with the attributes used by the model.
+pub(super) struct SchemaStart<'a> {
+    pub(super) local_name: &'a str,
+    /// The qualified name, for error messages.
+    pub(super) display: &'a dyn Display,
+    /// The `name` attribute.
+    pub(super) name: Option<OwnedName>,
+    /// The `xsi:type` attribute.
+    pub(super) value_type: Option<OwnedName>,
+    /// The in-scope namespaces, which are only required for `xsd:QName` values.
+    pub(super) namespace: Option<Namespace>,
+}
 
-        let mut value_context: Option<PsfValueContext> = None;
-        let mut parsed_value: Option<PropertyValue> = None;
+/// Builds the Print Schema model from XML events.
+///
+/// It is shared by the document parser and the streaming reader, which builds only the features it yields.
+#[derive(Default)]
+pub(super) struct SchemaBuilder {
+    depth: usize,
+    lossless: Option<LosslessContext>,
+    option_name: Option<OwnedName>,
+    parameter_ref: Option<OwnedName>,
+    parameter_def_name: Option<OwnedName>,
+    parameter_def_container: Option<Vec<ParameterDef>>,
+    parameter_init_name: Option<OwnedName>,
+    parameter_init_container: Option<Vec<ParameterInit>>,
+    feature_name: Vec<OwnedName>,
+    feature_containers: Vec<Vec<PrintFeature>>,
+    option_containers: Vec<Vec<PrintFeatureOption>>,
+    property_name: Vec<OwnedName>,
+    property_containers: Vec<Vec<Property>>,
+    scored_property_name: Vec<Option<OwnedName>>,
+    scored_property_containers: Vec<Vec<ScoredProperty>>,
+    value_context: Option<PsfValueContext>,
+    parsed_value: Option<PropertyValue>,
+    /// The depth of the element whose content is skipped in lenient mode.
+    skip: Option<usize>,
+    /// The diagnostics collected in lenient mode.
+    diagnostics: Option<Vec<ParseDiagnostic>>,
+}
 
-        loop {
-            let e = match reader.next() {
-                Ok(e) => e,
-                Err(e) => return Err(ParsePrintSchemaError::InvalidXml(e)),
-            };
-            match e {
-                XmlEvent::StartElement {
-                    name,
-                    attributes,
-                    namespace,
-                } => {
-                    depth += 1;
-
-                    if name.namespace_ref() == Some(NS_PSF) {
-                        match name.local_name.as_str() {
-                            "PrintCapabilities" => {
-                                if depth > 1 {
-                                    return Err(ParsePrintSchemaError::InvalidPrintSchema {
-                                        pos: reader.position(),
-                                        reason: "PrintCapabilities should be root element"
-                                            .to_string(),
-                                    });
-                                }
-                                // root container
-                                feature_containers.push(Vec::new());
-                                property_containers.push(Vec::new());
-                                parameter_def_container.replace(Vec::new());
-                            }
-                            "PrintTicket" => {
-                                if depth > 1 {
-                                    return Err(ParsePrintSchemaError::InvalidPrintSchema {
-                                        pos: reader.position(),
-                                        reason: "PrintTicket should be root element".to_string(),
-                                    });
-                                }
-                                // root container
-                                feature_containers.push(Vec::new());
-                                property_containers.push(Vec::new());
-                                parameter_init_container.replace(Vec::new());
-                            }
-                            "ParameterDef" => {
-                                parameter_def_name = parse_name_attribute(&attributes, &namespace);
-                                property_containers.push(Vec::new());
-                            }
-                            "ParameterInit" => {
-                                parameter_init_name = parse_name_attribute(&attributes, &namespace);
-                            }
-                            "Feature" => {
-                                feature_name.push(
-                                    parse_name_attribute(&attributes, &namespace).ok_or_else(
-                                        || ParsePrintSchemaError::InvalidPrintSchema {
-                                            pos: reader.position(),
-                                            reason: "Feature name not found".to_string(),
-                                        },
-                                    )?,
-                                );
-
-                                // for sub-elements
-                                feature_containers.push(Vec::new());
-                                property_containers.push(Vec::new());
-                                option_containers.push(Vec::new());
-                            }
-                            "Option" => {
-                                option_name = parse_name_attribute(&attributes, &namespace);
-                                property_containers.push(Vec::new());
-                                scored_property_containers.push(Vec::new());
-                            }
-                            "ParameterRef" => {
-                                parameter_ref = parse_name_attribute(&attributes, &namespace);
-                            }
-                            "ScoredProperty" => {
-                                scored_property_name
-                                    .push(parse_name_attribute(&attributes, &namespace));
-
-                                // for sub-elements
-                                property_containers.push(Vec::new());
-                                scored_property_containers.push(Vec::new());
-
-                                // clear previous value
-                                parsed_value.take();
-                                parameter_ref.take();
-                            }
-                            "Property" => {
-                                property_name.push(
-                                    parse_name_attribute(&attributes, &namespace).ok_or_else(
-                                        || ParsePrintSchemaError::InvalidPrintSchema {
-                                            pos: reader.position(),
-                                            reason: "Property name not found".to_string(),
-                                        },
-                                    )?,
-                                );
-
-                                // for sub-elements
-                                property_containers.push(Vec::new());
-
-                                // clear previous value
-                                parsed_value.take();
-                            }
-                            "Value" => {
-                                if let Some(value_type) =
-                                    parse_type_attribute(&attributes, &namespace)
-                                {
-                                    value_context.replace(PsfValueContext {
-                                        pos: reader.position(),
-                                        value: String::new(),
-                                        value_type,
-                                        namespace,
-                                    });
-                                }
-                            }
-                            _ => {
-                                return Err(ParsePrintSchemaError::InvalidPrintSchema {
-                                    pos: reader.position(),
-                                    reason: format!("Invalid element: {}", name),
-                                })
-                            }
+impl SchemaBuilder {
+    pub(super) fn new(options: &ParseOptions) -> Self {
+        Self {
+            lossless: options.lossless.then(LosslessContext::default),
+            diagnostics: options.lenient.then(Vec::new),
+            ..Default::default()
+        }
+    }
+
+    /// Create a builder for a single feature subtree, which is not nested in a root element.
+    pub(super) fn for_feature(options: &ParseOptions) -> Self {
+        let mut builder = Self::new(options);
+        builder.feature_containers.push(Vec::new());
+        builder
+    }
+
+    /// The depth of the open elements.
+    pub(super) fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Take the feature built at the top level, see [`SchemaBuilder::for_feature`].
+    pub(super) fn take_feature(&mut self) -> Option<PrintFeature> {
+        self.feature_containers.last_mut().and_then(Vec::pop)
+    }
+
+    /// Start an element, with the parsed attributes if it is in the PSF namespace.
+    /// Reject the element which is being started, and skip its content in lenient mode.
+    fn skip(
+        &mut self,
+        pos: TextPosition,
+        reason: impl Into<String>,
+    ) -> Result<(), ParsePrintSchemaError> {
+        self.reject::<()>(pos, reason)?;
+        self.skip = Some(self.depth);
+        Ok(())
+    }
+
+    /// Reject an invalid node, which is an error unless it is dropped in lenient mode.
+    fn reject<T: Default>(
+        &mut self,
+        pos: TextPosition,
+        reason: impl Into<String>,
+    ) -> Result<T, ParsePrintSchemaError> {
+        let reason = reason.into();
+        if self.diagnostics.is_none() {
+            return Err(ParsePrintSchemaError::InvalidPrintSchema { pos, reason });
+        }
+        self.report(pos, reason, DiagnosticAction::Skipped);
+        Ok(T::default())
+    }
+
+    fn report(&mut self, pos: TextPosition, reason: String, action: DiagnosticAction) {
+        if let Some(diagnostics) = self.diagnostics.as_mut() {
+            diagnostics.push(ParseDiagnostic {
+                pos,
+                reason,
+                action,
+            });
+        }
+    }
+
+    /// Take the diagnostics collected in lenient mode.
+    pub(super) fn take_diagnostics(&mut self) -> Vec<ParseDiagnostic> {
+        self.diagnostics
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    pub(super) fn start_element(
+        &mut self,
+        element: Option<SchemaStart<'_>>,
+        pos: TextPosition,
+    ) -> Result<(), ParsePrintSchemaError> {
+        self.depth += 1;
+        if self.skip.is_some() {
+            return Ok(());
+        }
+        let Some(SchemaStart {
+            local_name,
+            display,
+            name: name_attribute,
+            value_type,
+            namespace,
+        }) = element
+        else {
+            return Ok(());
+        };
+        match local_name {
+            "PrintCapabilities" => {
+                if self.depth > 1 {
+                    return self.skip(pos, "PrintCapabilities should be root element");
+                }
+                // root container
+                self.feature_containers.push(Vec::new());
+                self.property_containers.push(Vec::new());
+                self.parameter_def_container.replace(Vec::new());
+            }
+            "PrintTicket" => {
+                if self.depth > 1 {
+                    return self.skip(pos, "PrintTicket should be root element");
+                }
+                // root container
+                self.feature_containers.push(Vec::new());
+                self.property_containers.push(Vec::new());
+                self.parameter_init_container.replace(Vec::new());
+            }
+            "ParameterDef" => {
+                if name_attribute.is_none() {
+                    return self.skip(pos, "ParameterDef name not found");
+                }
+                self.parameter_def_name = name_attribute;
+                self.property_containers.push(Vec::new());
+            }
+            "ParameterInit" => {
+                if name_attribute.is_none() {
+                    return self.skip(pos, "ParameterInit name not found");
+                }
+                self.parameter_init_name = name_attribute;
+            }
+            "Feature" => {
+                let Some(name_attribute) = name_attribute else {
+                    return self.skip(pos, "Feature name not found");
+                };
+                self.feature_name.push(name_attribute);
+
+                // for sub-elements
+                self.feature_containers.push(Vec::new());
+                self.property_containers.push(Vec::new());
+                self.option_containers.push(Vec::new());
+            }
+            "Option" => {
+                self.option_name = name_attribute;
+                self.property_containers.push(Vec::new());
+                self.scored_property_containers.push(Vec::new());
+            }
+            "ParameterRef" => {
+                self.parameter_ref = name_attribute;
+            }
+            "ScoredProperty" => {
+                self.scored_property_name.push(name_attribute);
+
+                // for sub-elements
+                self.property_containers.push(Vec::new());
+                self.scored_property_containers.push(Vec::new());
+
+                // clear previous value
+                self.parsed_value.take();
+                self.parameter_ref.take();
+            }
+            "Property" => {
+                let Some(name_attribute) = name_attribute else {
+                    return self.skip(pos, "Property name not found");
+                };
+                self.property_name.push(name_attribute);
+
+                // for sub-elements
+                self.property_containers.push(Vec::new());
+
+                // clear previous value
+                self.parsed_value.take();
+            }
+            "Value" => {
+                if let Some(value_type) = value_type {
+                    self.value_context.replace(PsfValueContext {
+                        pos,
+                        value: String::new(),
+                        value_type,
+                        namespace: namespace.unwrap_or_else(Namespace::empty),
+                    });
+                }
+            }
+            _ => {
+                return self.skip(pos, format!("Invalid element: {}", display));
+            }
+        }
+        Ok(())
+    }
+
+    /// End an element, with its local name if it is in the PSF namespace.
+    /// Return the document when its root element is closed.
+    pub(super) fn end_element(
+        &mut self,
+        local_name: Option<&str>,
+        pos: TextPosition,
+    ) -> Result<Option<PrintSchemaDocument>, ParsePrintSchemaError> {
+        self.depth -= 1;
+        let Some(local_name) = local_name else {
+            return Ok(None);
+        };
+        let extra = self
+            .lossless
+            .as_mut()
+            .map(LosslessContext::end_schema_element)
+            .unwrap_or_default();
+        if let Some(skip) = self.skip {
+            if self.depth < skip {
+                self.skip = None;
+            }
+            return Ok(None);
+        }
+        match local_name {
+            "PrintCapabilities" => {
+                return Ok(Some(
+                    PrintCapabilitiesDocument {
+                        parameter_defs: self.parameter_def_container.take().unwrap(),
+                        features: self.feature_containers.pop().unwrap(),
+                        properties: self.property_containers.pop().unwrap(),
+                        extra,
+                    }
+                    .into(),
+                ));
+            }
+            "PrintTicket" => {
+                return Ok(Some(
+                    PrintTicketDocument {
+                        parameter_inits: self.parameter_init_container.take().unwrap(),
+                        features: self.feature_containers.pop().unwrap(),
+                        properties: self.property_containers.pop().unwrap(),
+                        extra,
+                    }
+                    .into(),
+                ));
+            }
+            "ParameterDef" => {
+                // element should be paired, so it's safe to unwrap
+                let parameter_def_name = self.parameter_def_name.take().unwrap();
+                let properties = self.property_containers.pop().unwrap();
+
+                let Some(parent) = self.parameter_def_container.as_mut() else {
+                    return self.reject(pos, "ParameterDef cannot be here");
+                };
+                parent.push(ParameterDef {
+                    name: parameter_def_name,
+                    properties,
+                    extra,
+                });
+            }
+            "ParameterInit" => {
+                // element should be paired, so it's safe to unwrap
+                let parameter_init_name = self.parameter_init_name.take().unwrap();
+
+                // value may not be found
+                // check it, and if not found, reject it
+                let Some(value) = self.parsed_value.take() else {
+                    return self.reject(pos, "ParameterInit value not found");
+                };
+
+                let Some(parent) = self.parameter_init_container.as_mut() else {
+                    return self.reject(pos, "ParameterInit cannot be here");
+                };
+                parent.push(ParameterInit {
+                    name: parameter_init_name,
+                    value,
+                    extra,
+                });
+            }
+            "Feature" => {
+                // element should be paired, so it's safe to unwrap
+                let frature_name = self.feature_name.pop().unwrap();
+                let features = self.feature_containers.pop().unwrap();
+                let properties = self.property_containers.pop().unwrap();
+                let options = self.option_containers.pop().unwrap();
+
+                let Some(parent) = self.feature_containers.last_mut() else {
+                    return self.reject(pos, "Feature cannot be here");
+                };
+                parent.push(PrintFeature {
+                    name: frature_name,
+                    properties,
+                    options,
+                    features,
+                    extra,
+                });
+            }
+            "Option" => {
+                // element should be paired, so it's safe to unwrap
+                let option_name = self.option_name.take();
+                let properties = self.property_containers.pop().unwrap();
+                let scored_properties = self.scored_property_containers.pop().unwrap();
+
+                let Some(parent) = self.option_containers.last_mut() else {
+                    return self.reject(pos, "Option cannot be here");
+                };
+                parent.push(PrintFeatureOption {
+                    name: option_name,
+                    scored_properties,
+                    properties,
+                    extra,
+                });
+            }
+            "ScoredProperty" => {
+                // element should be paired, so it's safe to unwrap
+                let scored_property_name = self.scored_property_name.pop().unwrap();
+                let properties = self.property_containers.pop().unwrap();
+                let scored_properties = self.scored_property_containers.pop().unwrap();
+
+                let Some(parent) = self.scored_property_containers.last_mut() else {
+                    return self.reject(pos, "ScoredProperty cannot be here");
+                };
+                parent.push(ScoredProperty {
+                    name: scored_property_name,
+                    parameter_ref: self.parameter_ref.take(),
+                    value: self.parsed_value.take(),
+                    properties,
+                    scored_properties,
+                    extra,
+                });
+            }
+            "Property" => {
+                // element should be paired, so it's safe to unwrap
+                let property_name = self.property_name.pop().unwrap();
+                let properties = self.property_containers.pop().unwrap();
+
+                let Some(parent) = self.property_containers.last_mut() else {
+                    return self.reject(pos, "Property cannot be here");
+                };
+                parent.push(Property {
+                    name: property_name,
+                    value: self.parsed_value.take(),
+                    properties,
+                    extra,
+                });
+            }
+            "Value" => {
+                if let Some(value_context) = self.value_context.take() {
+                    // keep the raw text of an invalid value in lenient mode, or for the types checked lately
+                    let keep = self.diagnostics.is_some()
+                        || keeps_invalid_value(&value_context.value_type);
+                    let raw = keep.then(|| {
+                        PropertyValue::Unknown(
+                            value_context.value_type.clone(),
+                            value_context.value.clone(),
+                        )
+                    });
+                    let value = match (value_context.parse(), raw) {
+                        (Ok(value), _) => value,
+                        (
+                            Err(ParsePrintSchemaError::InvalidPrintSchema { pos, reason }),
+                            Some(raw),
+                        ) => {
+                            self.report(pos, reason, DiagnosticAction::KeptRaw);
+                            raw
                         }
+                        (Err(e), _) => return Err(e),
+                    };
+                    self.parsed_value.replace(value);
+                }
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    /// Append text to the value being parsed.
+    pub(super) fn characters(&mut self, s: &str) {
+        if let Some(c) = self.value_context.as_mut() {
+            c.value.push_str(s);
+        }
+    }
+
+    /// Append whitespace to the value being parsed.
+    pub(super) fn whitespace(&mut self) {
+        if let Some(c) = self.value_context.as_mut() {
+            c.value.push(' ');
+        }
+    }
+
+    /// Handle an event, and return the document when its root element is closed.
+    pub(super) fn handle(
+        &mut self,
+        event: XmlEvent,
+        pos: TextPosition,
+    ) -> Result<Option<PrintSchemaDocument>, ParsePrintSchemaError> {
+        match event {
+            XmlEvent::StartElement {
+                name,
+                attributes,
+                namespace,
+            } => {
+                if let Some(lossless) = self.lossless.as_mut() {
+                    if lossless.in_raw_element() || name.namespace_ref() != Some(NS_PSF) {
+                        lossless.start_raw_element(name, attributes, namespace);
+                        self.start_element(None, pos)?;
+                        return Ok(None);
                     }
+                    lossless.start_schema_element(&attributes, &namespace);
                 }
-                XmlEvent::EndElement { name } => {
-                    depth -= 1;
-
-                    if name.namespace_ref() == Some(NS_PSF) {
-                        match name.local_name.as_str() {
-                            "PrintCapabilities" => {
-                                return Ok(PrintCapabilitiesDocument {
-                                    parameter_defs: parameter_def_container.unwrap(),
-                                    features: feature_containers.pop().unwrap(),
-                                    properties: property_containers.pop().unwrap(),
-                                }
-                                .into());
-                            }
-                            "PrintTicket" => {
-                                return Ok(PrintTicketDocument {
-                                    parameter_inits: parameter_init_container.unwrap(),
-                                    features: feature_containers.pop().unwrap(),
-                                    properties: property_containers.pop().unwrap(),
-                                }
-                                .into());
-                            }
-                            "ParameterDef" => {
-                                // element should be paired, so it's safe to unwrap
-                                let parameter_def_name = parameter_def_name.take().unwrap();
-                                let properties = property_containers.pop().unwrap();
-
-                                let parent = parameter_def_container.as_mut().ok_or_else(|| {
-                                    ParsePrintSchemaError::InvalidPrintSchema {
-                                        pos: reader.position(),
-                                        reason: "ParameterDef cannot be here".to_string(),
-                                    }
-                                })?;
-                                parent.push(ParameterDef {
-                                    name: parameter_def_name,
-                                    properties,
-                                });
-                            }
-                            "ParameterInit" => {
-                                // element should be paired, so it's safe to unwrap
-                                let parameter_init_name = parameter_init_name.take().unwrap();
-
-                                // value may not be found
-                                // check it, and if not found, return error
-                                let value = parsed_value.take().ok_or_else(|| {
-                                    ParsePrintSchemaError::InvalidPrintSchema {
-                                        pos: reader.position(),
-                                        reason: "ParameterInit value not found".to_string(),
-                                    }
-                                })?;
-
-                                let parent =
-                                    parameter_init_container.as_mut().ok_or_else(|| {
-                                        ParsePrintSchemaError::InvalidPrintSchema {
-                                            pos: reader.position(),
-                                            reason: "ParameterInit cannot be here".to_string(),
-                                        }
-                                    })?;
-                                parent.push(ParameterInit {
-                                    name: parameter_init_name,
-                                    value,
-                                });
-                            }
-                            "Feature" => {
-                                // element should be paired, so it's safe to unwrap
-                                let frature_name = feature_name.pop().unwrap();
-                                let features = feature_containers.pop().unwrap();
-                                let properties = property_containers.pop().unwrap();
-                                let options = option_containers.pop().unwrap();
-
-                                let parent = feature_containers.last_mut().ok_or_else(|| {
-                                    ParsePrintSchemaError::InvalidPrintSchema {
-                                        pos: reader.position(),
-                                        reason: "Feature cannot be here".to_string(),
-                                    }
-                                })?;
-                                parent.push(PrintFeature {
-                                    name: frature_name,
-                                    properties,
-                                    options,
-                                    features,
-                                });
-                            }
-                            "Option" => {
-                                // element should be paired, so it's safe to unwrap
-                                let option_name = option_name.take();
-                                let properties = property_containers.pop().unwrap();
-                                let scored_properties = scored_property_containers.pop().unwrap();
-
-                                let parent = option_containers.last_mut().ok_or_else(|| {
-                                    ParsePrintSchemaError::InvalidPrintSchema {
-                                        pos: reader.position(),
-                                        reason: "Option cannot be here".to_string(),
-                                    }
-                                })?;
-                                parent.push(PrintFeatureOption {
-                                    name: option_name,
-                                    scored_properties,
-                                    properties,
-                                });
-                            }
-                            "ScoredProperty" => {
-                                // element should be paired, so it's safe to unwrap
-                                let scored_property_name = scored_property_name.pop().unwrap();
-                                let properties = property_containers.pop().unwrap();
-                                let scored_properties = scored_property_containers.pop().unwrap();
-
-                                let parent =
-                                    scored_property_containers.last_mut().ok_or_else(|| {
-                                        ParsePrintSchemaError::InvalidPrintSchema {
-                                            pos: reader.position(),
-                                            reason: "ScoredProperty cannot be here".to_string(),
-                                        }
-                                    })?;
-                                parent.push(ScoredProperty {
-                                    name: scored_property_name,
-                                    parameter_ref: parameter_ref.take(),
-                                    value: parsed_value.take(),
-                                    properties,
-                                    scored_properties,
-                                });
-                            }
-                            "Property" => {
-                                // element should be paired, so it's safe to unwrap
-                                let property_name = property_name.pop().unwrap();
-                                let properties = property_containers.pop().unwrap();
-
-                                let parent = property_containers.last_mut().ok_or_else(|| {
-                                    ParsePrintSchemaError::InvalidPrintSchema {
-                                        pos: reader.position(),
-                                        reason: "Property cannot be here".to_string(),
-                                    }
-                                })?;
-                                parent.push(Property {
-                                    name: property_name,
-                                    value: parsed_value.take(),
-                                    properties,
-                                });
-                            }
-                            "Value" => {
-                                if let Some(value_context) = value_context.take() {
-                                    parsed_value.replace(value_context.parse()?);
-                                }
-                            }
-                            _ => {}
-                        }
+
+                let element = (name.namespace_ref() == Some(NS_PSF)).then(|| SchemaStart {
+                    local_name: &name.local_name,
+                    display: &name,
+                    name: parse_name_attribute(&attributes, &namespace),
+                    value_type: parse_type_attribute(&attributes, &namespace),
+                    namespace: Some(namespace),
+                });
+                self.start_element(element, pos)?;
+                // the place of a skipped element is not kept, so that its siblings keep their order
+                if let (Some(lossless), Some(kind), None) = (
+                    self.lossless.as_mut(),
+                    schema_element(&name.local_name),
+                    self.skip,
+                ) {
+                    lossless.accept_schema_element(kind);
+                }
+            }
+            XmlEvent::EndElement { name } => {
+                if let Some(lossless) = self.lossless.as_mut() {
+                    if lossless.in_raw_element() {
+                        lossless.end_raw_element();
+                        return self.end_element(None, pos);
                     }
                 }
-                XmlEvent::Characters(s) => {
-                    if let Some(c) = value_context.as_mut() {
-                        c.value.push_str(&s);
+                let local_name =
+                    (name.namespace_ref() == Some(NS_PSF)).then_some(name.local_name.as_str());
+                return self.end_element(local_name, pos);
+            }
+            XmlEvent::Characters(s) => {
+                if let Some(lossless) = self.lossless.as_mut() {
+                    if lossless.in_raw_element() || self.value_context.is_none() {
+                        lossless.push_node(XmlNode::Characters(s));
+                        return Ok(None);
                     }
                 }
-                XmlEvent::Whitespace(_) => {
-                    if let Some(c) = value_context.as_mut() {
-                        c.value.push(' ');
+                self.characters(&s);
+            }
+            XmlEvent::Whitespace(s) => {
+                if let Some(lossless) = self.lossless.as_mut() {
+                    if lossless.in_raw_element() || self.value_context.is_none() {
+                        lossless.push_node(XmlNode::Whitespace(s));
+                        return Ok(None);
                     }
                 }
-                XmlEvent::CData(s) => {
-                    if let Some(c) = value_context.as_mut() {
-                        c.value.push_str(&s);
+                self.whitespace();
+            }
+            XmlEvent::CData(s) => {
+                if let Some(lossless) = self.lossless.as_mut() {
+                    if lossless.in_raw_element() || self.value_context.is_none() {
+                        lossless.push_node(XmlNode::CData(s));
+                        return Ok(None);
                     }
                 }
-                XmlEvent::EndDocument => break,
-                _ => {}
+                self.characters(&s);
+            }
+            XmlEvent::Comment(s) => {
+                if let Some(lossless) = self.lossless.as_mut() {
+                    lossless.push_node(XmlNode::Comment(s));
+                }
+            }
+            XmlEvent::ProcessingInstruction { name, data } => {
+                if let Some(lossless) = self.lossless.as_mut() {
+                    lossless.push_node(XmlNode::ProcessingInstruction { name, data });
+                }
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+}
+
+impl ParsableXmlDocument for PrintSchemaDocument {
+    type Error = ParsePrintSchemaError;
+    fn parse<R>(reader: &mut EventReader<R>) -> Result<Self, Self::Error>
+    where
+        R: std::io::Read,
+    {
+        Self::parse_with_options(reader, &ParseOptions::default())
+    }
+
+    fn parse_with_diagnostics<R>(
+        reader: &mut EventReader<R>,
+        options: &ParseOptions,
+    ) -> Result<(Self, Vec<ParseDiagnostic>), Self::Error>
+    where
+        R: std::io::Read,
+    {
+        let mut builder = SchemaBuilder::new(options);
+        loop {
+            let e = match reader.next() {
+                Ok(e) => e,
+                Err(e) => return Err(ParsePrintSchemaError::InvalidXml(e)),
+            };
+            if let XmlEvent::EndDocument = e {
+                break;
+            }
+            if let Some(document) = builder.handle(e, reader.position())? {
+                return Ok((document, builder.take_diagnostics()));
             }
         }
 
@@ -450,6 +955,25 @@ impl ParsableXmlDocument for PrintSchemaDocument {
             reason: "No valid root element found".to_string(),
         })
     }
+
+    /// Parse the XML document from the given bytes, with the given options.
+    /// Return the diagnostics of the lenient mode as well, which are always empty otherwise.
+    ///
+    /// Unless the document is kept losslessly, UTF-8 input is parsed by a faster reader which borrows names and text
+    /// from the input. Other input, and input which is not well-formed, is parsed by [`EventReader`].
+    fn parse_from_bytes_with_diagnostics(
+        xml: impl AsRef<[u8]>,
+        options: &ParseOptions,
+    ) -> Result<(Self, Vec<ParseDiagnostic>), Self::Error> {
+        let xml = xml.as_ref();
+        if !options.lossless {
+            if let Some(result) = fast_reader::parse(xml, options) {
+                return result;
+            }
+        }
+        let mut reader = options.parser_config().create_reader(Cursor::new(xml));
+        Self::parse_with_diagnostics(&mut reader, options)
+    }
 }
 
 impl ParsableXmlDocument for PrintCapabilitiesDocument {
@@ -458,13 +982,39 @@ impl ParsableXmlDocument for PrintCapabilitiesDocument {
     where
         R: std::io::Read,
     {
-        PrintSchemaDocument::parse(reader).and_then(|x| match x {
-            PrintSchemaDocument::PrintCapabilities(document) => Ok(document),
-            PrintSchemaDocument::PrintTicket(_) => Err(ParsePrintSchemaError::WrongDocumentType {
-                expected: "PrintCapabilities",
-                found: "PrintTicket",
-            }),
-        })
+        Self::parse_with_options(reader, &ParseOptions::default())
+    }
+
+    fn parse_with_diagnostics<R>(
+        reader: &mut EventReader<R>,
+        options: &ParseOptions,
+    ) -> Result<(Self, Vec<ParseDiagnostic>), Self::Error>
+    where
+        R: std::io::Read,
+    {
+        let (document, diagnostics) = PrintSchemaDocument::parse_with_diagnostics(reader, options)?;
+        Ok((into_print_capabilities(document)?, diagnostics))
+    }
+
+    fn parse_from_bytes_with_diagnostics(
+        xml: impl AsRef<[u8]>,
+        options: &ParseOptions,
+    ) -> Result<(Self, Vec<ParseDiagnostic>), Self::Error> {
+        let (document, diagnostics) =
+            PrintSchemaDocument::parse_from_bytes_with_diagnostics(xml, options)?;
+        Ok((into_print_capabilities(document)?, diagnostics))
+    }
+}
+
+fn into_print_capabilities(
+    document: PrintSchemaDocument,
+) -> Result<PrintCapabilitiesDocument, ParsePrintSchemaError> {
+    match document {
+        PrintSchemaDocument::PrintCapabilities(document) => Ok(document),
+        PrintSchemaDocument::PrintTicket(_) => Err(ParsePrintSchemaError::WrongDocumentType {
+            expected: "PrintCapabilities",
+            found: "PrintTicket",
+        }),
     }
 }
 
@@ -474,23 +1024,50 @@ impl ParsableXmlDocument for PrintTicketDocument {
     where
         R: std::io::Read,
     {
-        PrintSchemaDocument::parse(reader).and_then(|x| match x {
-            PrintSchemaDocument::PrintTicket(document) => Ok(document),
-            PrintSchemaDocument::PrintCapabilities(_) => {
-                Err(ParsePrintSchemaError::WrongDocumentType {
-                    expected: "PrintTicket",
-                    found: "PrintCapabilities",
-                })
-            }
-        })
+        Self::parse_with_options(reader, &ParseOptions::default())
+    }
+
+    fn parse_with_diagnostics<R>(
+        reader: &mut EventReader<R>,
+        options: &ParseOptions,
+    ) -> Result<(Self, Vec<ParseDiagnostic>), Self::Error>
+    where
+        R: std::io::Read,
+    {
+        let (document, diagnostics) = PrintSchemaDocument::parse_with_diagnostics(reader, options)?;
+        Ok((into_print_ticket(document)?, diagnostics))
+    }
+
+    fn parse_from_bytes_with_diagnostics(
+        xml: impl AsRef<[u8]>,
+        options: &ParseOptions,
+    ) -> Result<(Self, Vec<ParseDiagnostic>), Self::Error> {
+        let (document, diagnostics) =
+            PrintSchemaDocument::parse_from_bytes_with_diagnostics(xml, options)?;
+        Ok((into_print_ticket(document)?, diagnostics))
+    }
+}
+
+fn into_print_ticket(
+    document: PrintSchemaDocument,
+) -> Result<PrintTicketDocument, ParsePrintSchemaError> {
+    match document {
+        PrintSchemaDocument::PrintTicket(document) => Ok(document),
+        PrintSchemaDocument::PrintCapabilities(_) => {
+            Err(ParsePrintSchemaError::WrongDocumentType {
+                expected: "PrintTicket",
+                found: "PrintCapabilities",
+            })
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{ParsableXmlDocument, ParsePrintSchemaError};
+    use super::{DiagnosticAction, ParsableXmlDocument, ParseOptions, ParsePrintSchemaError};
     use crate::ticket::document::{
-        PrintCapabilitiesDocument, PrintSchemaDocument, PrintTicketDocument,
+        writer::XmlDocumentRoot, PrintCapabilitiesDocument, PrintSchemaDocument,
+        PrintTicketDocument, PropertyValue, XmlNode,
     };
 
     #[test]
@@ -579,4 +1156,222 @@ mod tests {
         let xml = include_bytes!("../../../test_data/print_capabilities.xml");
         let _document = PrintCapabilitiesDocument::parse_from_bytes(xml).unwrap();
     }
+
+    #[test]
+    fn extra_is_only_kept_in_lossless_mode() {
+        let xml = include_bytes!("../../../test_data/print_ticket.xml");
+        let document = PrintTicketDocument::parse_from_bytes(xml).unwrap();
+        assert!(document.extra.is_empty());
+        assert!(document.features.iter().all(|x| x.extra.is_empty()));
+
+        let options = ParseOptions::new().lossless(true);
+        let document = PrintTicketDocument::parse_from_bytes_with_options(xml, &options).unwrap();
+        assert!(!document.extra.namespaces.is_empty());
+        assert!(document
+            .extra
+            .nodes()
+            .all(|x| matches!(x, XmlNode::Whitespace(_))));
+    }
+
+    fn ticket_xml(value_type: &str, value: &str) -> String {
+        format!(
+            r#"<psf:PrintTicket version="1"
+    xmlns:psf="http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework"
+    xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xmlns:xsd="http://www.w3.org/2001/XMLSchema"
+    xmlns:ns0000="http://vendor/private">
+    <psf:ParameterInit name="ns0000:Value">
+        <psf:Value xsi:type="xsd:{}">{}</psf:Value>
+    </psf:ParameterInit>
+</psf:PrintTicket>"#,
+            value_type, value
+        )
+    }
+
+    fn ticket_with_value(
+        value_type: &str,
+        value: &str,
+    ) -> Result<PrintTicketDocument, ParsePrintSchemaError> {
+        PrintTicketDocument::parse_from_bytes(ticket_xml(value_type, value))
+    }
+
+    fn parse_value(value_type: &str, value: &str) -> PropertyValue {
+        ticket_with_value(value_type, value)
+            .unwrap()
+            .parameter_inits
+            .remove(0)
+            .value
+    }
+
+    #[test]
+    fn typed_values() {
+        assert_eq!(parse_value("integer", " 42 ").integer(), Some(42));
+        let value = parse_value("integer", "+0012345678901234567890");
+        assert_eq!(value.big_integer(), Some("12345678901234567890"));
+        assert_eq!(value.saturating_integer(), Some(i32::MAX));
+        assert_eq!(parse_value("decimal", "-1.50").decimal(), Some(-1.5));
+        assert_eq!(parse_value("boolean", "1").boolean(), Some(true));
+        assert_eq!(
+            parse_value("base64Binary", "AAEC\n/w==").base64_binary(),
+            Some(&[0, 1, 2, 255][..])
+        );
+        let value = parse_value("dateTime", "2024-05-01T08:30:00Z");
+        assert_eq!(value.date_time().map(|x| (x.year, x.hour)), Some((2024, 8)));
+
+        assert!(matches!(
+            ticket_with_value("integer", "1.0"),
+            Err(ParsePrintSchemaError::InvalidPrintSchema { .. })
+        ));
+        for (value_type, value) in [
+            ("decimal", "1e3"),
+            ("boolean", "yes"),
+            ("base64Binary", "A"),
+            ("dateTime", "2024-05-01"),
+        ] {
+            assert!(matches!(
+                parse_value(value_type, value),
+                PropertyValue::Unknown(t, v) if t.local_name == value_type && v == value
+            ));
+            let xml = ticket_xml(value_type, value);
+            let (_, diagnostics) = PrintTicketDocument::parse_from_bytes_with_diagnostics(
+                xml,
+                &ParseOptions::new().lenient(true),
+            )
+            .unwrap();
+            assert_eq!(diagnostics.len(), 1);
+            assert_eq!(diagnostics[0].action, DiagnosticAction::KeptRaw);
+        }
+    }
+
+    #[test]
+    fn typed_values_round_trip() {
+        for (value_type, value) in [
+            ("integer", "-98765432109876543210"),
+            ("decimal", "0.125"),
+            ("boolean", "false"),
+            ("base64Binary", "3q2+7w=="),
+            ("dateTime", "2024-05-01T08:30:00.5-07:00"),
+        ] {
+            let document = ticket_with_value(value_type, value).unwrap();
+            let xml = String::from_utf8(document.to_xml()).unwrap();
+            assert!(xml.contains(&format!(
+                r#"<psf:Value xsi:type="xsd:{}">{}</psf:Value>"#,
+                value_type, value
+            )));
+            assert_eq!(
+                PrintTicketDocument::parse_from_bytes(xml).unwrap(),
+                document
+            );
+        }
+    }
+
+    const BUGGY_TICKET: &str = r#"<psf:PrintTicket version="1"
+    xmlns:psf="http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework"
+    xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xmlns:xsd="http://www.w3.org/2001/XMLSchema"
+    xmlns:psk="http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords">
+    <psf:Feature>
+        <psf:Option name="psk:ISOA4" />
+    </psf:Feature>
+    <psf:Feature name="psk:PageOrientation">
+        <psf:Option>
+            <psf:Property name="psk:Angle">
+                <psf:Value xsi:type="xsd:integer">9O</psf:Value>
+            </psf:Property>
+        </psf:Option>
+        <psf:Unknown><psf:Option name="psk:Landscape" /></psf:Unknown>
+    </psf:Feature>
+    <psf:ParameterInit name="psk:JobCopiesAllDocuments" />
+    <psf:ParameterInit>
+        <psf:Value xsi:type="xsd:integer">1</psf:Value>
+    </psf:ParameterInit>
+    <psf:ParameterInit name="psk:DocumentCopiesAllPages">
+        <psf:Value xsi:type="xsd:integer"> 2 </psf:Value>
+    </psf:ParameterInit>
+</psf:PrintTicket>"#;
+
+    #[test]
+    fn lenient_mode_collects_diagnostics() {
+        assert!(matches!(
+            PrintTicketDocument::parse_from_bytes(BUGGY_TICKET),
+            Err(ParsePrintSchemaError::InvalidPrintSchema { .. })
+        ));
+
+        let options = ParseOptions::new().lenient(true);
+        let (document, diagnostics) =
+            PrintTicketDocument::parse_from_bytes_with_diagnostics(BUGGY_TICKET, &options).unwrap();
+        let summary = diagnostics
+            .iter()
+            .map(|x| (x.pos.row + 1, x.reason.as_str(), x.action))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            [
+                (5, "Feature name not found", DiagnosticAction::Skipped),
+                (11, "Invalid integer", DiagnosticAction::KeptRaw),
+                (
+                    14,
+                    "Invalid element: {http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework}psf:Unknown",
+                    DiagnosticAction::Skipped
+                ),
+                (16, "ParameterInit value not found", DiagnosticAction::Skipped),
+                (17, "ParameterInit name not found", DiagnosticAction::Skipped),
+            ]
+        );
+
+        assert_eq!(document.features.len(), 1);
+        let options = &document.features[0].options;
+        assert_eq!(options.len(), 1);
+        assert!(options[0].name.is_none());
+        assert!(matches!(
+            &options[0].properties[0].value,
+            Some(PropertyValue::Unknown(t, v)) if t.local_name == "integer" && v == "9O"
+        ));
+        assert_eq!(document.parameter_inits.len(), 1);
+        assert_eq!(document.parameter_inits[0].value, PropertyValue::Integer(2));
+    }
+
+    #[test]
+    fn lenient_lossless_round_trip() {
+        let skipped = r#"<psf:Feature>
+        <psf:Option name="psk:ISOA4" />
+    </psf:Feature>"#;
+        let xml = format!(
+            r#"<psf:PrintTicket version="1"
+    xmlns:psf="http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework"
+    xmlns:psk="http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords"
+    xmlns:ns0000="http://vendor/private">
+    <!-- first -->
+    {}
+    <!-- second -->
+    <ns0000:Private />
+    <psf:Feature name="psk:PageOrientation">
+        <psf:Option name="psk:Portrait" />
+    </psf:Feature>
+    <!-- third -->
+</psf:PrintTicket>"#,
+            skipped
+        );
+        let options = ParseOptions::new().lenient(true).lossless(true);
+        let (document, diagnostics) =
+            PrintTicketDocument::parse_from_bytes_with_diagnostics(&xml, &options).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        let expected = PrintTicketDocument::parse_from_bytes_with_options(
+            xml.replace(skipped, ""),
+            &ParseOptions::new().lossless(true),
+        )
+        .unwrap();
+        assert_eq!(document.to_xml(), expected.to_xml());
+    }
+
+    #[test]
+    fn lenient_mode_without_diagnostics() {
+        let xml = include_bytes!("../../../test_data/print_capabilities.xml");
+        let options = ParseOptions::new().lenient(true);
+        let (document, diagnostics) =
+            PrintCapabilitiesDocument::parse_from_bytes_with_diagnostics(xml, &options).unwrap();
+        assert!(diagnostics.is_empty());
+        assert_eq!(
+            document,
+            PrintCapabilitiesDocument::parse_from_bytes(xml).unwrap()
+        );
+    }
 }
@@ -0,0 +1,354 @@
+use super::{
+    fast_reader::{xml_error, Events, ReadError, ReaderState},
+    print_schema::same_name,
+    reader::{ParseDiagnostic, ParseOptions, ParsePrintSchemaError, SchemaBuilder},
+    PrintFeature, NS_PSF,
+};
+use quick_xml::events::Event;
+use std::borrow::Cow;
+use xml::name::OwnedName;
+
+/// A pull reader which yields the top-level features of a Print Schema document as they are parsed.
+///
+/// Unlike [`ParsableXmlDocument`](super::reader::ParsableXmlDocument), it never builds the whole document.
+/// Features which are not requested, as well as the parameters and properties at the top level,
+/// are skipped by counting the depth of their elements, without decoding them.
+///
+/// The input is read in UTF-8, or in UTF-16 when it starts with a byte order mark.
+///
+/// # Examples
+/// ```
+/// use winprint::ticket::document::{stream::FeatureReader, OwnedName, NS_PSK};
+///
+/// let xml = br#"<psf:PrintCapabilities version="1"
+///     xmlns:psf="http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework"
+///     xmlns:psk="http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords">
+///     <psf:Feature name="psk:PageMediaSize"><psf:Option name="psk:ISOA4" /></psf:Feature>
+///     <psf:Feature name="psk:PageOrientation"><psf:Option name="psk:Portrait" /></psf:Feature>
+/// </psf:PrintCapabilities>"#;
+/// let features = FeatureReader::from_bytes(xml)
+///     .only([OwnedName::qualified("PageOrientation", NS_PSK, Some("psk"))])
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// assert_eq!(features.len(), 1);
+/// assert_eq!(features[0].options.len(), 1);
+/// ```
+pub struct FeatureReader<'a> {
+    bytes: &'a [u8],
+    /// The decoded input, which is `None` if the encoding is not supported.
+    xml: Option<Cow<'a, str>>,
+    state: ReaderState,
+    options: ParseOptions,
+    names: Option<Vec<OwnedName>>,
+    finished: bool,
+    diagnostics: Vec<ParseDiagnostic>,
+}
+
+impl<'a> FeatureReader<'a> {
+    /// Create a reader over the given bytes.
+    pub fn from_bytes(xml: &'a [u8]) -> Self {
+        Self::from_bytes_with_options(xml, &ParseOptions::default())
+    }
+
+    /// Create a reader over the given bytes, with the given options.
+    pub fn from_bytes_with_options(xml: &'a [u8], options: &ParseOptions) -> Self {
+        Self {
+            bytes: xml,
+            xml: decode(xml),
+            state: ReaderState::new(options),
+            options: options.clone(),
+            names: None,
+            finished: false,
+            diagnostics: Vec::new(),
+        }
+    }
+
+    /// Only yield the features with the given names, compared by namespace and local name.
+    ///
+    /// The reader stops once every requested feature is found, without reading the rest of the document.
+    pub fn only(mut self, names: impl IntoIterator<Item = OwnedName>) -> Self {
+        self.names = Some(names.into_iter().collect());
+        self
+    }
+
+    /// Get the diagnostics of the lenient mode for the features read so far.
+    ///
+    /// A feature which is skipped in lenient mode is not yielded.
+    pub fn diagnostics(&self) -> &[ParseDiagnostic] {
+        &self.diagnostics
+    }
+
+    fn next_feature(&mut self) -> Result<Option<PrintFeature>, ParsePrintSchemaError> {
+        let Some(xml) = self.xml.as_deref() else {
+            return Err(xml_error(self.bytes, &self.options));
+        };
+        let mut events = Events::resume(xml, &mut self.state);
+        let result = read_feature(
+            &mut events,
+            &self.options,
+            &mut self.names,
+            &mut self.diagnostics,
+        );
+        match result {
+            Ok(feature) => Ok(feature),
+            Err(ReadError::Schema(e)) => Err(e),
+            Err(ReadError::Malformed) => Err(xml_error(self.bytes, &self.options)),
+        }
+    }
+}
+
+impl Iterator for FeatureReader<'_> {
+    type Item = Result<PrintFeature, ParsePrintSchemaError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let result = self.next_feature().transpose();
+        if !matches!(result, Some(Ok(_))) {
+            self.finished = true;
+        }
+        result
+    }
+}
+
+/// Decode the input as UTF-8, or as UTF-16 if it starts with a byte order mark.
+fn decode(xml: &[u8]) -> Option<Cow<'_, str>> {
+    if let Ok(xml) = std::str::from_utf8(xml) {
+        return Some(Cow::Borrowed(xml));
+    }
+    let (units, from_bytes): (_, fn([u8; 2]) -> u16) = match xml {
+        [0xFF, 0xFE, units @ ..] => (units, u16::from_le_bytes),
+        [0xFE, 0xFF, units @ ..] => (units, u16::from_be_bytes),
+        _ => return None,
+    };
+    if units.len() % 2 != 0 {
+        return None;
+    }
+    let units = units.chunks_exact(2).map(|x| from_bytes([x[0], x[1]]));
+    let xml = char::decode_utf16(units)
+        .collect::<Result<String, _>>()
+        .ok()?;
+    Some(Cow::Owned(xml))
+}
+
+/// Read up to the next requested feature, and return `None` at the end of the root element.
+fn read_feature(
+    events: &mut Events<'_, '_>,
+    options: &ParseOptions,
+    names: &mut Option<Vec<OwnedName>>,
+    diagnostics: &mut Vec<ParseDiagnostic>,
+) -> Result<Option<PrintFeature>, ReadError> {
+    loop {
+        if names.as_ref().is_some_and(Vec::is_empty) {
+            return Ok(None);
+        }
+        let event = events.next()?;
+        let (element, empty) = match &event {
+            Event::Start(element) => (element, false),
+            Event::Empty(element) => (element, true),
+            Event::End(_) if events.depth() == 0 => return Ok(None),
+            Event::Eof => return Err(ReadError::Malformed),
+            _ => continue,
+        };
+        let (name, feature_name) = events.element_name(element)?;
+        let is_psf = name.namespace_ref() == Some(NS_PSF);
+        if events.depth() == usize::from(!empty) {
+            if !is_psf
+                || !matches!(
+                    name.local_name.as_str(),
+                    "PrintCapabilities" | "PrintTicket"
+                )
+            {
+                return Err(ParsePrintSchemaError::InvalidPrintSchema {
+                    pos: events.position(),
+                    reason: format!("Invalid root element: {}", name),
+                }
+                .into());
+            }
+            if empty {
+                return Ok(None);
+            }
+            events.enter(element)?;
+            continue;
+        }
+
+        let wanted = is_psf
+            && name.local_name == "Feature"
+            && match (names.as_mut(), feature_name) {
+                (None, _) => true,
+                (Some(names), Some(feature_name)) => {
+                    let index = names.iter().position(|x| same_name(x, &feature_name));
+                    index.map(|x| names.swap_remove(x)).is_some()
+                }
+                (Some(_), None) => false,
+            };
+        if !wanted {
+            if !empty {
+                events.skip()?;
+            }
+            continue;
+        }
+
+        let mut builder = SchemaBuilder::for_feature(options);
+        events.feed(&mut builder, event)?;
+        while builder.depth() > 0 {
+            let event = events.next()?;
+            events.feed(&mut builder, event)?;
+        }
+        diagnostics.extend(builder.take_diagnostics());
+        if let Some(feature) = builder.take_feature() {
+            return Ok(Some(feature));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FeatureReader;
+    use crate::ticket::document::{
+        reader::{ParsableXmlDocument, ParseOptions, ParsePrintSchemaError},
+        writer::{SerializeOptions, XmlDocumentRoot, XmlEncoding},
+        OwnedName, PrintCapabilitiesDocument, NS_PSK,
+    };
+
+    const PRINT_CAPABILITIES: &[u8] = include_bytes!("../../../test_data/print_capabilities.xml");
+
+    #[test]
+    fn yields_all_top_level_features() {
+        let document = PrintCapabilitiesDocument::parse_from_bytes(PRINT_CAPABILITIES).unwrap();
+        let features = FeatureReader::from_bytes(PRINT_CAPABILITIES)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(features, document.features);
+    }
+
+    #[test]
+    fn yields_only_requested_features() {
+        let document = PrintCapabilitiesDocument::parse_from_bytes(PRINT_CAPABILITIES).unwrap();
+        let names = [
+            OwnedName::qualified("PageOrientation", NS_PSK, Some("other")),
+            OwnedName::qualified("PageMediaSize", NS_PSK, Some("psk")),
+            OwnedName::qualified("NotExisting", NS_PSK, Some("psk")),
+        ];
+        let features = FeatureReader::from_bytes(PRINT_CAPABILITIES)
+            .only(names)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let expected = document
+            .features
+            .iter()
+            .filter(|x| {
+                x.name.namespace_ref() == Some(NS_PSK)
+                    && matches!(
+                        x.name.local_name.as_str(),
+                        "PageOrientation" | "PageMediaSize"
+                    )
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(expected.len(), 2);
+        assert_eq!(features, expected);
+    }
+
+    #[test]
+    fn lossless_features() {
+        let xml = br#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- capabilities -->
+<psf:PrintCapabilities version="1"
+    xmlns:psf="http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework"
+    xmlns:psk="http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords"
+    xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+    xmlns:xsd="http://www.w3.org/2001/XMLSchema">
+    <psf:Property name="psk:Private"><psf:Value xsi:type="xsd:string">skipped</psf:Value></psf:Property>
+    <psf:Feature name="psk:PageMediaSize" xmlns:ns0000="http://vendor" ns0000:flag="1">
+        <!-- sizes --><?vendor hint?>
+        <psf:Option name="psk:ISOA4">
+            <psf:ScoredProperty name="psk:MediaSizeWidth">
+                <psf:Value xsi:type="xsd:integer"><![CDATA[210000]]></psf:Value>
+            </psf:ScoredProperty>
+        </psf:Option>
+        <ns0000:Private><ns0000:Inner a="&amp;" /> text </ns0000:Private>
+    </psf:Feature>
+</psf:PrintCapabilities>"#;
+        let options = ParseOptions::new().lossless(true);
+        let document =
+            PrintCapabilitiesDocument::parse_from_bytes_with_options(xml, &options).unwrap();
+        let features = FeatureReader::from_bytes_with_options(xml, &options)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(features.len(), 1);
+        assert_eq!(features, document.features);
+        let (feature, expected) = (&features[0], &document.features[0]);
+        assert_eq!(feature.extra.attributes, expected.extra.attributes);
+        assert_eq!(feature.extra.content, expected.extra.content);
+        assert_eq!(feature.options[0].extra, expected.options[0].extra);
+        let (property, expected_property) = (
+            &feature.options[0].scored_properties[0],
+            &expected.options[0].scored_properties[0],
+        );
+        assert_eq!(property.extra, expected_property.extra);
+        // the declarations of the root element are in scope of the feature, which stands on its own
+        assert!(feature
+            .extra
+            .namespaces
+            .iter()
+            .any(|(prefix, uri)| prefix == "psk" && uri == NS_PSK));
+    }
+
+    #[test]
+    fn utf16_input() {
+        let document = PrintCapabilitiesDocument::parse_from_bytes(PRINT_CAPABILITIES).unwrap();
+        let utf16 = document
+            .try_to_xml_with_options(&SerializeOptions::new().encoding(XmlEncoding::Utf16Le))
+            .unwrap();
+        let features = FeatureReader::from_bytes(&utf16)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(features, document.features);
+    }
+
+    #[test]
+    fn invalid_xml_stops_the_reader() {
+        let xml = br#"<psf:PrintCapabilities version="1"
+            xmlns:psf="http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework"
+            xmlns:psk="http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords">
+            <psf:Feature name="psk:PageOrientation"></psf:Feature>
+            <psf:Feature name="psk:PageMediaSize"><psf:Unknown /></psf:Feature>
+            <psf:Feature name="psk:JobCopies"></psf:Feature>"#;
+        let mut reader = FeatureReader::from_bytes(xml);
+        assert!(reader.next().unwrap().is_ok());
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().is_none());
+
+        let xml = br#"<psf:PrintCapabilities version="1"
+            xmlns:psf="http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework"
+            xmlns:psk="http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords">
+            <psf:Property name="psk:Skipped"><psf:Value></psf:Property>
+            <psf:Feature name="psk:PageOrientation"></psf:Feature>
+        </psf:PrintCapabilities>"#;
+        let mut reader = FeatureReader::from_bytes(xml);
+        assert!(matches!(
+            reader.next(),
+            Some(Err(ParsePrintSchemaError::InvalidXml(_)))
+        ));
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn lenient_mode_skips_invalid_features() {
+        let xml = br#"<psf:PrintCapabilities version="1"
+            xmlns:psf="http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework"
+            xmlns:psk="http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords">
+            <psf:Feature><psf:Option name="psk:ISOA4" /></psf:Feature>
+            <psf:Feature name="psk:PageOrientation"><psf:Option name="psk:Portrait" /></psf:Feature>
+        </psf:PrintCapabilities>"#;
+        let mut reader =
+            FeatureReader::from_bytes_with_options(xml, &ParseOptions::new().lenient(true));
+        let features = reader.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(features.len(), 1);
+        assert_eq!(features[0].name.local_name, "PageOrientation");
+        assert_eq!(reader.diagnostics().len(), 1);
+        assert_eq!(reader.diagnostics()[0].reason, "Feature name not found");
+    }
+}
@@ -0,0 +1,769 @@
+//! Each method of [`Visit`] and [`VisitMut`] is implemented by default with the free function of the same name,
+//! which visits the children of the node. An overriding method calls the function to keep recursing.
+//!
+//! The `path` passed along is the names of the enclosing nodes, from the outermost one,
+//! without the documents and unnamed options or scored-properties.
+//! It is a single stack shared by the whole traversal, which the functions push to and pop from around the children,
+//! so an overriding method should leave it as it was given.
+//!
+//! The content kept by the lossless mode is visited last for each node, with the path of the node owning it.
+//!
+//! # Examples
+//! ```
+//! use winprint::ticket::document::{
+//!     reader::ParsableXmlDocument,
+//!     visit::{self, Visit},
+//!     OwnedName, PrintFeatureOption, PrintTicketDocument,
+//! };
+//!
+//! #[derive(Default)]
+//! struct OptionNames(Vec<String>);
+//!
+//! impl<'a> Visit<'a> for OptionNames {
+//!     fn visit_option(&mut self, option: &'a PrintFeatureOption, path: &mut Vec<&'a OwnedName>) {
+//!         if let Some(name) = &option.name {
+//!             self.0.push(format!("{}/{}", path[0].local_name, name.local_name));
+//!         }
+//!         visit::visit_option(self, option, path);
+//!     }
+//! }
+//!
+//! let ticket = PrintTicketDocument::parse_from_bytes(br#"<psf:PrintTicket version="1"
+//!     xmlns:psf="http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework"
+//!     xmlns:psk="http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords">
+//!     <psf:Feature name="psk:PageOrientation"><psf:Option name="psk:Landscape" /></psf:Feature>
+//! </psf:PrintTicket>"#).unwrap();
+//! let mut names = OptionNames::default();
+//! names.visit_print_ticket(&ticket);
+//! assert_eq!(names.0, ["PageOrientation/Landscape"]);
+//! ```
+
+use super::{
+    ParameterDef, ParameterInit, PrintCapabilitiesDocument, PrintFeature, PrintFeatureOption,
+    PrintSchemaDocument, PrintTicketDocument, Property, PropertyValue, ScoredProperty, XmlContent,
+    XmlExtra, XmlNode,
+};
+use xml::name::OwnedName;
+
+/// A visitor over the nodes of a Print Schema document.
+pub trait Visit<'a> {
+    /// Visit a document of either type.
+    fn visit_document(&mut self, document: &'a PrintSchemaDocument) {
+        visit_document(self, document);
+    }
+    /// Visit a print capabilities document.
+    fn visit_print_capabilities(&mut self, document: &'a PrintCapabilitiesDocument) {
+        visit_print_capabilities(self, document);
+    }
+    /// Visit a print ticket document.
+    fn visit_print_ticket(&mut self, document: &'a PrintTicketDocument) {
+        visit_print_ticket(self, document);
+    }
+    /// Visit a feature.
+    fn visit_feature(&mut self, feature: &'a PrintFeature, path: &mut Vec<&'a OwnedName>) {
+        visit_feature(self, feature, path);
+    }
+    /// Visit an option.
+    fn visit_option(&mut self, option: &'a PrintFeatureOption, path: &mut Vec<&'a OwnedName>) {
+        visit_option(self, option, path);
+    }
+    /// Visit a scored-property.
+    fn visit_scored_property(
+        &mut self,
+        scored_property: &'a ScoredProperty,
+        path: &mut Vec<&'a OwnedName>,
+    ) {
+        visit_scored_property(self, scored_property, path);
+    }
+    /// Visit a property.
+    fn visit_property(&mut self, property: &'a Property, path: &mut Vec<&'a OwnedName>) {
+        visit_property(self, property, path);
+    }
+    /// Visit a parameter definition.
+    fn visit_parameter_def(
+        &mut self,
+        parameter_def: &'a ParameterDef,
+        path: &mut Vec<&'a OwnedName>,
+    ) {
+        visit_parameter_def(self, parameter_def, path);
+    }
+    /// Visit a parameter initialization.
+    fn visit_parameter_init(
+        &mut self,
+        parameter_init: &'a ParameterInit,
+        path: &mut Vec<&'a OwnedName>,
+    ) {
+        visit_parameter_init(self, parameter_init, path);
+    }
+    /// Visit a value.
+    fn visit_value(&mut self, value: &'a PropertyValue, path: &mut Vec<&'a OwnedName>) {
+        visit_value(self, value, path);
+    }
+    /// Visit the content kept by the lossless mode for a node.
+    fn visit_extra(&mut self, extra: &'a XmlExtra, path: &mut Vec<&'a OwnedName>) {
+        visit_extra(self, extra, path);
+    }
+    /// Visit a name, which is the name of a node, a parameter reference,
+    /// a qualified name value, the type of an unknown-typed value,
+    /// or the name of an element or attribute kept by the lossless mode.
+    fn visit_name(&mut self, _name: &'a OwnedName, _path: &mut Vec<&'a OwnedName>) {}
+}
+
+/// Visit the document of either type.
+pub fn visit_document<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    document: &'a PrintSchemaDocument,
+) {
+    match document {
+        PrintSchemaDocument::PrintCapabilities(x) => visitor.visit_print_capabilities(x),
+        PrintSchemaDocument::PrintTicket(x) => visitor.visit_print_ticket(x),
+    }
+}
+
+/// Visit the properties, parameter definitions, features and lossless content of the document.
+pub fn visit_print_capabilities<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    document: &'a PrintCapabilitiesDocument,
+) {
+    let path = &mut vec![];
+    for x in &document.properties {
+        visitor.visit_property(x, path);
+    }
+    for x in &document.parameter_defs {
+        visitor.visit_parameter_def(x, path);
+    }
+    for x in &document.features {
+        visitor.visit_feature(x, path);
+    }
+    visitor.visit_extra(&document.extra, path);
+}
+
+/// Visit the properties, parameter initializations, features and lossless content of the document.
+pub fn visit_print_ticket<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    document: &'a PrintTicketDocument,
+) {
+    let path = &mut vec![];
+    for x in &document.properties {
+        visitor.visit_property(x, path);
+    }
+    for x in &document.parameter_inits {
+        visitor.visit_parameter_init(x, path);
+    }
+    for x in &document.features {
+        visitor.visit_feature(x, path);
+    }
+    visitor.visit_extra(&document.extra, path);
+}
+
+/// Visit the name, properties, options, sub-features and lossless content of the feature.
+pub fn visit_feature<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    feature: &'a PrintFeature,
+    path: &mut Vec<&'a OwnedName>,
+) {
+    visitor.visit_name(&feature.name, path);
+    path.push(&feature.name);
+    for x in &feature.properties {
+        visitor.visit_property(x, path);
+    }
+    for x in &feature.options {
+        visitor.visit_option(x, path);
+    }
+    for x in &feature.features {
+        visitor.visit_feature(x, path);
+    }
+    visitor.visit_extra(&feature.extra, path);
+    path.pop();
+}
+
+/// Visit the name, scored-properties, properties and lossless content of the option.
+pub fn visit_option<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    option: &'a PrintFeatureOption,
+    path: &mut Vec<&'a OwnedName>,
+) {
+    if let Some(name) = &option.name {
+        visitor.visit_name(name, path);
+    }
+    let len = path.len();
+    path.extend(&option.name);
+    for x in &option.scored_properties {
+        visitor.visit_scored_property(x, path);
+    }
+    for x in &option.properties {
+        visitor.visit_property(x, path);
+    }
+    visitor.visit_extra(&option.extra, path);
+    path.truncate(len);
+}
+
+/// Visit the name, parameter reference, value, scored-properties, properties and lossless content of the scored-property.
+pub fn visit_scored_property<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    scored_property: &'a ScoredProperty,
+    path: &mut Vec<&'a OwnedName>,
+) {
+    if let Some(name) = &scored_property.name {
+        visitor.visit_name(name, path);
+    }
+    let len = path.len();
+    path.extend(&scored_property.name);
+    if let Some(parameter_ref) = &scored_property.parameter_ref {
+        visitor.visit_name(parameter_ref, path);
+    }
+    if let Some(value) = &scored_property.value {
+        visitor.visit_value(value, path);
+    }
+    for x in &scored_property.scored_properties {
+        visitor.visit_scored_property(x, path);
+    }
+    for x in &scored_property.properties {
+        visitor.visit_property(x, path);
+    }
+    visitor.visit_extra(&scored_property.extra, path);
+    path.truncate(len);
+}
+
+/// Visit the name, value, sub-properties and lossless content of the property.
+pub fn visit_property<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    property: &'a Property,
+    path: &mut Vec<&'a OwnedName>,
+) {
+    visitor.visit_name(&property.name, path);
+    path.push(&property.name);
+    if let Some(value) = &property.value {
+        visitor.visit_value(value, path);
+    }
+    for x in &property.properties {
+        visitor.visit_property(x, path);
+    }
+    visitor.visit_extra(&property.extra, path);
+    path.pop();
+}
+
+/// Visit the name, properties and lossless content of the parameter definition.
+pub fn visit_parameter_def<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    parameter_def: &'a ParameterDef,
+    path: &mut Vec<&'a OwnedName>,
+) {
+    visitor.visit_name(&parameter_def.name, path);
+    path.push(&parameter_def.name);
+    for x in &parameter_def.properties {
+        visitor.visit_property(x, path);
+    }
+    visitor.visit_extra(&parameter_def.extra, path);
+    path.pop();
+}
+
+/// Visit the name, value and lossless content of the parameter initialization.
+pub fn visit_parameter_init<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    parameter_init: &'a ParameterInit,
+    path: &mut Vec<&'a OwnedName>,
+) {
+    visitor.visit_name(&parameter_init.name, path);
+    path.push(&parameter_init.name);
+    visitor.visit_value(&parameter_init.value, path);
+    visitor.visit_extra(&parameter_init.extra, path);
+    path.pop();
+}
+
+/// Visit the names in the value.
+pub fn visit_value<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    value: &'a PropertyValue,
+    path: &mut Vec<&'a OwnedName>,
+) {
+    match value {
+        PropertyValue::QName(x) | PropertyValue::Unknown(x, _) => visitor.visit_name(x, path),
+        _ => {}
+    }
+}
+
+/// Visit the names of the attributes and the elements in the lossless content.
+pub fn visit_extra<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    extra: &'a XmlExtra,
+    path: &mut Vec<&'a OwnedName>,
+) {
+    for x in &extra.attributes {
+        visitor.visit_name(&x.name, path);
+    }
+    let nodes = extra
+        .content
+        .iter()
+        .filter_map(|x| match x {
+            XmlContent::Node(node) => Some(node),
+            XmlContent::Schema(_) => None,
+        })
+        .chain(&extra.prolog);
+    for node in nodes {
+        visit_node(visitor, node, path);
+    }
+}
+
+fn visit_node<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &'a XmlNode,
+    path: &mut Vec<&'a OwnedName>,
+) {
+    if let XmlNode::Element(element) = node {
+        visitor.visit_name(&element.name, path);
+        for x in &element.attributes {
+            visitor.visit_name(&x.name, path);
+        }
+        for x in &element.children {
+            visit_node(visitor, x, path);
+        }
+    }
+}
+
+/// A visitor which may modify the nodes of a Print Schema document in place.
+pub trait VisitMut {
+    /// Visit a document of either type.
+    fn visit_document_mut(&mut self, document: &mut PrintSchemaDocument) {
+        visit_document_mut(self, document);
+    }
+    /// Visit a print capabilities document.
+    fn visit_print_capabilities_mut(&mut self, document: &mut PrintCapabilitiesDocument) {
+        visit_print_capabilities_mut(self, document);
+    }
+    /// Visit a print ticket document.
+    fn visit_print_ticket_mut(&mut self, document: &mut PrintTicketDocument) {
+        visit_print_ticket_mut(self, document);
+    }
+    /// Visit a feature.
+    fn visit_feature_mut(&mut self, feature: &mut PrintFeature, path: &mut Vec<OwnedName>) {
+        visit_feature_mut(self, feature, path);
+    }
+    /// Visit an option.
+    fn visit_option_mut(&mut self, option: &mut PrintFeatureOption, path: &mut Vec<OwnedName>) {
+        visit_option_mut(self, option, path);
+    }
+    /// Visit a scored-property.
+    fn visit_scored_property_mut(
+        &mut self,
+        scored_property: &mut ScoredProperty,
+        path: &mut Vec<OwnedName>,
+    ) {
+        visit_scored_property_mut(self, scored_property, path);
+    }
+    /// Visit a property.
+    fn visit_property_mut(&mut self, property: &mut Property, path: &mut Vec<OwnedName>) {
+        visit_property_mut(self, property, path);
+    }
+    /// Visit a parameter definition.
+    fn visit_parameter_def_mut(
+        &mut self,
+        parameter_def: &mut ParameterDef,
+        path: &mut Vec<OwnedName>,
+    ) {
+        visit_parameter_def_mut(self, parameter_def, path);
+    }
+    /// Visit a parameter initialization.
+    fn visit_parameter_init_mut(
+        &mut self,
+        parameter_init: &mut ParameterInit,
+        path: &mut Vec<OwnedName>,
+    ) {
+        visit_parameter_init_mut(self, parameter_init, path);
+    }
+    /// Visit a value.
+    fn visit_value_mut(&mut self, value: &mut PropertyValue, path: &mut Vec<OwnedName>) {
+        visit_value_mut(self, value, path);
+    }
+    /// Visit the content kept by the lossless mode for a node.
+    fn visit_extra_mut(&mut self, extra: &mut XmlExtra, path: &mut Vec<OwnedName>) {
+        visit_extra_mut(self, extra, path);
+    }
+    /// Visit a name, see [`Visit::visit_name`].
+    ///
+    /// The namespace declarations kept by the lossless mode are also visited as names without a local name,
+    /// so that a changed prefix is declared together with the names using it.
+    ///
+    /// The names of the enclosing nodes in the path are the ones after this method is called for them.
+    fn visit_name_mut(&mut self, _name: &mut OwnedName, _path: &mut Vec<OwnedName>) {}
+}
+
+/// Visit the document of either type.
+pub fn visit_document_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    document: &mut PrintSchemaDocument,
+) {
+    match document {
+        PrintSchemaDocument::PrintCapabilities(x) => visitor.visit_print_capabilities_mut(x),
+        PrintSchemaDocument::PrintTicket(x) => visitor.visit_print_ticket_mut(x),
+    }
+}
+
+/// Visit the properties, parameter definitions, features and lossless content of the document.
+pub fn visit_print_capabilities_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    document: &mut PrintCapabilitiesDocument,
+) {
+    let path = &mut vec![];
+    for x in &mut document.properties {
+        visitor.visit_property_mut(x, path);
+    }
+    for x in &mut document.parameter_defs {
+        visitor.visit_parameter_def_mut(x, path);
+    }
+    for x in &mut document.features {
+        visitor.visit_feature_mut(x, path);
+    }
+    visitor.visit_extra_mut(&mut document.extra, path);
+}
+
+/// Visit the properties, parameter initializations, features and lossless content of the document.
+pub fn visit_print_ticket_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    document: &mut PrintTicketDocument,
+) {
+    let path = &mut vec![];
+    for x in &mut document.properties {
+        visitor.visit_property_mut(x, path);
+    }
+    for x in &mut document.parameter_inits {
+        visitor.visit_parameter_init_mut(x, path);
+    }
+    for x in &mut document.features {
+        visitor.visit_feature_mut(x, path);
+    }
+    visitor.visit_extra_mut(&mut document.extra, path);
+}
+
+/// Visit the name, properties, options, sub-features and lossless content of the feature.
+pub fn visit_feature_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    feature: &mut PrintFeature,
+    path: &mut Vec<OwnedName>,
+) {
+    visitor.visit_name_mut(&mut feature.name, path);
+    path.push(feature.name.clone());
+    for x in &mut feature.properties {
+        visitor.visit_property_mut(x, path);
+    }
+    for x in &mut feature.options {
+        visitor.visit_option_mut(x, path);
+    }
+    for x in &mut feature.features {
+        visitor.visit_feature_mut(x, path);
+    }
+    visitor.visit_extra_mut(&mut feature.extra, path);
+    path.pop();
+}
+
+/// Visit the name, scored-properties, properties and lossless content of the option.
+pub fn visit_option_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    option: &mut PrintFeatureOption,
+    path: &mut Vec<OwnedName>,
+) {
+    if let Some(name) = &mut option.name {
+        visitor.visit_name_mut(name, path);
+    }
+    let len = path.len();
+    path.extend(option.name.clone());
+    for x in &mut option.scored_properties {
+        visitor.visit_scored_property_mut(x, path);
+    }
+    for x in &mut option.properties {
+        visitor.visit_property_mut(x, path);
+    }
+    visitor.visit_extra_mut(&mut option.extra, path);
+    path.truncate(len);
+}
+
+/// Visit the name, parameter reference, value, scored-properties, properties and lossless content of the scored-property.
+pub fn visit_scored_property_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    scored_property: &mut ScoredProperty,
+    path: &mut Vec<OwnedName>,
+) {
+    if let Some(name) = &mut scored_property.name {
+        visitor.visit_name_mut(name, path);
+    }
+    let len = path.len();
+    path.extend(scored_property.name.clone());
+    if let Some(parameter_ref) = &mut scored_property.parameter_ref {
+        visitor.visit_name_mut(parameter_ref, path);
+    }
+    if let Some(value) = &mut scored_property.value {
+        visitor.visit_value_mut(value, path);
+    }
+    for x in &mut scored_property.scored_properties {
+        visitor.visit_scored_property_mut(x, path);
+    }
+    for x in &mut scored_property.properties {
+        visitor.visit_property_mut(x, path);
+    }
+    visitor.visit_extra_mut(&mut scored_property.extra, path);
+    path.truncate(len);
+}
+
+/// Visit the name, value, sub-properties and lossless content of the property.
+pub fn visit_property_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    property: &mut Property,
+    path: &mut Vec<OwnedName>,
+) {
+    visitor.visit_name_mut(&mut property.name, path);
+    path.push(property.name.clone());
+    if let Some(value) = &mut property.value {
+        visitor.visit_value_mut(value, path);
+    }
+    for x in &mut property.properties {
+        visitor.visit_property_mut(x, path);
+    }
+    visitor.visit_extra_mut(&mut property.extra, path);
+    path.pop();
+}
+
+/// Visit the name, properties and lossless content of the parameter definition.
+pub fn visit_parameter_def_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    parameter_def: &mut ParameterDef,
+    path: &mut Vec<OwnedName>,
+) {
+    visitor.visit_name_mut(&mut parameter_def.name, path);
+    path.push(parameter_def.name.clone());
+    for x in &mut parameter_def.properties {
+        visitor.visit_property_mut(x, path);
+    }
+    visitor.visit_extra_mut(&mut parameter_def.extra, path);
+    path.pop();
+}
+
+/// Visit the name, value and lossless content of the parameter initialization.
+pub fn visit_parameter_init_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    parameter_init: &mut ParameterInit,
+    path: &mut Vec<OwnedName>,
+) {
+    visitor.visit_name_mut(&mut parameter_init.name, path);
+    path.push(parameter_init.name.clone());
+    visitor.visit_value_mut(&mut parameter_init.value, path);
+    visitor.visit_extra_mut(&mut parameter_init.extra, path);
+    path.pop();
+}
+
+/// Visit the names in the value.
+pub fn visit_value_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    value: &mut PropertyValue,
+    path: &mut Vec<OwnedName>,
+) {
+    match value {
+        PropertyValue::QName(x) | PropertyValue::Unknown(x, _) => visitor.visit_name_mut(x, path),
+        _ => {}
+    }
+}
+
+/// Visit the namespace declarations, the names of the attributes and the elements in the lossless content.
+pub fn visit_extra_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    extra: &mut XmlExtra,
+    path: &mut Vec<OwnedName>,
+) {
+    visit_declarations_mut(visitor, &mut extra.namespaces, path);
+    for x in &mut extra.attributes {
+        visitor.visit_name_mut(&mut x.name, path);
+    }
+    let nodes = extra
+        .content
+        .iter_mut()
+        .filter_map(|x| match x {
+            XmlContent::Node(node) => Some(node),
+            XmlContent::Schema(_) => None,
+        })
+        .chain(&mut extra.prolog);
+    for node in nodes {
+        visit_node_mut(visitor, node, path);
+    }
+}
+
+fn visit_node_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    node: &mut XmlNode,
+    path: &mut Vec<OwnedName>,
+) {
+    if let XmlNode::Element(element) = node {
+        visitor.visit_name_mut(&mut element.name, path);
+        visit_declarations_mut(visitor, &mut element.namespaces, path);
+        for x in &mut element.attributes {
+            visitor.visit_name_mut(&mut x.name, path);
+        }
+        for x in &mut element.children {
+            visit_node_mut(visitor, x, path);
+        }
+    }
+}
+
+/// Visit the namespace declarations as names, so that their prefixes are changed together with the names.
+fn visit_declarations_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    namespaces: &mut [(String, String)],
+    path: &mut Vec<OwnedName>,
+) {
+    for (prefix, uri) in namespaces.iter_mut().filter(|(_, uri)| !uri.is_empty()) {
+        let mut name = OwnedName {
+            local_name: String::new(),
+            namespace: Some(uri.clone()),
+            prefix: Some(prefix.clone()),
+        };
+        visitor.visit_name_mut(&mut name, path);
+        *prefix = name.prefix.unwrap_or_default();
+    }
+}
+
+/// Documents whose nodes can be visited by a [`VisitMut`] as a whole.
+pub(crate) trait VisitableMut {
+    fn accept_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V);
+}
+
+impl VisitableMut for PrintCapabilitiesDocument {
+    fn accept_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+        visitor.visit_print_capabilities_mut(self);
+    }
+}
+
+impl VisitableMut for PrintTicketDocument {
+    fn accept_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+        visitor.visit_print_ticket_mut(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{visit_feature_mut, Visit, VisitMut};
+    use crate::ticket::document::{
+        reader::{ParsableXmlDocument, ParseOptions},
+        PrintFeature, PrintFeatureOption, PrintTicketDocument, XmlExtra, NS_PSK,
+    };
+    use crate::ticket::test_fixtures::sample_capabilities_document;
+    use std::collections::BTreeSet;
+    use xml::name::OwnedName;
+
+    #[test]
+    fn collect_names() {
+        #[derive(Default)]
+        struct Namespaces<'a>(BTreeSet<&'a str>, usize);
+        impl<'a> Visit<'a> for Namespaces<'a> {
+            fn visit_name(&mut self, name: &'a OwnedName, _path: &mut Vec<&'a OwnedName>) {
+                self.0.extend(name.namespace_ref());
+                self.1 += 1;
+            }
+        }
+
+        let capabilities = sample_capabilities_document();
+        let mut namespaces = Namespaces::default();
+        namespaces.visit_print_capabilities(&capabilities);
+        assert!(namespaces.0.contains(NS_PSK));
+        assert!(namespaces.0.len() >= 3);
+        assert!(namespaces.1 > capabilities.features.len());
+    }
+
+    #[test]
+    fn paths_of_options() {
+        struct Paths(Vec<Vec<String>>);
+        impl<'a> Visit<'a> for Paths {
+            fn visit_option(
+                &mut self,
+                option: &'a PrintFeatureOption,
+                path: &mut Vec<&'a OwnedName>,
+            ) {
+                let mut names = path
+                    .iter()
+                    .map(|x| x.local_name.clone())
+                    .collect::<Vec<_>>();
+                names.extend(option.name.as_ref().map(|x| x.local_name.clone()));
+                self.0.push(names);
+            }
+        }
+
+        let mut paths = Paths(vec![]);
+        paths.visit_print_capabilities(&sample_capabilities_document());
+        assert!(paths
+            .0
+            .contains(&vec!["PageMediaSize".to_string(), "ISOA4".to_string()]));
+    }
+
+    #[test]
+    fn strip_vendor_features_and_rename_options() {
+        struct Transform;
+        impl VisitMut for Transform {
+            fn visit_feature_mut(&mut self, feature: &mut PrintFeature, path: &mut Vec<OwnedName>) {
+                feature
+                    .features
+                    .retain(|x| x.name.namespace_ref() == Some(NS_PSK));
+                visit_feature_mut(self, feature, path);
+            }
+
+            fn visit_name_mut(&mut self, name: &mut OwnedName, path: &mut Vec<OwnedName>) {
+                if name.local_name == "ISOA4" && path.len() == 1 {
+                    name.local_name = "RenamedA4".to_string();
+                }
+            }
+        }
+
+        let mut capabilities = sample_capabilities_document();
+        capabilities
+            .features
+            .retain(|x| x.name.namespace_ref() == Some(NS_PSK));
+        Transform.visit_print_capabilities_mut(&mut capabilities);
+        let media_size = capabilities
+            .features
+            .iter()
+            .find(|x| x.name.local_name == "PageMediaSize")
+            .unwrap();
+        assert!(media_size
+            .options
+            .iter()
+            .any(|x| x.name.as_ref().unwrap().local_name == "RenamedA4"));
+        assert!(!media_size
+            .options
+            .iter()
+            .any(|x| x.name.as_ref().unwrap().local_name == "ISOA4"));
+    }
+
+    #[test]
+    fn visit_lossless_content() {
+        struct Extras(Vec<String>, usize);
+        impl VisitMut for Extras {
+            fn visit_extra_mut(&mut self, extra: &mut XmlExtra, path: &mut Vec<OwnedName>) {
+                if !extra.content.is_empty() {
+                    self.0.extend(path.iter().map(|x| x.local_name.clone()));
+                }
+                super::visit_extra_mut(self, extra, path);
+            }
+
+            fn visit_name_mut(&mut self, name: &mut OwnedName, _path: &mut Vec<OwnedName>) {
+                if name.local_name == "Private" {
+                    self.1 += 1;
+                }
+            }
+        }
+
+        let xml = br#"<psf:PrintTicket version="1"
+            xmlns:psf="http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework"
+            xmlns:psk="http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords"
+            xmlns:oem="http://example.com/oem">
+            <psf:Feature name="psk:PageOrientation">
+                <oem:Private oem:value="1" />
+                <psf:Option name="psk:Landscape" />
+            </psf:Feature>
+        </psf:PrintTicket>"#;
+        let mut ticket = PrintTicketDocument::parse_from_bytes_with_options(
+            xml,
+            &ParseOptions::new().lossless(true),
+        )
+        .unwrap();
+        let mut extras = Extras(vec![], 0);
+        extras.visit_print_ticket_mut(&mut ticket);
+        assert!(extras.0.contains(&"PageOrientation".to_string()));
+        assert_eq!(extras.1, 1);
+    }
+}
@@ -1,14 +1,20 @@
-use super::{NS_PSF, NS_XSI};
-use std::{borrow::Cow, io::Write};
+use super::{
+    canonical::Canonicalize,
+    device_capabilities::PARAMETER_ATTRIBUTES,
+    visit::{VisitMut, VisitableMut},
+    xsd::encode_base64,
+    SchemaElement, XmlContent, XmlExtra, XmlNode, NS_PSF, NS_PSF2, NS_XSI,
+};
+use std::{borrow::Cow, collections::HashMap, io::Write};
 use xml::{
-    attribute::Attribute, name::OwnedName, namespace::Namespace, writer::XmlEvent, EmitterConfig,
-    EventWriter,
+    attribute::Attribute,
+    common::XmlVersion,
+    name::{Name, OwnedName},
+    namespace::Namespace,
+    writer::XmlEvent,
+    EmitterConfig, EventWriter,
 };
 
-// mark document root
-impl XmlDocumentRoot for super::PrintCapabilitiesDocument {}
-impl XmlDocumentRoot for super::PrintTicketDocument {}
-
 /// A trait for serializing as XML element.
 pub trait XmlSerializer {
     /// Collect the namespace URIs and prefixes used in the XML element.
@@ -17,17 +23,213 @@ pub trait XmlSerializer {
     fn write_to<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), xml::writer::Error>;
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+/// Represents the encoding of the serialized XML.
+pub enum XmlEncoding {
+    /// UTF-8, without byte order mark.
+    #[default]
+    Utf8,
+    /// UTF-16 little endian, with byte order mark.
+    Utf16Le,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+/// Represents how the namespace prefixes are chosen when serializing.
+pub enum PrefixMode {
+    /// Use the prefixes of the names as they are.
+    #[default]
+    Preserve,
+    /// Use the standard prefixes, see [`Canonicalize::assign_standard_prefixes`].
+    Standard,
+}
+
+#[derive(Clone, Debug)]
+/// Represents options for serializing print schema.
+pub struct SerializeOptions {
+    indent: Option<String>,
+    declaration: bool,
+    encoding: XmlEncoding,
+    prefix_mode: PrefixMode,
+}
+
+impl Default for SerializeOptions {
+    fn default() -> Self {
+        Self {
+            indent: None,
+            declaration: true,
+            encoding: XmlEncoding::Utf8,
+            prefix_mode: PrefixMode::Preserve,
+        }
+    }
+}
+
+impl SerializeOptions {
+    /// Create the default options, which write unindented UTF-8 with an XML declaration and the original prefixes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Indent the elements with two spaces.
+    ///
+    /// The whitespace kept by the lossless mode is replaced by the indentation.
+    pub fn indent(mut self, value: bool) -> Self {
+        self.indent = value.then(|| "  ".to_string());
+        self
+    }
+
+    /// Indent the elements with the given string.
+    pub fn indent_string(mut self, value: impl Into<String>) -> Self {
+        self.indent = Some(value.into());
+        self
+    }
+
+    /// Write the XML declaration.
+    pub fn declaration(mut self, value: bool) -> Self {
+        self.declaration = value;
+        self
+    }
+
+    /// Set the encoding of the output.
+    pub fn encoding(mut self, value: XmlEncoding) -> Self {
+        self.encoding = value;
+        self
+    }
+
+    /// Set how the namespace prefixes are chosen.
+    pub fn prefix_mode(mut self, value: PrefixMode) -> Self {
+        self.prefix_mode = value;
+        self
+    }
+}
+
 /// A trait for serializing as XML document.
 pub trait XmlDocumentRoot: XmlSerializer {
+    /// Convert the struct to XML with the given options.
+    fn try_to_xml_with_options(
+        &self,
+        options: &SerializeOptions,
+    ) -> Result<Vec<u8>, xml::writer::Error>;
+
+    /// Convert the struct to XML.
+    fn try_to_xml(&self) -> Result<Vec<u8>, xml::writer::Error> {
+        self.try_to_xml_with_options(&SerializeOptions::default())
+    }
+
     /// Convert the struct to XML.
+    ///
+    /// # Panics
+    /// Panics if the document cannot be written, such as when it contains an invalid name.
+    /// Use [`XmlDocumentRoot::try_to_xml`] to handle the error.
     fn to_xml(&self) -> Vec<u8> {
-        let mut writer = EmitterConfig::new()
-            .perform_indent(false)
-            .create_writer(Vec::new());
-        let mut ns = Namespace::empty();
-        self.collect_namespace(&mut ns);
-        self.write_to(&mut writer).unwrap();
-        writer.into_inner()
+        self.try_to_xml().expect("failed to write print schema")
+    }
+}
+
+impl XmlDocumentRoot for super::PrintCapabilitiesDocument {
+    fn try_to_xml_with_options(
+        &self,
+        options: &SerializeOptions,
+    ) -> Result<Vec<u8>, xml::writer::Error> {
+        serialize(self, options)
+    }
+}
+
+impl XmlDocumentRoot for super::PrintTicketDocument {
+    fn try_to_xml_with_options(
+        &self,
+        options: &SerializeOptions,
+    ) -> Result<Vec<u8>, xml::writer::Error> {
+        serialize(self, options)
+    }
+}
+
+impl XmlDocumentRoot for super::PrintDeviceCapabilitiesDocument {
+    /// Convert the struct to XML with the given options.
+    ///
+    /// The elements are named by their keywords, so [`PrefixMode::Standard`] does not change the prefixes.
+    fn try_to_xml_with_options(
+        &self,
+        options: &SerializeOptions,
+    ) -> Result<Vec<u8>, xml::writer::Error> {
+        write_document(self, options)
+    }
+}
+
+/// Drops the whitespace kept by the lossless mode, which would be doubled by the indentation.
+struct RemoveWhitespace;
+
+impl VisitMut for RemoveWhitespace {
+    fn visit_extra_mut(&mut self, extra: &mut XmlExtra, _path: &mut Vec<OwnedName>) {
+        extra
+            .content
+            .retain(|x| !matches!(x, XmlContent::Node(XmlNode::Whitespace(_))));
+        extra.content.iter_mut().for_each(|x| {
+            if let XmlContent::Node(node) = x {
+                remove_whitespace(node);
+            }
+        });
+    }
+}
+
+fn serialize<T>(document: &T, options: &SerializeOptions) -> Result<Vec<u8>, xml::writer::Error>
+where
+    T: XmlSerializer + Canonicalize + VisitableMut,
+{
+    let mut document = Cow::Borrowed(document);
+    if options.prefix_mode == PrefixMode::Standard {
+        document.to_mut().assign_standard_prefixes();
+    }
+    if options.indent.is_some() {
+        document.to_mut().accept_mut(&mut RemoveWhitespace);
+    }
+    write_document(document.as_ref(), options)
+}
+
+/// Write the document with the output options, which are the indentation, the declaration and the encoding.
+pub(super) fn write_document<T: XmlSerializer>(
+    document: &T,
+    options: &SerializeOptions,
+) -> Result<Vec<u8>, xml::writer::Error> {
+    let mut config = EmitterConfig::new()
+        .perform_indent(options.indent.is_some())
+        .write_document_declaration(false)
+        .autopad_comments(false);
+    if let Some(indent) = &options.indent {
+        config = config.indent_string(indent.clone());
+    }
+    let mut writer = config.create_writer(Vec::new());
+    if options.declaration {
+        writer.write(XmlEvent::StartDocument {
+            version: XmlVersion::Version10,
+            encoding: Some(match options.encoding {
+                XmlEncoding::Utf8 => "UTF-8",
+                XmlEncoding::Utf16Le => "UTF-16",
+            }),
+            standalone: None,
+        })?;
+    }
+    document.write_to(&mut writer)?;
+    let xml = writer.into_inner();
+    Ok(match options.encoding {
+        XmlEncoding::Utf8 => xml,
+        XmlEncoding::Utf16Le => {
+            // the writer only produces UTF-8 from valid strings
+            let xml = String::from_utf8(xml).expect("the writer produces UTF-8");
+            let mut result = Vec::with_capacity(xml.len() * 2 + 2);
+            result.extend_from_slice(&[0xFF, 0xFE]);
+            xml.encode_utf16()
+                .for_each(|x| result.extend_from_slice(&x.to_le_bytes()));
+            result
+        }
+    })
+}
+
+fn remove_whitespace(node: &mut XmlNode) {
+    if let XmlNode::Element(element) = node {
+        element
+            .children
+            .retain(|x| !matches!(x, XmlNode::Whitespace(_)));
+        element.children.iter_mut().for_each(remove_whitespace);
     }
 }
 
@@ -42,23 +244,33 @@ impl XmlSerializer for super::PrintCapabilitiesDocument {
     }
 
     fn write_to<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), xml::writer::Error> {
+        write_prolog(writer, &self.extra)?;
         {
-            let mut ns = Namespace::empty();
+            let mut ns = declared_namespace(&self.extra);
             self.collect_namespace(&mut ns);
-            let event = XmlEvent::StartElement {
-                name: "psf:PrintCapabilities".into(),
-                attributes: Cow::Owned(vec![Attribute::new("version".into(), "1")]),
-                namespace: Cow::Owned(ns),
-            };
-            writer.write(event)?;
+            start_element(
+                writer,
+                "psf:PrintCapabilities",
+                &[("version", Some("1"))],
+                ns,
+                &self.extra,
+            )?;
         }
-        self.properties
-            .iter()
-            .try_for_each(|x| x.write_to(writer))?;
-        self.parameter_defs
-            .iter()
-            .try_for_each(|x| x.write_to(writer))?;
-        self.features.iter().try_for_each(|x| x.write_to(writer))?;
+        write_content(
+            writer,
+            &self.extra,
+            &[
+                SchemaElement::Property,
+                SchemaElement::ParameterDef,
+                SchemaElement::Feature,
+            ],
+            |writer, kind, index| match kind {
+                SchemaElement::Property => write_nth(writer, &self.properties, index),
+                SchemaElement::ParameterDef => write_nth(writer, &self.parameter_defs, index),
+                SchemaElement::Feature => write_nth(writer, &self.features, index),
+                _ => Ok(false),
+            },
+        )?;
         writer.write(XmlEvent::end_element())?;
         Ok(())
     }
@@ -75,23 +287,33 @@ impl XmlSerializer for super::PrintTicketDocument {
     }
 
     fn write_to<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), xml::writer::Error> {
+        write_prolog(writer, &self.extra)?;
         {
-            let mut ns = Namespace::empty();
+            let mut ns = declared_namespace(&self.extra);
             self.collect_namespace(&mut ns);
-            let event = XmlEvent::StartElement {
-                name: "psf:PrintTicket".into(),
-                attributes: Cow::Owned(vec![Attribute::new("version".into(), "1")]),
-                namespace: Cow::Owned(ns),
-            };
-            writer.write(event)?;
+            start_element(
+                writer,
+                "psf:PrintTicket",
+                &[("version", Some("1"))],
+                ns,
+                &self.extra,
+            )?;
         }
-        self.properties
-            .iter()
-            .try_for_each(|x| x.write_to(writer))?;
-        self.parameter_inits
-            .iter()
-            .try_for_each(|x| x.write_to(writer))?;
-        self.features.iter().try_for_each(|x| x.write_to(writer))?;
+        write_content(
+            writer,
+            &self.extra,
+            &[
+                SchemaElement::Property,
+                SchemaElement::ParameterInit,
+                SchemaElement::Feature,
+            ],
+            |writer, kind, index| match kind {
+                SchemaElement::Property => write_nth(writer, &self.properties, index),
+                SchemaElement::ParameterInit => write_nth(writer, &self.parameter_inits, index),
+                SchemaElement::Feature => write_nth(writer, &self.features, index),
+                _ => Ok(false),
+            },
+        )?;
         writer.write(XmlEvent::end_element())?;
         Ok(())
     }
@@ -107,14 +329,28 @@ impl XmlSerializer for super::PrintFeature {
     }
 
     fn write_to<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), xml::writer::Error> {
-        writer.write(
-            XmlEvent::start_element("psf:Feature").attr("name", format_name(&self.name).as_ref()),
+        start_element(
+            writer,
+            "psf:Feature",
+            &[("name", Some(&format_name(&self.name)?))],
+            declared_namespace(&self.extra),
+            &self.extra,
+        )?;
+        write_content(
+            writer,
+            &self.extra,
+            &[
+                SchemaElement::Option,
+                SchemaElement::Property,
+                SchemaElement::Feature,
+            ],
+            |writer, kind, index| match kind {
+                SchemaElement::Option => write_nth(writer, &self.options, index),
+                SchemaElement::Property => write_nth(writer, &self.properties, index),
+                SchemaElement::Feature => write_nth(writer, &self.features, index),
+                _ => Ok(false),
+            },
         )?;
-        self.options.iter().try_for_each(|x| x.write_to(writer))?;
-        self.properties
-            .iter()
-            .try_for_each(|x| x.write_to(writer))?;
-        self.features.iter().try_for_each(|x| x.write_to(writer))?;
         writer.write(XmlEvent::end_element())?;
         Ok(())
     }
@@ -128,11 +364,22 @@ impl XmlSerializer for super::ParameterInit {
     }
 
     fn write_to<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), xml::writer::Error> {
-        writer.write(
-            XmlEvent::start_element("psf:ParameterInit")
-                .attr("name", format_name(&self.name).as_ref()),
+        start_element(
+            writer,
+            "psf:ParameterInit",
+            &[("name", Some(&format_name(&self.name)?))],
+            declared_namespace(&self.extra),
+            &self.extra,
+        )?;
+        write_content(
+            writer,
+            &self.extra,
+            &[SchemaElement::Value],
+            |writer, kind, index| match kind {
+                SchemaElement::Value => write_nth(writer, std::slice::from_ref(&self.value), index),
+                _ => Ok(false),
+            },
         )?;
-        self.value.write_to(writer)?;
         writer.write(XmlEvent::end_element())?;
         Ok(())
     }
@@ -148,13 +395,22 @@ impl XmlSerializer for super::ParameterDef {
     }
 
     fn write_to<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), xml::writer::Error> {
-        writer.write(
-            XmlEvent::start_element("psf:ParameterDef")
-                .attr("name", format_name(&self.name).as_ref()),
+        start_element(
+            writer,
+            "psf:ParameterDef",
+            &[("name", Some(&format_name(&self.name)?))],
+            declared_namespace(&self.extra),
+            &self.extra,
+        )?;
+        write_content(
+            writer,
+            &self.extra,
+            &[SchemaElement::Property],
+            |writer, kind, index| match kind {
+                SchemaElement::Property => write_nth(writer, &self.properties, index),
+                _ => Ok(false),
+            },
         )?;
-        for x in &self.properties {
-            x.write_to(writer)?;
-        }
         writer.write(XmlEvent::end_element())?;
         Ok(())
     }
@@ -173,13 +429,24 @@ impl XmlSerializer for super::PrintFeatureOption {
     }
 
     fn write_to<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), xml::writer::Error> {
-        start_element_with_optional_name(writer, "psf:Option", self.name.as_ref())?;
-        self.scored_properties
-            .iter()
-            .try_for_each(|x| x.write_to(writer))?;
-        self.properties
-            .iter()
-            .try_for_each(|x| x.write_to(writer))?;
+        let name = self.name.as_ref().map(format_name).transpose()?;
+        start_element(
+            writer,
+            "psf:Option",
+            &[("name", name.as_deref())],
+            declared_namespace(&self.extra),
+            &self.extra,
+        )?;
+        write_content(
+            writer,
+            &self.extra,
+            &[SchemaElement::ScoredProperty, SchemaElement::Property],
+            |writer, kind, index| match kind {
+                SchemaElement::ScoredProperty => write_nth(writer, &self.scored_properties, index),
+                SchemaElement::Property => write_nth(writer, &self.properties, index),
+                _ => Ok(false),
+            },
+        )?;
         writer.write(XmlEvent::end_element())?;
         Ok(())
     }
@@ -204,23 +471,41 @@ impl XmlSerializer for super::ScoredProperty {
     }
 
     fn write_to<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), xml::writer::Error> {
-        start_element_with_optional_name(writer, "psf:ScoredProperty", self.name.as_ref())?;
-        if let Some(parameter_ref) = &self.parameter_ref {
-            writer.write(
-                XmlEvent::start_element("psf:ParameterRef")
-                    .attr("name", format_name(parameter_ref).as_ref()),
-            )?;
-            writer.write(XmlEvent::end_element())?;
-        }
-        if let Some(value) = &self.value {
-            value.write_to(writer)?;
-        }
-        self.scored_properties
-            .iter()
-            .try_for_each(|x| x.write_to(writer))?;
-        self.properties
-            .iter()
-            .try_for_each(|x| x.write_to(writer))?;
+        let name = self.name.as_ref().map(format_name).transpose()?;
+        start_element(
+            writer,
+            "psf:ScoredProperty",
+            &[("name", name.as_deref())],
+            declared_namespace(&self.extra),
+            &self.extra,
+        )?;
+        write_content(
+            writer,
+            &self.extra,
+            &[
+                SchemaElement::ParameterRef,
+                SchemaElement::Value,
+                SchemaElement::ScoredProperty,
+                SchemaElement::Property,
+            ],
+            |writer, kind, index| match kind {
+                SchemaElement::ParameterRef => match (&self.parameter_ref, index) {
+                    (Some(parameter_ref), 0) => {
+                        writer.write(
+                            XmlEvent::start_element("psf:ParameterRef")
+                                .attr("name", format_name(parameter_ref)?.as_ref()),
+                        )?;
+                        writer.write(XmlEvent::end_element())?;
+                        Ok(true)
+                    }
+                    _ => Ok(false),
+                },
+                SchemaElement::Value => write_nth(writer, self.value.as_slice(), index),
+                SchemaElement::ScoredProperty => write_nth(writer, &self.scored_properties, index),
+                SchemaElement::Property => write_nth(writer, &self.properties, index),
+                _ => Ok(false),
+            },
+        )?;
         writer.write(XmlEvent::end_element())?;
         Ok(())
     }
@@ -237,15 +522,23 @@ impl XmlSerializer for super::Property {
     }
 
     fn write_to<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), xml::writer::Error> {
-        writer.write(
-            XmlEvent::start_element("psf:Property").attr("name", format_name(&self.name).as_ref()),
+        start_element(
+            writer,
+            "psf:Property",
+            &[("name", Some(&format_name(&self.name)?))],
+            declared_namespace(&self.extra),
+            &self.extra,
+        )?;
+        write_content(
+            writer,
+            &self.extra,
+            &[SchemaElement::Value, SchemaElement::Property],
+            |writer, kind, index| match kind {
+                SchemaElement::Value => write_nth(writer, self.value.as_slice(), index),
+                SchemaElement::Property => write_nth(writer, &self.properties, index),
+                _ => Ok(false),
+            },
         )?;
-        if let Some(value) = &self.value {
-            value.write_to(writer)?;
-        }
-        self.properties
-            .iter()
-            .try_for_each(|x| x.write_to(writer))?;
         writer.write(XmlEvent::end_element())?;
         Ok(())
     }
@@ -256,40 +549,57 @@ impl XmlSerializer for super::PropertyValue {
         ns.put("psf", NS_PSF);
         ns.put("xsi", NS_XSI);
         collect_namespace_from_name(&self.xsi_type(), ns);
+        if let super::PropertyValue::QName(name) = self {
+            collect_namespace_from_name(name, ns);
+        }
     }
 
     fn write_to<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), xml::writer::Error> {
         let xsi_type = self.xsi_type();
         writer.write(
-            XmlEvent::start_element("psf:Value").attr("xsi:type", format_name(&xsi_type).as_ref()),
+            XmlEvent::start_element("psf:Value").attr("xsi:type", format_name(&xsi_type)?.as_ref()),
         )?;
-        match self {
-            super::PropertyValue::String(s) => {
-                writer.write(XmlEvent::characters(s))?;
-            }
-            super::PropertyValue::Integer(i) => {
-                writer.write(XmlEvent::characters(&i.to_string()))?;
-            }
-            super::PropertyValue::QName(q) => {
-                writer.write(XmlEvent::characters(&q.to_string()))?;
-            }
-            super::PropertyValue::Unknown(_, s) => {
-                writer.write(XmlEvent::characters(s))?;
-            }
-        }
+        writer.write(XmlEvent::characters(&value_text(self)?))?;
         writer.write(XmlEvent::end_element())?;
         Ok(())
     }
 }
 
-fn collect_namespace_from_name(name: &OwnedName, ns: &mut Namespace) {
+/// Format the value as the text content of its element.
+pub(super) fn value_text(value: &super::PropertyValue) -> Result<Cow<'_, str>, xml::writer::Error> {
+    Ok(match value {
+        super::PropertyValue::String(s) => Cow::Borrowed(s),
+        super::PropertyValue::Integer(i) => Cow::Owned(i.to_string()),
+        super::PropertyValue::BigInteger(i) | super::PropertyValue::Decimal(i) => Cow::Borrowed(i),
+        super::PropertyValue::Boolean(b) => Cow::Borrowed(if *b { "true" } else { "false" }),
+        super::PropertyValue::Base64Binary(b) => Cow::Owned(encode_base64(b)),
+        super::PropertyValue::DateTime(d) => Cow::Owned(d.to_string()),
+        super::PropertyValue::QName(q) => format_name(q)?,
+        super::PropertyValue::Unknown(_, s) => Cow::Borrowed(s),
+    })
+}
+
+pub(super) fn collect_namespace_from_name(name: &OwnedName, ns: &mut Namespace) {
     if let (Some(prefix), Some(namespace)) = (name.prefix_ref(), name.namespace_ref()) {
         ns.put(prefix, namespace);
     }
 }
 
 /// Format the name as a string, with the prefix if present. But no namespace URI.
-fn format_name(name: &OwnedName) -> Cow<'_, String> {
+///
+/// Return an error if the name is not a valid XML name, as the writer does not check it.
+pub(super) fn format_name(name: &OwnedName) -> Result<Cow<'_, str>, xml::writer::Error> {
+    let valid = is_ncname(&name.local_name) && name.prefix_ref().is_none_or(is_ncname);
+    if !valid {
+        return Err(xml::writer::Error::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Invalid XML name: {:?}", format_name_unchecked(name)),
+        )));
+    }
+    Ok(format_name_unchecked(name))
+}
+
+fn format_name_unchecked(name: &OwnedName) -> Cow<'_, str> {
     if let Some(prefix) = name.prefix_ref() {
         Cow::Owned(format!("{}:{}", prefix, name.local_name))
     } else {
@@ -297,26 +607,398 @@ fn format_name(name: &OwnedName) -> Cow<'_, String> {
     }
 }
 
-fn start_element_with_optional_name<W: Write>(
+/// Check whether the string is a name without colon, loosely following the XML specification.
+pub(super) fn is_ncname(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars.next().is_some_and(|x| x == '_' || x.is_alphabetic())
+        && chars.all(|x| x.is_alphanumeric() || matches!(x, '_' | '-' | '.') || !x.is_ascii())
+}
+
+impl XmlSerializer for super::PrintDeviceCapabilitiesDocument {
+    fn collect_namespace(&self, ns: &mut Namespace) {
+        ns.put("psf2", NS_PSF2);
+        self.properties.iter().for_each(|x| x.collect_namespace(ns));
+        self.parameter_defs
+            .iter()
+            .for_each(|x| x.collect_namespace(ns));
+        self.features.iter().for_each(|x| x.collect_namespace(ns));
+    }
+
+    fn write_to<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), xml::writer::Error> {
+        let mut ns = Namespace::empty();
+        self.collect_namespace(&mut ns);
+        writer.write(XmlEvent::StartElement {
+            name: Name::prefixed("PrintDeviceCapabilities", "psf2"),
+            attributes: Cow::Owned(vec![Attribute::new(Name::local("version"), "2")]),
+            namespace: Cow::Owned(ns),
+        })?;
+        for property in &self.properties {
+            write_device_property(writer, property)?;
+        }
+        for parameter_def in &self.parameter_defs {
+            write_device_parameter_def(writer, parameter_def)?;
+        }
+        for feature in &self.features {
+            feature.write_to(writer)?;
+        }
+        writer.write(XmlEvent::end_element())?;
+        Ok(())
+    }
+}
+
+impl XmlSerializer for super::DeviceFeature {
+    fn collect_namespace(&self, ns: &mut Namespace) {
+        ns.put("psf2", NS_PSF2);
+        collect_namespace_from_name(&self.name, ns);
+        self.properties.iter().for_each(|x| x.collect_namespace(ns));
+        self.options.iter().for_each(|x| x.collect_namespace(ns));
+        self.features.iter().for_each(|x| x.collect_namespace(ns));
+    }
+
+    fn write_to<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), xml::writer::Error> {
+        start_device_element(writer, &self.name, "Feature", &[])?;
+        for property in &self.properties {
+            write_device_property(writer, property)?;
+        }
+        for option in &self.options {
+            option.write_to(writer)?;
+        }
+        for feature in &self.features {
+            feature.write_to(writer)?;
+        }
+        writer.write(XmlEvent::end_element())?;
+        Ok(())
+    }
+}
+
+impl XmlSerializer for super::DeviceOption {
+    fn collect_namespace(&self, ns: &mut Namespace) {
+        ns.put("psf2", NS_PSF2);
+        collect_namespace_from_name(&self.name, ns);
+        self.scored_properties
+            .iter()
+            .for_each(|x| x.collect_namespace(ns));
+        self.properties.iter().for_each(|x| x.collect_namespace(ns));
+    }
+
+    fn write_to<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), xml::writer::Error> {
+        let default = self
+            .default
+            .then_some((Name::prefixed("default", "psf2"), Cow::Borrowed("true")));
+        start_device_element(writer, &self.name, "Option", default.as_slice())?;
+        for scored_property in &self.scored_properties {
+            write_device_scored_property(writer, scored_property)?;
+        }
+        for property in &self.properties {
+            write_device_property(writer, property)?;
+        }
+        writer.write(XmlEvent::end_element())?;
+        Ok(())
+    }
+}
+
+/// Start an element of a PrintDeviceCapabilities document, which is named by its keyword
+/// and typed by the `psf2:psftype` attribute.
+fn start_device_element<W: Write>(
+    writer: &mut EventWriter<W>,
+    name: &OwnedName,
+    psftype: &str,
+    attributes: &[(Name<'_>, Cow<'_, str>)],
+) -> Result<(), xml::writer::Error> {
+    let name = format_name(name)?;
+    let attributes = [(Name::prefixed("psftype", "psf2"), psftype)]
+        .into_iter()
+        .chain(
+            attributes
+                .iter()
+                .map(|(name, value)| (*name, value.as_ref())),
+        )
+        .map(|(name, value)| Attribute::new(name, value))
+        .collect::<Vec<_>>();
+    writer.write(XmlEvent::StartElement {
+        name: Name::from(name.as_ref()),
+        attributes: Cow::Owned(attributes),
+        namespace: Cow::Owned(Namespace::empty()),
+    })
+}
+
+/// Get the `xsi:type` attribute of an element of a PrintDeviceCapabilities document, whose text is the value.
+fn device_value_type(
+    value: Option<&super::PropertyValue>,
+) -> Result<Option<(Name<'static>, Cow<'static, str>)>, xml::writer::Error> {
+    value
+        .map(|x| {
+            let xsi_type = format_name(&x.xsi_type())?.into_owned();
+            Ok((Name::prefixed("type", "xsi"), Cow::Owned(xsi_type)))
+        })
+        .transpose()
+}
+
+fn write_device_property<W: Write>(
+    writer: &mut EventWriter<W>,
+    property: &super::Property,
+) -> Result<(), xml::writer::Error> {
+    let xsi_type = device_value_type(property.value.as_ref())?;
+    start_device_element(writer, &property.name, "Property", xsi_type.as_slice())?;
+    if let Some(value) = &property.value {
+        writer.write(XmlEvent::characters(&value_text(value)?))?;
+    }
+    for property in &property.properties {
+        write_device_property(writer, property)?;
+    }
+    writer.write(XmlEvent::end_element())?;
+    Ok(())
+}
+
+fn write_device_scored_property<W: Write>(
+    writer: &mut EventWriter<W>,
+    scored_property: &super::ScoredProperty,
+) -> Result<(), xml::writer::Error> {
+    let Some(name) = &scored_property.name else {
+        return Err(xml::writer::Error::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "ScoredProperty without name",
+        )));
+    };
+    let xsi_type = device_value_type(scored_property.value.as_ref())?;
+    start_device_element(writer, name, "ScoredProperty", xsi_type.as_slice())?;
+    if let Some(value) = &scored_property.value {
+        writer.write(XmlEvent::characters(&value_text(value)?))?;
+    }
+    if let Some(parameter_ref) = &scored_property.parameter_ref {
+        start_device_element(writer, parameter_ref, "ParameterRef", &[])?;
+        writer.write(XmlEvent::end_element())?;
+    }
+    for scored_property in &scored_property.scored_properties {
+        write_device_scored_property(writer, scored_property)?;
+    }
+    for property in &scored_property.properties {
+        write_device_property(writer, property)?;
+    }
+    writer.write(XmlEvent::end_element())?;
+    Ok(())
+}
+
+/// Write the parameter definition with its `psf` properties as the `psf2` attributes, see [`PARAMETER_ATTRIBUTES`].
+/// Other properties are written as child elements.
+fn write_device_parameter_def<W: Write>(
+    writer: &mut EventWriter<W>,
+    parameter_def: &super::ParameterDef,
+) -> Result<(), xml::writer::Error> {
+    let psf_value = |local_name: &str| {
+        parameter_def
+            .properties
+            .iter()
+            .find(|x| x.name.namespace_ref() == Some(NS_PSF) && x.name.local_name == local_name)
+            .and_then(|x| x.value.as_ref())
+    };
+    let mut attributes = vec![];
+    if let Some(super::PropertyValue::QName(data_type)) = psf_value("DataType") {
+        attributes.push((Name::prefixed("type", "xsi"), format_name(data_type)?));
+    }
+    if let Some(value) = psf_value("DefaultValue") {
+        attributes.push((Name::prefixed("default", "psf2"), value_text(value)?));
+    }
+    let mut names = vec!["DataType", "DefaultValue"];
+    for (attribute, local_name, _) in PARAMETER_ATTRIBUTES {
+        if let Some(value) = psf_value(local_name) {
+            attributes.push((Name::prefixed(attribute, "psf2"), value_text(value)?));
+        }
+        names.push(local_name);
+    }
+    start_device_element(writer, &parameter_def.name, "ParameterDef", &attributes)?;
+    for property in parameter_def.properties.iter().filter(|x| {
+        x.name.namespace_ref() != Some(NS_PSF) || !names.contains(&x.name.local_name.as_str())
+    }) {
+        write_device_property(writer, property)?;
+    }
+    writer.write(XmlEvent::end_element())?;
+    Ok(())
+}
+
+impl XmlSerializer for XmlNode {
+    fn collect_namespace(&self, _ns: &mut Namespace) {
+        // raw nodes keep their own namespace declarations
+    }
+
+    fn write_to<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), xml::writer::Error> {
+        match self {
+            XmlNode::Element(element) => {
+                writer.write(XmlEvent::StartElement {
+                    name: element.name.borrow(),
+                    attributes: Cow::Owned(element.attributes.iter().map(|x| x.borrow()).collect()),
+                    namespace: Cow::Owned(namespace_from(&element.namespaces)),
+                })?;
+                element
+                    .children
+                    .iter()
+                    .try_for_each(|x| x.write_to(writer))?;
+                writer.write(XmlEvent::end_element())?;
+            }
+            XmlNode::Characters(s) | XmlNode::Whitespace(s) => {
+                writer.write(XmlEvent::characters(s))?;
+            }
+            XmlNode::CData(s) => writer.write(XmlEvent::cdata(s))?,
+            XmlNode::Comment(s) => writer.write(XmlEvent::comment(s))?,
+            XmlNode::ProcessingInstruction { name, data } => {
+                writer.write(XmlEvent::processing_instruction(name, data.as_deref()))?
+            }
+        }
+        Ok(())
+    }
+}
+
+fn namespace_from(namespaces: &[(String, String)]) -> Namespace {
+    let mut ns = Namespace::empty();
+    for (prefix, uri) in namespaces {
+        ns.put(prefix.as_str(), uri.as_str());
+    }
+    ns
+}
+
+/// The namespaces declared by the element in the original document.
+fn declared_namespace(extra: &XmlExtra) -> Namespace {
+    namespace_from(&extra.namespaces)
+}
+
+fn write_prolog<W: Write>(
+    writer: &mut EventWriter<W>,
+    extra: &XmlExtra,
+) -> Result<(), xml::writer::Error> {
+    if extra.prolog.is_empty() {
+        return Ok(());
+    }
+    // comments do not trigger the XML declaration, so make sure it is written first
+    writer.write(XmlEvent::characters(""))?;
+    extra.prolog.iter().try_for_each(|x| x.write_to(writer))
+}
+
+/// Start an element with the given model attributes, keeping the original attribute order from `extra`.
+/// Model attributes without value are omitted.
+fn start_element<W: Write>(
     writer: &mut EventWriter<W>,
     elem_name: &str,
-    name: Option<&OwnedName>,
+    known: &[(&str, Option<&str>)],
+    ns: Namespace,
+    extra: &XmlExtra,
 ) -> Result<(), xml::writer::Error> {
-    if let Some(name) = name {
-        writer
-            .write(XmlEvent::start_element(elem_name).attr("name", format_name(name).as_ref()))?;
-    } else {
-        writer.write(XmlEvent::start_element(elem_name))?;
+    let is_known = |name: &OwnedName| {
+        name.namespace.is_none()
+            && name.prefix.is_none()
+            && known.iter().any(|(x, _)| *x == name.local_name)
+    };
+    let mut attributes = known
+        .iter()
+        .filter(|(name, _)| {
+            !extra
+                .attributes
+                .iter()
+                .any(|x| is_known(&x.name) && x.name.local_name == *name)
+        })
+        .filter_map(|(name, value)| value.map(|x| Attribute::new(Name::local(name), x)))
+        .collect::<Vec<_>>();
+    for attribute in &extra.attributes {
+        if is_known(&attribute.name) {
+            if let Some((name, Some(value))) =
+                known.iter().find(|(x, _)| *x == attribute.name.local_name)
+            {
+                attributes.push(Attribute::new(Name::local(name), value));
+            }
+        } else {
+            attributes.push(attribute.borrow());
+        }
+    }
+    writer.write(XmlEvent::StartElement {
+        name: elem_name.into(),
+        attributes: Cow::Owned(attributes),
+        namespace: Cow::Owned(ns),
+    })
+}
+
+/// Write the children in the original order from `extra`, then the remaining ones in `default_order`.
+///
+/// `write_child` writes the child of the given kind at the given index, and returns `false` if there is no such child.
+fn write_content<W: Write>(
+    writer: &mut EventWriter<W>,
+    extra: &XmlExtra,
+    default_order: &[SchemaElement],
+    mut write_child: impl FnMut(
+        &mut EventWriter<W>,
+        SchemaElement,
+        usize,
+    ) -> Result<bool, xml::writer::Error>,
+) -> Result<(), xml::writer::Error> {
+    let mut written = HashMap::<SchemaElement, usize>::new();
+    for content in &extra.content {
+        match content {
+            XmlContent::Schema(kind) => {
+                let index = written.entry(*kind).or_default();
+                if write_child(writer, *kind, *index)? {
+                    *index += 1;
+                }
+            }
+            XmlContent::Node(node) => node.write_to(writer)?,
+        }
+    }
+    for kind in default_order {
+        let index = written.entry(*kind).or_default();
+        while write_child(writer, *kind, *index)? {
+            *index += 1;
+        }
     }
     Ok(())
 }
 
+fn write_nth<W: Write, T: XmlSerializer>(
+    writer: &mut EventWriter<W>,
+    items: &[T],
+    index: usize,
+) -> Result<bool, xml::writer::Error> {
+    match items.get(index) {
+        Some(item) => item.write_to(writer).map(|_| true),
+        None => Ok(false),
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::XmlDocumentRoot;
+    use super::{PrefixMode, SerializeOptions, XmlDocumentRoot, XmlEncoding};
     use crate::ticket::document::{
-        reader::ParsableXmlDocument, PrintCapabilitiesDocument, PrintTicketDocument,
+        canonical::Canonicalize,
+        reader::{ParsableXmlDocument, ParseOptions},
+        ParameterInit, PrintCapabilitiesDocument, PrintDeviceCapabilitiesDocument,
+        PrintTicketDocument, PropertyValue, XmlNode, NS_PSK,
     };
+    use crate::ticket::{merge_print_tickets, PrintScope};
+    use xml::name::OwnedName;
+
+    const VENDOR_TICKET: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- written by the vendor driver -->
+<psf:PrintTicket version="1"
+    xmlns:psf="http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework"
+    xmlns:psk="http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords"
+    xmlns:xsd="http://www.w3.org/2001/XMLSchema"
+    xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+    xmlns:ns0000="http://vendor/private">
+    <?vendor-hint keep?>
+    <ns0000:Private ns0000:mode="fast"><ns0000:Blob>AAEC</ns0000:Blob></ns0000:Private>
+    <psf:Feature name="psk:PageMediaSize">
+        <!-- A4 -->
+        <psf:Option name="psk:ISOA4" ns0000:tag="a4">
+            <psf:ScoredProperty name="psk:MediaSizeWidth">
+                <psf:Value xsi:type="xsd:integer">210000</psf:Value>
+            </psf:ScoredProperty>
+        </psf:Option>
+    </psf:Feature>
+    <psf:ParameterInit name="psk:JobCopiesAllDocuments">
+        <psf:Value xsi:type="xsd:integer">1</psf:Value>
+    </psf:ParameterInit>
+</psf:PrintTicket>"#;
+
+    fn parse_lossless(xml: impl AsRef<[u8]>) -> PrintTicketDocument {
+        PrintTicketDocument::parse_from_bytes_with_options(xml, &ParseOptions::new().lossless(true))
+            .unwrap()
+    }
 
     #[test]
     fn serialize_for_print_ticket() {
@@ -337,4 +1019,179 @@ mod tests {
         let xml2 = document.to_xml();
         assert_eq!(xml1, xml2);
     }
+
+    #[test]
+    fn lossless_keeps_unknown_content() {
+        let document = parse_lossless(VENDOR_TICKET);
+        assert!(matches!(
+            document.extra.prolog.as_slice(),
+            [XmlNode::Comment(x)] if x == " written by the vendor driver "
+        ));
+
+        let xml = String::from_utf8(document.to_xml()).unwrap();
+        assert!(xml.contains("<!-- written by the vendor driver -->"));
+        assert!(xml.contains("<?vendor-hint keep?>"));
+        assert!(xml.contains("<!-- A4 -->"));
+        assert!(xml.contains(
+            r#"<ns0000:Private ns0000:mode="fast"><ns0000:Blob>AAEC</ns0000:Blob></ns0000:Private>"#
+        ));
+        assert!(xml.contains(r#"xmlns:ns0000="http://vendor/private""#));
+        assert!(xml.contains(r#"<psf:Option name="psk:ISOA4" ns0000:tag="a4">"#));
+        // the features and parameters are kept in the original order
+        assert!(xml.find("psf:Feature").unwrap() < xml.find("psf:ParameterInit").unwrap());
+
+        assert_eq!(parse_lossless(&xml), document);
+        assert_eq!(parse_lossless(&xml).to_xml(), xml.as_bytes());
+    }
+
+    #[test]
+    fn lossless_data_is_not_compared() {
+        use std::hash::{BuildHasher, RandomState};
+
+        let lossless = parse_lossless(VENDOR_TICKET);
+        let normal = PrintTicketDocument::parse_from_bytes(VENDOR_TICKET).unwrap();
+        assert_eq!(lossless, normal);
+        let state = RandomState::new();
+        assert_eq!(state.hash_one(&lossless), state.hash_one(&normal));
+    }
+
+    #[test]
+    fn merge_and_filter_keep_lossless_root() {
+        let base = parse_lossless(VENDOR_TICKET);
+        let delta = PrintTicketDocument::new(
+            vec![],
+            vec![ParameterInit::new(
+                OwnedName::qualified("JobCopiesAllDocuments", NS_PSK, Some("psk")),
+                PropertyValue::Integer(2),
+            )],
+            vec![],
+        );
+        for document in [
+            merge_print_tickets(&base, &delta),
+            base.restrict_to_scope(PrintScope::Page),
+        ] {
+            let xml = String::from_utf8(document.to_xml()).unwrap();
+            assert!(xml.contains("<!-- written by the vendor driver -->"));
+            assert!(xml.contains(r#"xmlns:ns0000="http://vendor/private""#));
+            assert!(xml.contains("<ns0000:Private"));
+        }
+    }
+
+    #[test]
+    fn lossless_edit_changes_only_edited_part() {
+        let mut document = parse_lossless(VENDOR_TICKET);
+        let xml1 = String::from_utf8(document.to_xml()).unwrap();
+        document.parameter_inits[0].value = PropertyValue::Integer(2);
+        let xml2 = String::from_utf8(document.to_xml()).unwrap();
+        assert_eq!(
+            xml2,
+            xml1.replace(
+                r#"<psf:Value xsi:type="xsd:integer">1</psf:Value>"#,
+                r#"<psf:Value xsi:type="xsd:integer">2</psf:Value>"#
+            )
+        );
+    }
+
+    #[test]
+    fn lossless_print_capabilities() {
+        let origin = include_bytes!("../../../test_data/print_capabilities.xml");
+        let options = ParseOptions::new().lossless(true);
+        let document =
+            PrintCapabilitiesDocument::parse_from_bytes_with_options(origin, &options).unwrap();
+        let xml = document.to_xml();
+        assert_eq!(
+            PrintCapabilitiesDocument::parse_from_bytes(&xml).unwrap(),
+            PrintCapabilitiesDocument::parse_from_bytes(origin).unwrap()
+        );
+        assert_eq!(
+            PrintCapabilitiesDocument::parse_from_bytes_with_options(&xml, &options)
+                .unwrap()
+                .to_xml(),
+            xml
+        );
+    }
+
+    #[test]
+    fn serialize_with_indent() {
+        let document = parse_lossless(VENDOR_TICKET);
+        let options = SerializeOptions::new().indent(true);
+        let xml = String::from_utf8(document.try_to_xml_with_options(&options).unwrap()).unwrap();
+        assert!(xml.contains("\n  <psf:Feature name=\"psk:PageMediaSize\">"));
+        assert!(xml.contains("\n    <!-- A4 -->"));
+        assert_eq!(
+            PrintTicketDocument::parse_from_bytes(&xml).unwrap(),
+            PrintTicketDocument::parse_from_bytes(VENDOR_TICKET).unwrap()
+        );
+    }
+
+    #[test]
+    fn serialize_without_declaration() {
+        let document = PrintTicketDocument::parse_from_bytes(VENDOR_TICKET).unwrap();
+        let options = SerializeOptions::new().declaration(false);
+        let xml = document.try_to_xml_with_options(&options).unwrap();
+        assert!(xml.starts_with(b"<psf:PrintTicket"));
+        assert!(document
+            .to_xml()
+            .starts_with(b"<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+    }
+
+    #[test]
+    fn serialize_as_utf16() {
+        let document = PrintTicketDocument::parse_from_bytes(VENDOR_TICKET).unwrap();
+        let options = SerializeOptions::new().encoding(XmlEncoding::Utf16Le);
+        let xml = document.try_to_xml_with_options(&options).unwrap();
+        assert_eq!(xml[..2], [0xFF, 0xFE]);
+        let units = xml[2..]
+            .chunks(2)
+            .map(|x| u16::from_le_bytes([x[0], x[1]]))
+            .collect::<Vec<_>>();
+        let text = String::from_utf16(&units).unwrap();
+        assert!(text.starts_with("<?xml version=\"1.0\" encoding=\"UTF-16\"?>"));
+        assert_eq!(
+            PrintTicketDocument::parse_from_bytes(&xml).unwrap(),
+            document
+        );
+    }
+
+    #[test]
+    fn serialize_with_standard_prefixes() {
+        let xml = VENDOR_TICKET
+            .replace("psf:", "f:")
+            .replace("xmlns:psf", "xmlns:f")
+            .replace("psk:", "k:")
+            .replace("xmlns:psk", "xmlns:k");
+        let document = PrintTicketDocument::parse_from_bytes(&xml).unwrap();
+        let xml = String::from_utf8(document.to_xml()).unwrap();
+        assert!(xml.contains("<psf:Feature name=\"k:PageMediaSize\">"));
+
+        let options = SerializeOptions::new().prefix_mode(PrefixMode::Standard);
+        let xml = String::from_utf8(document.try_to_xml_with_options(&options).unwrap()).unwrap();
+        assert!(xml.contains("<psf:Feature name=\"psk:PageMediaSize\">"));
+        assert!(PrintTicketDocument::parse_from_bytes(&xml)
+            .unwrap()
+            .semantic_eq(&document));
+    }
+
+    #[test]
+    fn serialize_print_device_capabilities() {
+        let xml = include_bytes!("../../../test_data/print_device_capabilities.xml");
+        let document = PrintDeviceCapabilitiesDocument::parse_from_bytes(xml).unwrap();
+        let written = document.to_xml();
+        assert_eq!(
+            PrintDeviceCapabilitiesDocument::parse_from_bytes(&written).unwrap(),
+            document
+        );
+        let written = String::from_utf8(written).unwrap();
+        assert!(written
+            .contains(r#"<psk:NorthAmericaLetter psf2:psftype="Option" psf2:default="true">"#));
+        assert!(written.contains(r#"psf2:minValue="1" psf2:maxValue="999""#));
+    }
+
+    #[test]
+    fn invalid_name_is_an_error() {
+        let mut document = PrintTicketDocument::parse_from_bytes(VENDOR_TICKET).unwrap();
+        document.features[0].name =
+            OwnedName::qualified("bad name", crate::ticket::document::NS_PSK, Some("psk"));
+        assert!(document.try_to_xml().is_err());
+    }
 }
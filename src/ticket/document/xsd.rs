@@ -0,0 +1,291 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use std::{fmt, str::FromStr};
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+/// Represents an error occurred while parsing a `xsd:dateTime` value.
+#[error("Invalid xsd:dateTime: {0:?}")]
+pub struct ParseDateTimeError(String);
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// Represents a `xsd:dateTime` value.
+pub struct XsdDateTime {
+    /// The year, which may be negative.
+    pub year: i32,
+    /// The month, from 1 to 12.
+    pub month: u8,
+    /// The day of the month, from 1 to 31.
+    pub day: u8,
+    /// The hour, from 0 to 23.
+    pub hour: u8,
+    /// The minute, from 0 to 59.
+    pub minute: u8,
+    /// The second, from 0 to 59.
+    pub second: u8,
+    /// The fractional part of the second, in nanoseconds.
+    pub nanosecond: u32,
+    /// The time zone offset from UTC in minutes, or `None` if the value has no time zone.
+    pub offset_minutes: Option<i16>,
+}
+
+impl FromStr for XsdDateTime {
+    type Err = ParseDateTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseDateTimeError(s.to_string());
+        let number = |x: &str, len: usize| {
+            if x.len() == len && x.bytes().all(|x| x.is_ascii_digit()) {
+                x.parse::<u32>().ok()
+            } else {
+                None
+            }
+        };
+
+        let (negative, rest) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        let (date, rest) = rest.split_once('T').ok_or_else(error)?;
+        let mut date_parts = date.rsplitn(3, '-');
+        let day = date_parts.next().and_then(|x| number(x, 2));
+        let month = date_parts.next().and_then(|x| number(x, 2));
+        let year = date_parts
+            .next()
+            .filter(|x| x.len() >= 4 && !(x.len() > 4 && x.starts_with('0')))
+            .and_then(|x| number(x, x.len()));
+        let (Some(year), Some(month), Some(day)) = (year, month, day) else {
+            return Err(error());
+        };
+        let year = i32::try_from(year).map_err(|_| error())?;
+        let year = if negative { -year } else { year };
+
+        let (time, offset_minutes) = if let Some(time) = rest.strip_suffix('Z') {
+            (time, Some(0))
+        } else if let Some(index) = rest.rfind(['+', '-']) {
+            let (time, offset) = rest.split_at(index);
+            let (hours, minutes) = offset[1..].split_once(':').ok_or_else(error)?;
+            let (Some(hours), Some(minutes)) = (number(hours, 2), number(minutes, 2)) else {
+                return Err(error());
+            };
+            if minutes > 59 || hours * 60 + minutes > 14 * 60 {
+                return Err(error());
+            }
+            let minutes = (hours * 60 + minutes) as i16;
+            (
+                time,
+                Some(if offset.starts_with('-') {
+                    -minutes
+                } else {
+                    minutes
+                }),
+            )
+        } else {
+            (rest, None)
+        };
+
+        let (time, fraction) = match time.split_once('.') {
+            Some((time, fraction)) => (time, Some(fraction)),
+            None => (time, None),
+        };
+        let mut time_parts = time.splitn(3, ':');
+        let hour = time_parts.next().and_then(|x| number(x, 2));
+        let minute = time_parts.next().and_then(|x| number(x, 2));
+        let second = time_parts.next().and_then(|x| number(x, 2));
+        let (Some(hour), Some(minute), Some(second)) = (hour, minute, second) else {
+            return Err(error());
+        };
+        let nanosecond = match fraction {
+            Some(x) if !x.is_empty() && x.bytes().all(|x| x.is_ascii_digit()) => {
+                format!("{:0<9}", &x[..x.len().min(9)]).parse().unwrap()
+            }
+            Some(_) => return Err(error()),
+            None => 0,
+        };
+
+        if !(1..=12).contains(&month)
+            || day < 1
+            || day > days_in_month(year, month)
+            || minute > 59
+            || second > 59
+            || (hour > 23 && !(hour == 24 && minute == 0 && second == 0 && nanosecond == 0))
+        {
+            return Err(error());
+        }
+        let mut result = Self {
+            year,
+            month: month as u8,
+            day: day as u8,
+            hour: hour as u8,
+            minute: minute as u8,
+            second: second as u8,
+            nanosecond,
+            offset_minutes,
+        };
+        if hour == 24 {
+            // 24:00:00 is the same as 00:00:00 of the next day
+            result.hour = 0;
+            if u32::from(result.day) < days_in_month(year, month) {
+                result.day += 1;
+            } else if result.month < 12 {
+                result.day = 1;
+                result.month += 1;
+            } else {
+                result.day = 1;
+                result.month = 1;
+                result.year = year.checked_add(1).ok_or_else(error)?;
+            }
+        }
+        Ok(result)
+    }
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl fmt::Display for XsdDateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.year < 0 {
+            write!(f, "-")?;
+        }
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.year.unsigned_abs(),
+            self.month,
+            self.day,
+            self.hour,
+            self.minute,
+            self.second
+        )?;
+        if self.nanosecond != 0 {
+            let fraction = format!("{:09}", self.nanosecond);
+            write!(f, ".{}", fraction.trim_end_matches('0'))?;
+        }
+        match self.offset_minutes {
+            Some(0) => write!(f, "Z"),
+            Some(offset) => write!(
+                f,
+                "{}{:02}:{:02}",
+                if offset < 0 { '-' } else { '+' },
+                offset.unsigned_abs() / 60,
+                offset.unsigned_abs() % 60
+            ),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Parse a `xsd:integer` lexical value into its canonical form, without sign for positive values and leading zeros.
+pub(crate) fn normalize_integer(s: &str) -> Option<String> {
+    let (negative, digits) = split_sign(s.trim());
+    if digits.is_empty() || !digits.bytes().all(|x| x.is_ascii_digit()) {
+        return None;
+    }
+    let digits = digits.trim_start_matches('0');
+    Some(match (negative, digits) {
+        (_, "") => "0".to_string(),
+        (true, digits) => format!("-{}", digits),
+        (false, digits) => digits.to_string(),
+    })
+}
+
+/// Check a `xsd:decimal` lexical value.
+pub(crate) fn is_decimal(s: &str) -> bool {
+    let (_, digits) = split_sign(s);
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    !(integer.is_empty() && fraction.is_empty())
+        && integer.bytes().all(|x| x.is_ascii_digit())
+        && fraction.bytes().all(|x| x.is_ascii_digit())
+}
+
+/// Convert a `xsd:decimal` lexical value into its canonical form, which has no redundant zeros or sign.
+pub(crate) fn normalize_decimal(s: &str) -> String {
+    let (negative, digits) = split_sign(s);
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    let integer = integer.trim_start_matches('0');
+    let fraction = fraction.trim_end_matches('0');
+    let integer = if integer.is_empty() { "0" } else { integer };
+    let sign = if negative && (integer != "0" || !fraction.is_empty()) {
+        "-"
+    } else {
+        ""
+    };
+    if fraction.is_empty() {
+        format!("{}{}", sign, integer)
+    } else {
+        format!("{}{}.{}", sign, integer, fraction)
+    }
+}
+
+fn split_sign(s: &str) -> (bool, &str) {
+    if let Some(rest) = s.strip_prefix('-') {
+        (true, rest)
+    } else {
+        (false, s.strip_prefix('+').unwrap_or(s))
+    }
+}
+
+/// Parse a `xsd:boolean` lexical value.
+pub(crate) fn parse_boolean(s: &str) -> Option<bool> {
+    match s {
+        "true" | "1" => Some(true),
+        "false" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// Decode a `xsd:base64Binary` lexical value, ignoring whitespace.
+pub(crate) fn decode_base64(s: &str) -> Option<Vec<u8>> {
+    let compact = s
+        .chars()
+        .filter(|x| !x.is_ascii_whitespace())
+        .collect::<String>();
+    STANDARD.decode(compact).ok()
+}
+
+/// Encode a `xsd:base64Binary` value.
+pub(crate) fn encode_base64(data: &[u8]) -> String {
+    STANDARD.encode(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize_decimal, normalize_integer, XsdDateTime};
+
+    #[test]
+    fn date_time() {
+        let value = "2024-02-29T13:05:09.120+08:00"
+            .parse::<XsdDateTime>()
+            .unwrap();
+        assert_eq!(value.year, 2024);
+        assert_eq!(value.nanosecond, 120_000_000);
+        assert_eq!(value.offset_minutes, Some(480));
+        assert_eq!(value.to_string(), "2024-02-29T13:05:09.12+08:00");
+
+        let value = "-0044-03-15T00:00:00Z".parse::<XsdDateTime>().unwrap();
+        assert_eq!(value.year, -44);
+        assert_eq!(value.to_string(), "-0044-03-15T00:00:00Z");
+
+        let value = "1999-12-31T24:00:00".parse::<XsdDateTime>().unwrap();
+        assert_eq!(value.to_string(), "2000-01-01T00:00:00");
+
+        assert!("2023-02-29T00:00:00".parse::<XsdDateTime>().is_err());
+        assert!("2023-01-01".parse::<XsdDateTime>().is_err());
+        assert!("2023-01-01T00:00:00+15:00".parse::<XsdDateTime>().is_err());
+    }
+
+    #[test]
+    fn lexical_forms() {
+        assert_eq!(normalize_integer("+0042").as_deref(), Some("42"));
+        assert_eq!(normalize_integer("-0").as_deref(), Some("0"));
+        assert_eq!(normalize_integer("1.0"), None);
+        assert_eq!(normalize_decimal("+001.500"), "1.5");
+        assert_eq!(normalize_decimal("-.0"), "0");
+    }
+}
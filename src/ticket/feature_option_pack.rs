@@ -1,11 +1,12 @@
+use thiserror::Error;
 use xml::name::OwnedName;
 
 use super::{
     document::{
-        ParameterInit, PrintFeature, PrintFeatureOption, PrintTicketDocument, WithProperties,
-        NS_PSK,
+        same_name, ParameterInit, ParameterValueError, PrintFeature, PrintFeatureOption,
+        PrintTicketDocument, PropertyValue, WithProperties, WithScoredProperties, NS_PSK,
     },
-    PrintCapabilities, PrintTicket,
+    Keyword, PrintCapabilities, PrintTicket, PropertyKeyword,
 };
 
 /// A trait for the predefined name.
@@ -63,16 +64,16 @@ where
 {
     fn from(value: T) -> Self {
         let (option, parameters) = value.into_option_with_parameters();
-        PrintTicketDocument {
-            properties: vec![],
-            parameter_inits: parameters,
-            features: vec![PrintFeature {
-                name: T::feature_name(),
-                properties: vec![],
-                options: vec![option],
-                features: vec![],
-            }],
-        }
+        PrintTicketDocument::new(
+            vec![],
+            parameters,
+            vec![PrintFeature::new(
+                T::feature_name(),
+                vec![],
+                vec![option],
+                vec![],
+            )],
+        )
         .into()
     }
 }
@@ -92,68 +93,325 @@ pub trait FeatureOptionPackWithPredefined: FeatureOptionPack {
     }
 }
 
-/// Implement the [`FeatureOptionPack`] for the given type.
+#[derive(Error, Debug)]
+/// Represents an error occurred while setting a parameter referenced by an option of a feature option pack.
+pub enum SetParameterError {
+    /// The option does not take the scored property as a parameter.
+    #[error("The option does not take {0} as a parameter")]
+    NotParameterized(OwnedName),
+    /// The parameter is not defined by the print capabilities.
+    #[error("Parameter {0} is not defined by the print capabilities")]
+    UndefinedParameter(OwnedName),
+    /// The value is not valid for the parameter.
+    #[error("Invalid value for the parameter")]
+    InvalidValue(#[source] ParameterValueError),
+}
+
+/// Set the parameter referenced by the given scored property of the option,
+/// after checking the value against the parameter definition of the capabilities.
+pub(crate) fn set_referenced_parameter(
+    capabilities: &PrintCapabilities,
+    option: &PrintFeatureOption,
+    parameters: &mut Vec<ParameterInit>,
+    property: PropertyKeyword,
+    value: PropertyValue,
+) -> Result<(), SetParameterError> {
+    let Some(name) = option
+        .get_scored_property(property.local_name(), Some(NS_PSK))
+        .and_then(|x| x.parameter_ref.as_ref())
+    else {
+        return Err(SetParameterError::NotParameterized(property.name()));
+    };
+    let parameter_init = capabilities
+        .document
+        .parameter_defs
+        .iter()
+        .find(|x| same_name(&x.name, name))
+        .ok_or_else(|| SetParameterError::UndefinedParameter(name.clone()))?
+        .definition()
+        .init(value)
+        .map_err(SetParameterError::InvalidValue)?;
+    match parameters.iter_mut().find(|x| same_name(&x.name, name)) {
+        Some(parameter) => parameter.value = parameter_init.value,
+        None => parameters.push(parameter_init),
+    }
+    Ok(())
+}
+
+/// Define a feature option pack type, which implements [`FeatureOptionPack`] for the options of the given feature.
 ///
 /// # Parameters
-/// - `$feature_name:expr`: The feature name of the option.
+/// - `$feature_name:expr`: The feature name of the option, as [`OwnedName`](crate::ticket::document::OwnedName).
 /// - `$name:ident`: The type to define.
-/// - `$predefined_name:ident`: The type of predefined name. If not specified, the type is not predefined.
+/// - `$predefined_name:path`: The type of predefined name, which implements [`PredefinedName`].
+///   If specified, [`FeatureOptionPackWithPredefined`] is implemented as well.
 ///
 /// # Example
-/// ```ignore
+/// A vendor feature outside the `psk` namespace, with its predefined options.
+/// ```rust
+/// use winprint::{define_feature_option_pack, define_predefined_name};
+/// use winprint::ticket::{
+///     document::{reader::ParsableXmlDocument, OwnedName, PrintCapabilitiesDocument},
+///     FeatureOptionPack, FeatureOptionPackWithPredefined, PrintCapabilities, PrintTicket,
+/// };
+///
+/// const NS_VENDOR: &str = "http://vendor/private";
+///
+/// define_predefined_name! {
+///     namespace = NS_VENDOR;
+///     #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+///     /// Represents a predefined staple mode of the vendor.
+///     pub enum StapleMode {
+///         /// No staple.
+///         Off,
+///         /// One staple at the top left.
+///         TopLeft,
+///     }
+/// }
+///
 /// define_feature_option_pack!(
-///     OwnedName::qualified("MyFeature", NS_PSK, Some("psk")),
-///     MyPack,
-///     MyPredefinedName
+///     OwnedName::qualified("JobStapleMode", NS_VENDOR, Some("ns0000")),
+///     JobStapleMode,
+///     StapleMode
 /// );
+///
+/// let xml = r#"<psf:PrintCapabilities version="1"
+///     xmlns:psf="http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework"
+///     xmlns:psk="http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords"
+///     xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+///     xmlns:xsd="http://www.w3.org/2001/XMLSchema"
+///     xmlns:ns0000="http://vendor/private">
+///     <psf:Feature name="ns0000:JobStapleMode">
+///         <psf:Option name="ns0000:Off" />
+///         <psf:Option name="ns0000:TopLeft">
+///             <psf:Property name="psk:DisplayName">
+///                 <psf:Value xsi:type="xsd:string">Top left</psf:Value>
+///             </psf:Property>
+///         </psf:Option>
+///     </psf:Feature>
+/// </psf:PrintCapabilities>"#;
+/// let capabilities = PrintCapabilities {
+///     document: PrintCapabilitiesDocument::parse_from_bytes(xml).unwrap(),
+/// };
+/// let top_left = JobStapleMode::list(&capabilities)
+///     .find(|x| x.as_predefined_name() == Some(StapleMode::TopLeft))
+///     .unwrap();
+/// assert_eq!(top_left.display_name(), Some("Top left"));
+/// let ticket = PrintTicket::from(top_left);
 /// ```
+#[macro_export]
 macro_rules! define_feature_option_pack {
     ($feature_name:expr, $name:ident) => {
         #[derive(Clone, Debug)]
         #[doc = concat!("Represents a feature option pack as [`", stringify!($name), "`].")]
         pub struct $name {
             /// The option of the feature.
-            option: PrintFeatureOption,
+            option: $crate::ticket::document::PrintFeatureOption,
             /// The parameters that is used by the option.
-            parameters: Vec<ParameterInit>,
+            parameters: Vec<$crate::ticket::document::ParameterInit>,
         }
 
-        impl crate::ticket::FeatureOptionPack for $name {
-            fn new(option: PrintFeatureOption, parameters: Vec<ParameterInit>) -> Self {
+        impl $crate::ticket::FeatureOptionPack for $name {
+            fn new(
+                option: $crate::ticket::document::PrintFeatureOption,
+                parameters: Vec<$crate::ticket::document::ParameterInit>,
+            ) -> Self {
                 Self { option, parameters }
             }
 
-            fn feature_name() -> OwnedName {
+            fn feature_name() -> $crate::ticket::document::OwnedName {
                 $feature_name
             }
 
-            fn option(&self) -> &PrintFeatureOption {
+            fn option(&self) -> &$crate::ticket::document::PrintFeatureOption {
                 &self.option
             }
 
-            fn option_mut(&mut self) -> &mut PrintFeatureOption {
+            fn option_mut(&mut self) -> &mut $crate::ticket::document::PrintFeatureOption {
                 &mut self.option
             }
 
-            fn parameters(&self) -> &[ParameterInit] {
+            fn parameters(&self) -> &[$crate::ticket::document::ParameterInit] {
                 &self.parameters
             }
 
-            fn parameters_mut(&mut self) -> &mut Vec<ParameterInit> {
+            fn parameters_mut(&mut self) -> &mut Vec<$crate::ticket::document::ParameterInit> {
                 &mut self.parameters
             }
 
-            fn into_option_with_parameters(self) -> (PrintFeatureOption, Vec<ParameterInit>) {
+            fn into_option_with_parameters(
+                self,
+            ) -> (
+                $crate::ticket::document::PrintFeatureOption,
+                Vec<$crate::ticket::document::ParameterInit>,
+            ) {
                 (self.option, self.parameters)
             }
         }
     };
-    ($feature_name:expr, $name:ident, $predefined_name:ident) => {
-        define_feature_option_pack!($feature_name, $name);
+    ($feature_name:expr, $name:ident, $predefined_name:path) => {
+        $crate::define_feature_option_pack!($feature_name, $name);
 
-        impl crate::ticket::FeatureOptionPackWithPredefined for $name {
+        impl $crate::ticket::FeatureOptionPackWithPredefined for $name {
             type PredefinedName = $predefined_name;
         }
     };
 }
 pub(crate) use define_feature_option_pack;
+
+/// Define an enum of predefined names, which implements [`PredefinedName`] for the names in the given namespace.
+///
+/// Each variant matches the name whose local name is the name of the variant,
+/// or the string literal given as `Variant = "LocalName"` for local names which are not Rust identifiers.
+/// The attributes of the enum and its variants are kept, so derives like `Debug` and `Clone` are given as usual.
+///
+/// # Example
+/// ```rust
+/// use winprint::define_predefined_name;
+/// use winprint::ticket::{document::OwnedName, PredefinedName};
+///
+/// define_predefined_name! {
+///     namespace = "http://vendor/private";
+///     #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+///     /// Represents a predefined font mode of the vendor.
+///     pub enum FontMode {
+///         /// Download fonts as outlines.
+///         Outline,
+///         /// Render fonts as bitmaps.
+///         Bitmap,
+///         /// Keep fonts as TrueType ones.
+///         TrueType = "True-Type",
+///     }
+/// }
+///
+/// let name = OwnedName::qualified("Bitmap", "http://vendor/private", Some("ns0000"));
+/// assert_eq!(FontMode::from_name(&name), Some(FontMode::Bitmap));
+/// let name = OwnedName::qualified("True-Type", "http://vendor/private", Some("ns0000"));
+/// assert_eq!(FontMode::from_name(&name), Some(FontMode::TrueType));
+/// assert_eq!(FontMode::from_name(&OwnedName::local("Bitmap")), None);
+/// ```
+#[macro_export]
+macro_rules! define_predefined_name {
+    (
+        namespace = $namespace:expr;
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident $(= $local_name:literal)?),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $($(#[$variant_meta])* $variant,)*
+        }
+
+        impl $crate::ticket::PredefinedName for $name {
+            fn from_name(name: &$crate::ticket::document::OwnedName) -> Option<Self> {
+                if name.namespace_ref() != Some($namespace) {
+                    return None;
+                }
+                match name.local_name.as_str() {
+                    $($crate::define_predefined_name!(@local_name $variant $(= $local_name)?) => {
+                        Some(Self::$variant)
+                    })*
+                    _ => None,
+                }
+            }
+        }
+    };
+    (@local_name $variant:ident) => {
+        stringify!($variant)
+    };
+    (@local_name $variant:ident = $local_name:literal) => {
+        $local_name
+    };
+}
+pub(crate) use define_predefined_name;
+
+#[cfg(test)]
+mod tests {
+    use super::{FeatureOptionPack, FeatureOptionPackWithPredefined, PredefinedName};
+    use crate::ticket::test_fixtures::capabilities_with;
+    use crate::ticket::{
+        document::{reader::ParsableXmlDocument, PrintTicketDocument},
+        PrintCapabilities, PrintTicket,
+    };
+    use xml::name::OwnedName;
+
+    const NS_VENDOR: &str = "http://vendor/private";
+
+    define_predefined_name! {
+        namespace = NS_VENDOR;
+        #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+        pub enum StapleMode {
+            Off,
+            TopLeft,
+            DualLeft = "Dual-Left",
+        }
+    }
+
+    define_feature_option_pack!(
+        OwnedName::qualified("JobStapleMode", NS_VENDOR, Some("ns0000")),
+        JobStapleMode,
+        self::StapleMode
+    );
+
+    fn capabilities() -> PrintCapabilities {
+        capabilities_with(
+            r#"    <psf:Feature name="ns0000:JobStapleMode">
+        <psf:Option name="ns0000:Off" />
+        <psf:Option name="ns0000:TopLeft">
+            <psf:Property name="psk:DisplayName">
+                <psf:Value xsi:type="xsd:string">Top left</psf:Value>
+            </psf:Property>
+        </psf:Option>
+        <psf:Option name="ns0000:Saddle" />
+    </psf:Feature>
+    <psf:Feature name="psk:PageOrientation">
+        <psf:Option name="psk:Portrait" />
+    </psf:Feature>"#,
+        )
+    }
+
+    #[test]
+    fn predefined_name_in_vendor_namespace() {
+        let name = |local_name: &str, namespace: &str| {
+            OwnedName::qualified(local_name, namespace, Some("vendor"))
+        };
+        assert_eq!(
+            StapleMode::from_name(&name("TopLeft", NS_VENDOR)),
+            Some(StapleMode::TopLeft)
+        );
+        assert_eq!(StapleMode::from_name(&name("Saddle", NS_VENDOR)), None);
+        assert_eq!(
+            StapleMode::from_name(&name("Dual-Left", NS_VENDOR)),
+            Some(StapleMode::DualLeft)
+        );
+        assert_eq!(StapleMode::from_name(&name("DualLeft", NS_VENDOR)), None);
+        assert_eq!(
+            StapleMode::from_name(&name("TopLeft", crate::ticket::document::NS_PSK)),
+            None
+        );
+    }
+
+    #[test]
+    fn feature_option_pack_in_vendor_namespace() {
+        let capabilities = capabilities();
+        let options = JobStapleMode::list(&capabilities).collect::<Vec<_>>();
+        assert_eq!(
+            options
+                .iter()
+                .map(|x| x.as_predefined_name())
+                .collect::<Vec<_>>(),
+            vec![Some(StapleMode::Off), Some(StapleMode::TopLeft), None]
+        );
+        assert_eq!(options[0].display_name(), None);
+        assert_eq!(options[1].display_name(), Some("Top left"));
+
+        let ticket = PrintTicket::from(options[1].clone());
+        let document = PrintTicketDocument::parse_from_bytes(ticket.xml).unwrap();
+        let feature = document.feature(&JobStapleMode::feature_name()).unwrap();
+        let option = feature.options[0].name.as_ref().unwrap();
+        assert_eq!(option.local_name, "TopLeft");
+        assert_eq!(option.namespace_ref(), Some(NS_VENDOR));
+    }
+}
@@ -0,0 +1,12 @@
+use super::{define_feature_option_pack, FeatureKeyword, Keyword, PredefinedHolePunch};
+
+define_feature_option_pack!(
+    FeatureKeyword::JobHolePunch.name(),
+    JobHolePunch,
+    PredefinedHolePunch
+);
+define_feature_option_pack!(
+    FeatureKeyword::DocumentHolePunch.name(),
+    DocumentHolePunch,
+    PredefinedHolePunch
+);
@@ -0,0 +1,131 @@
+use super::{
+    define_feature_option_pack,
+    document::{PrintFeatureOption, WithProperties, NS_PSK},
+    FeatureKeyword, InputBinType, InputFeedType, Keyword, PredefinedInputBin, PredefinedName,
+};
+
+define_feature_option_pack!(
+    FeatureKeyword::JobInputBin.name(),
+    JobInputBin,
+    PredefinedInputBin
+);
+define_feature_option_pack!(
+    FeatureKeyword::DocumentInputBin.name(),
+    DocumentInputBin,
+    PredefinedInputBin
+);
+define_feature_option_pack!(
+    FeatureKeyword::PageInputBin.name(),
+    PageInputBin,
+    PredefinedInputBin
+);
+
+fn qualified_property<T: PredefinedName>(option: &PrintFeatureOption, name: &str) -> Option<T> {
+    option
+        .get_property(name, Some(NS_PSK))
+        .and_then(|x| x.value.as_ref())
+        .and_then(|x| x.qualified_name())
+        .and_then(T::from_name)
+}
+
+macro_rules! impl_input_bin {
+    ($name:ident) => {
+        impl $name {
+            /// Get the kind of media the input bin holds.
+            /// If the option does not specify a predefined bin type, `None` is returned.
+            pub fn bin_type(&self) -> Option<InputBinType> {
+                qualified_property(&self.option, "BinType")
+            }
+
+            /// Get how media is fed from the input bin.
+            /// If the option does not specify a predefined feed type, `None` is returned.
+            pub fn feed_type(&self) -> Option<InputFeedType> {
+                qualified_property(&self.option, "FeedType")
+            }
+        }
+    };
+}
+
+impl_input_bin!(JobInputBin);
+impl_input_bin!(DocumentInputBin);
+impl_input_bin!(PageInputBin);
+
+#[cfg(test)]
+mod tests {
+    use crate::ticket::test_fixtures::capabilities_with;
+    use crate::ticket::{
+        FeatureOptionPackWithPredefined, InputBinType, InputFeedType, PredefinedInputBin,
+        PredefinedMediaType, PrintCapabilities,
+    };
+
+    fn capabilities() -> PrintCapabilities {
+        capabilities_with(
+            r#"    <psf:Feature name="psk:PageInputBin">
+        <psf:Option name="psk:Tractor">
+            <psf:Property name="psk:BinType">
+                <psf:Value xsi:type="xsd:QName">psk:ContinuousFeed</psf:Value>
+            </psf:Property>
+            <psf:Property name="psk:FeedType">
+                <psf:Value xsi:type="xsd:QName">psk:Automatic</psf:Value>
+            </psf:Property>
+        </psf:Option>
+        <psf:Option name="ns0000:ChequeTray">
+            <psf:Property name="psk:BinType">
+                <psf:Value xsi:type="xsd:QName">psk:SheetFeed</psf:Value>
+            </psf:Property>
+            <psf:Property name="psk:FeedType">
+                <psf:Value xsi:type="xsd:QName">psk:Manual</psf:Value>
+            </psf:Property>
+        </psf:Option>
+    </psf:Feature>
+    <psf:Feature name="psk:PageMediaType">
+        <psf:Option name="psk:Plain" />
+        <psf:Option name="psk:Label" />
+        <psf:Option name="ns0000:Cheque" />
+    </psf:Feature>"#,
+        )
+    }
+
+    #[test]
+    fn list_input_bins() {
+        let capabilities = capabilities();
+        let bins = capabilities
+            .page_input_bins()
+            .map(|x| (x.as_predefined_name(), x.bin_type(), x.feed_type()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            bins,
+            vec![
+                (
+                    Some(PredefinedInputBin::Tractor),
+                    Some(InputBinType::ContinuousFeed),
+                    Some(InputFeedType::Automatic)
+                ),
+                (
+                    None,
+                    Some(InputBinType::SheetFeed),
+                    Some(InputFeedType::Manual)
+                ),
+            ]
+        );
+        assert_eq!(capabilities.job_input_bins().count(), 0);
+        assert_eq!(capabilities.document_input_bins().count(), 0);
+    }
+
+    #[test]
+    fn list_page_media_types() {
+        let capabilities = capabilities();
+        let types = capabilities
+            .page_media_types()
+            .map(|x| x.as_predefined_name())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            types,
+            vec![
+                Some(PredefinedMediaType::Plain),
+                Some(PredefinedMediaType::Label),
+                None
+            ]
+        );
+    }
+}
@@ -1,12 +1,7 @@
-use super::{
-    define_feature_option_pack,
-    document::{ParameterInit, PrintFeatureOption, NS_PSK},
-    PredefinedDuplexType,
-};
-use xml::name::OwnedName;
+use super::{define_feature_option_pack, FeatureKeyword, Keyword, PredefinedDuplexType};
 
 define_feature_option_pack!(
-    OwnedName::qualified("JobDuplexAllDocumentsContiguously", NS_PSK, Some("psk")),
+    FeatureKeyword::JobDuplexAllDocumentsContiguously.name(),
     JobDuplex,
     PredefinedDuplexType
 );
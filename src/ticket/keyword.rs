@@ -0,0 +1,644 @@
+use super::{define_predefined_name, document::NS_PSK, PredefinedName, PrintScope};
+use super::{
+    InputBinType, InputFeedType, PredefinedBinding, PredefinedDuplexType, PredefinedHolePunch,
+    PredefinedInputBin, PredefinedMediaName, PredefinedMediaType, PredefinedPageOrientation,
+    PredefinedPageOutputColor, PredefinedPageScaling, PredefinedPresentationDirection,
+    PredefinedStaple, SelectionType,
+};
+use strum::{EnumString, IntoStaticStr, VariantArray};
+use xml::name::OwnedName;
+
+/// A trait for the names defined by the Print Schema Keywords in the `psk` namespace.
+pub trait Keyword: PredefinedName + Copy + 'static {
+    /// Get the local name of the keyword.
+    fn local_name(self) -> &'static str;
+
+    /// Get the qualified name of the keyword with the `psk` prefix.
+    fn name(self) -> OwnedName {
+        OwnedName::qualified(self.local_name(), NS_PSK, Some("psk"))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// Represents the expected type of the value of a property or a parameter.
+pub enum KeywordValueType {
+    /// `xsd:string`
+    String,
+    /// `xsd:integer`
+    Integer,
+    /// `xsd:decimal`
+    Decimal,
+    /// `xsd:boolean`
+    Boolean,
+    /// `xsd:QName`
+    QName,
+}
+
+impl KeywordValueType {
+    /// Get the `xsd` name of the type, as used by `xsi:type` and the `psf:DataType` property.
+    pub fn data_type(self) -> OwnedName {
+        let local_name = match self {
+            KeywordValueType::String => "string",
+            KeywordValueType::Integer => "integer",
+            KeywordValueType::Decimal => "decimal",
+            KeywordValueType::Boolean => "boolean",
+            KeywordValueType::QName => "QName",
+        };
+        OwnedName::qualified(local_name, super::document::NS_XSD, Some("xsd"))
+    }
+}
+
+/// Implement [`Keyword`] and the conversions from and to [`OwnedName`] for the given predefined name.
+macro_rules! impl_keyword {
+    ($name:ident) => {
+        impl Keyword for $name {
+            fn local_name(self) -> &'static str {
+                self.into()
+            }
+        }
+
+        impl From<$name> for OwnedName {
+            fn from(keyword: $name) -> Self {
+                keyword.name()
+            }
+        }
+
+        impl TryFrom<&OwnedName> for $name {
+            type Error = ();
+
+            fn try_from(name: &OwnedName) -> Result<Self, Self::Error> {
+                Self::from_name(name).ok_or(())
+            }
+        }
+    };
+}
+
+/// Define an enum of keywords, with the expected value type of each keyword,
+/// which is optional unless the enum is declared with `: KeywordValueType`.
+macro_rules! define_keywords {
+    (
+        $(#[$meta:meta])*
+        $name:ident {
+            $($variant:ident $(: $value_type:ident)?,)*
+        }
+    ) => {
+        define_keywords!(@enum $(#[$meta])* $name { $($variant,)* });
+
+        impl $name {
+            #[allow(dead_code)]
+            fn declared_value_type(self) -> Option<KeywordValueType> {
+                match self {
+                    $($name::$variant => None$(.or(Some(KeywordValueType::$value_type)))?,)*
+                }
+            }
+        }
+    };
+    (
+        $(#[$meta:meta])*
+        $name:ident: KeywordValueType {
+            $($variant:ident: $value_type:ident,)*
+        }
+    ) => {
+        define_keywords!(@enum $(#[$meta])* $name { $($variant,)* });
+
+        impl $name {
+            fn declared_value_type(self) -> KeywordValueType {
+                match self {
+                    $($name::$variant => KeywordValueType::$value_type,)*
+                }
+            }
+        }
+    };
+    (
+        @enum
+        $(#[$meta:meta])*
+        $name:ident {
+            $($variant:ident,)*
+        }
+    ) => {
+        define_predefined_name! {
+            namespace = NS_PSK;
+            #[derive(EnumString, IntoStaticStr, VariantArray, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+            #[allow(missing_docs)]
+            $(#[$meta])*
+            pub enum $name {
+                $($variant,)*
+            }
+        }
+
+        impl_keyword!($name);
+    };
+}
+
+define_keywords! {
+    /// Represents the features of the Print Schema Keywords.
+    FeatureKeyword {
+        DocumentBannerSheet,
+        DocumentBinding,
+        DocumentCollate,
+        DocumentCoverBack,
+        DocumentCoverFront,
+        DocumentDuplex,
+        DocumentHolePunch,
+        DocumentInputBin,
+        DocumentNUp,
+        DocumentOutputBin,
+        DocumentRollCut,
+        DocumentSeparatorSheet,
+        DocumentStaple,
+        JobAccountingSheet,
+        JobBindAllDocuments,
+        JobCollateAllDocuments,
+        JobDeviceLanguage,
+        JobDigitalSignatureProcessing,
+        JobDuplexAllDocumentsContiguously,
+        JobErrorSheet,
+        JobHolePunch,
+        JobInputBin,
+        JobNUpAllDocumentsContiguously,
+        JobOutputBin,
+        JobOutputOptimization,
+        JobPageOrder,
+        JobPrimaryBannerSheet,
+        JobPrimaryCoverBack,
+        JobPrimaryCoverFront,
+        JobRollCutAtEndOfJob,
+        JobStapleAllDocuments,
+        PageBlackGenerationProcessing,
+        PageBorderless,
+        PageColorManagement,
+        PageDestinationColorProfile,
+        PageDeviceColorSpaceUsage,
+        PageDeviceFontSubstitution,
+        PageForceFrontSide,
+        PageICMRenderingIntent,
+        PageInputBin,
+        PageMediaColor,
+        PageMediaSize,
+        PageMediaType,
+        PageMirrorImage,
+        PageNegativeImage,
+        PageOrientation,
+        PageOutputBin,
+        PageOutputColor,
+        PageOutputQuality,
+        PagePhotoPrintingIntent,
+        PagePoster,
+        PageResolution,
+        PageScaling,
+        PageTrueTypeFontMode,
+        PageWatermark,
+        PresentationDirection,
+    }
+}
+
+impl FeatureKeyword {
+    /// Get the scope of the feature, see [`PrintScope::of`].
+    ///
+    /// `None` is returned for sub-features, which take the scope of their parent feature.
+    pub fn scope(self) -> Option<PrintScope> {
+        PrintScope::of(&self.name())
+    }
+}
+
+define_keywords! {
+    /// Represents the parameters of the Print Schema Keywords.
+    ParameterKeyword: KeywordValueType {
+        DocumentBindingGutter: Integer,
+        DocumentCopiesAllPages: Integer,
+        JobBindAllDocumentsGutter: Integer,
+        JobCopiesAllDocuments: Integer,
+        PageCopies: Integer,
+        PageDestinationColorProfileURI: String,
+        PageDeviceColorSpaceProfileURI: String,
+        PageMediaSizeMediaSizeHeight: Integer,
+        PageMediaSizeMediaSizeWidth: Integer,
+        PageMediaSizePSHeight: Integer,
+        PageMediaSizePSHeightOffset: Integer,
+        PageMediaSizePSOrientation: String,
+        PageMediaSizePSWidth: Integer,
+        PageMediaSizePSWidthOffset: Integer,
+        PageScalingOffsetHeight: Integer,
+        PageScalingOffsetWidth: Integer,
+        PageScalingScale: Integer,
+        PageScalingScaleHeight: Integer,
+        PageScalingScaleWidth: Integer,
+        PageWatermarkOriginHeight: Integer,
+        PageWatermarkOriginWidth: Integer,
+        PageWatermarkSizeHeight: Integer,
+        PageWatermarkSizeWidth: Integer,
+        PageWatermarkTextAngle: Integer,
+        PageWatermarkTextColor: String,
+        PageWatermarkTextFontSize: Integer,
+        PageWatermarkTextText: String,
+        PageWatermarkTransparency: Integer,
+    }
+}
+
+impl ParameterKeyword {
+    /// Get the scope of the parameter, see [`PrintScope::of`].
+    pub fn scope(self) -> PrintScope {
+        PrintScope::of(&self.name()).unwrap_or(PrintScope::Job)
+    }
+
+    /// Get the expected type of the value of the parameter.
+    pub fn value_type(self) -> KeywordValueType {
+        self.declared_value_type()
+    }
+}
+
+define_keywords! {
+    /// Represents the properties and scored properties of the Print Schema Keywords.
+    PropertyKeyword {
+        Angle: Integer,
+        BackCoating: QName,
+        BindingGutter: Integer,
+        BinType: QName,
+        DeviceBitsPerPixel: Integer,
+        DisplayName: String,
+        DriverBitsPerPixel: Integer,
+        ExtentHeight: Integer,
+        ExtentWidth: Integer,
+        FeedType: QName,
+        FontColor: String,
+        FontSize: Integer,
+        FrontCoating: QName,
+        ImageableArea,
+        ImageableSizeHeight: Integer,
+        ImageableSizeWidth: Integer,
+        JobID: String,
+        JobName: String,
+        JobUserName: String,
+        Layering: QName,
+        Material: QName,
+        MediaSizeHeight: Integer,
+        MediaSizeWidth: Integer,
+        OffsetHeight: Integer,
+        OffsetWidth: Integer,
+        OriginHeight: Integer,
+        OriginWidth: Integer,
+        PageImageableSize,
+        PagesPerSheet: Integer,
+        QualitativeResolution: QName,
+        ResolutionX: Integer,
+        ResolutionY: Integer,
+        Scale: Integer,
+        ScaleHeight: Integer,
+        ScaleWidth: Integer,
+        SizeHeight: Integer,
+        SizeWidth: Integer,
+        Text: String,
+        Transparency: Integer,
+    }
+}
+
+impl PropertyKeyword {
+    /// Get the scope of the property, see [`PrintScope::of`].
+    ///
+    /// `None` is returned for properties of options and sub-properties.
+    pub fn scope(self) -> Option<PrintScope> {
+        PrintScope::of(&self.name())
+    }
+
+    /// Get the expected type of the value of the property.
+    ///
+    /// `None` is returned for properties which only contain other properties.
+    pub fn value_type(self) -> Option<KeywordValueType> {
+        self.declared_value_type()
+    }
+}
+
+define_keywords! {
+    /// Represents the options and the values of properties of the Print Schema Keywords.
+    ///
+    /// The options of [`FeatureKeyword::PageMediaSize`] are defined by [`PredefinedMediaName`].
+    OptionKeyword {
+        AbsoluteColorimetric,
+        Always,
+        Application,
+        ArchiveFormat,
+        Archival,
+        AutoSelect,
+        AutoSheetFeeder,
+        Automatic,
+        BackPrintFilm,
+        Banner,
+        BindBottom,
+        BindLeft,
+        BindRight,
+        BindTop,
+        Black,
+        BlankCover,
+        Blue,
+        Bond,
+        Booklet,
+        BothSheets,
+        BottomEdge,
+        BottomLeft,
+        BottomRight,
+        Borderless,
+        Brown,
+        BusinessGraphics,
+        CardStock,
+        Cassette,
+        Collated,
+        Color,
+        Conditional,
+        Continuous,
+        ContinuousFeed,
+        Custom,
+        CustomMediaSize,
+        CustomSquare,
+        CutImmediatelyAfterSpecifiedLength,
+        CutSheetAtImageEdge,
+        CutSheetAtStandardMediaSize,
+        Default,
+        Device,
+        DownloadAsNativeTrueTypeFont,
+        DownloadAsOutlineFont,
+        DownloadAsRasterFont,
+        Draft,
+        Driver,
+        DriverConfiguration,
+        EdgeStitchBottom,
+        EdgeStitchLeft,
+        EdgeStitchRight,
+        EdgeStitchTop,
+        EndSheet,
+        EnvelopePlain,
+        EnvelopeWindow,
+        Fabric,
+        Fax,
+        FitApplicationBleedSizeToPageImageableSize,
+        FitApplicationContentSizeToPageImageableSize,
+        FitApplicationMediaSizeToPageImageableSize,
+        FitApplicationMediaSizeToPageMediaSize,
+        Fold,
+        ForceFrontSide,
+        Glossy,
+        Gold,
+        GoldenRod,
+        Gray,
+        Grayscale,
+        Green,
+        High,
+        HighGloss,
+        HighResolution,
+        Ivory,
+        JogOffset,
+        Label,
+        Landscape,
+        LeftBottom,
+        LeftEdge,
+        LeftTop,
+        Manual,
+        MatchToDefault,
+        Matte,
+        MirrorImageHeight,
+        MirrorImageWidth,
+        Monochrome,
+        MultiLayerForm,
+        MultiPartForm,
+        Negative,
+        NoColor,
+        NoCover,
+        None,
+        Normal,
+        Off,
+        On,
+        OnError,
+        OneSided,
+        Optional,
+        Orange,
+        Overlay,
+        OverrideDeviceDefault,
+        PhotoBest,
+        PhotoDraft,
+        PhotoStandard,
+        Photographic,
+        PhotographicFilm,
+        PhotographicGlossy,
+        PhotographicHighGloss,
+        PhotographicMatte,
+        PhotographicSatin,
+        PhotographicSemiGloss,
+        Photographs,
+        PickMany,
+        PickOne,
+        Pink,
+        Plain,
+        Portrait,
+        PrintBack,
+        PrintBoth,
+        PrintFront,
+        PrintInvalidSignatures,
+        PrintInvalidSignaturesWithErrorReport,
+        PrintOnlyValidSignatures,
+        Red,
+        RelativeColorimetric,
+        RenderAsBitmap,
+        Reverse,
+        ReverseLandscape,
+        ReversePortrait,
+        RightBottom,
+        RightEdge,
+        RightTop,
+        SaddleStitch,
+        Satin,
+        Screen,
+        ScreenPaged,
+        SemiGloss,
+        SheetFeed,
+        Silver,
+        StapleBottomLeft,
+        StapleBottomRight,
+        StapleDualBottom,
+        StapleDualLeft,
+        StapleDualRight,
+        StapleDualTop,
+        StapleTopLeft,
+        StapleTopRight,
+        Standard,
+        StartSheet,
+        Stationery,
+        System,
+        TShirtTransfer,
+        TabStockFull,
+        TabStockPreCut,
+        Text,
+        TopEdge,
+        TopLeft,
+        TopRight,
+        Tractor,
+        Transparency,
+        Trim,
+        Turquoise,
+        Uncollated,
+        Unconditional,
+        Underlay,
+        Violet,
+        White,
+        Yellow,
+        TwoSidedLongEdge,
+        TwoSidedShortEdge,
+    }
+}
+
+impl_keyword!(PredefinedBinding);
+impl_keyword!(PredefinedDuplexType);
+impl_keyword!(PredefinedHolePunch);
+impl_keyword!(PredefinedInputBin);
+impl_keyword!(PredefinedMediaName);
+impl_keyword!(PredefinedMediaType);
+impl_keyword!(PredefinedPageOrientation);
+impl_keyword!(PredefinedPageOutputColor);
+impl_keyword!(PredefinedPageScaling);
+impl_keyword!(PredefinedPresentationDirection);
+impl_keyword!(PredefinedStaple);
+impl_keyword!(SelectionType);
+impl_keyword!(InputBinType);
+impl_keyword!(InputFeedType);
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        FeatureKeyword, Keyword, KeywordValueType, OptionKeyword, ParameterKeyword, PropertyKeyword,
+    };
+    use crate::ticket::test_fixtures::{
+        sample_capabilities_document, sample_device_capabilities_document, sample_ticket_document,
+    };
+    use crate::ticket::{
+        document::{visit::Visit, NS_PSK},
+        InputBinType, InputFeedType, PredefinedBinding, PredefinedDuplexType, PredefinedHolePunch,
+        PredefinedInputBin, PredefinedMediaName, PredefinedMediaType, PredefinedName,
+        PredefinedPageOrientation, PredefinedPageOutputColor, PredefinedPageScaling,
+        PredefinedPresentationDirection, PredefinedStaple, PrintScope, SelectionType,
+    };
+    use std::collections::BTreeSet;
+    use strum::VariantArray;
+    use xml::name::OwnedName;
+
+    fn round_trip<T: Keyword + VariantArray + PartialEq + std::fmt::Debug>() {
+        for keyword in T::VARIANTS {
+            let name = keyword.name();
+            assert_eq!(name.namespace_ref(), Some(NS_PSK));
+            assert_eq!(T::from_name(&name), Some(*keyword));
+        }
+    }
+
+    #[test]
+    fn convert_names() {
+        round_trip::<FeatureKeyword>();
+        round_trip::<ParameterKeyword>();
+        round_trip::<PropertyKeyword>();
+        round_trip::<OptionKeyword>();
+        round_trip::<PredefinedMediaName>();
+
+        let name = OwnedName::qualified("JobCopiesAllDocuments", NS_PSK, Some("other"));
+        assert_eq!(
+            ParameterKeyword::try_from(&name),
+            Ok(ParameterKeyword::JobCopiesAllDocuments)
+        );
+        assert_eq!(
+            ParameterKeyword::from_name(&OwnedName::local("JobCopiesAllDocuments")),
+            None
+        );
+        assert_eq!(
+            FeatureKeyword::from_name(&OwnedName::qualified("PageSize", NS_PSK, Some("psk"))),
+            None
+        );
+        assert_eq!(
+            OwnedName::from(PredefinedMediaName::ISOA4),
+            OwnedName::qualified("ISOA4", NS_PSK, Some("psk"))
+        );
+        assert_eq!(
+            OptionKeyword::from_name(&OwnedName::from(OptionKeyword::OneSided)),
+            Some(OptionKeyword::OneSided)
+        );
+    }
+
+    #[test]
+    fn scope_and_value_type() {
+        assert_eq!(
+            FeatureKeyword::DocumentCollate.scope(),
+            Some(PrintScope::Document)
+        );
+        assert_eq!(FeatureKeyword::PresentationDirection.scope(), None);
+        assert_eq!(
+            ParameterKeyword::JobCopiesAllDocuments.scope(),
+            PrintScope::Job
+        );
+        assert!(ParameterKeyword::VARIANTS
+            .iter()
+            .all(|x| PrintScope::of(&x.name()).is_some()));
+        assert_eq!(
+            ParameterKeyword::PageWatermarkTextText.value_type(),
+            KeywordValueType::String
+        );
+        assert_eq!(
+            PropertyKeyword::MediaSizeWidth.value_type(),
+            Some(KeywordValueType::Integer)
+        );
+        assert_eq!(PropertyKeyword::ImageableArea.value_type(), None);
+        assert_eq!(
+            PropertyKeyword::PageImageableSize.scope(),
+            Some(PrintScope::Page)
+        );
+        assert_eq!(KeywordValueType::QName.data_type().local_name, "QName");
+    }
+
+    #[test]
+    fn fixture_names_are_keywords() {
+        #[derive(Default)]
+        struct Unknown(BTreeSet<String>);
+        impl<'a> Visit<'a> for Unknown {
+            fn visit_name(&mut self, name: &'a OwnedName, _path: &mut Vec<&'a OwnedName>) {
+                if name.namespace_ref() != Some(NS_PSK) {
+                    return;
+                }
+                let known = FeatureKeyword::from_name(name).is_some()
+                    || ParameterKeyword::from_name(name).is_some()
+                    || PropertyKeyword::from_name(name).is_some()
+                    || OptionKeyword::from_name(name).is_some()
+                    || PredefinedMediaName::from_name(name).is_some();
+                if !known {
+                    self.0.insert(name.local_name.clone());
+                }
+            }
+        }
+
+        let mut unknown = Unknown::default();
+        unknown.visit_print_capabilities(&sample_capabilities_document());
+        unknown.visit_print_ticket(&sample_ticket_document());
+        let device_capabilities = sample_device_capabilities_document().to_print_capabilities();
+        unknown.visit_print_capabilities(&device_capabilities);
+        assert_eq!(unknown.0, BTreeSet::new());
+    }
+
+    #[test]
+    fn predefined_names_are_keywords() {
+        fn options<T: Keyword + VariantArray>() -> bool {
+            T::VARIANTS
+                .iter()
+                .all(|x| OptionKeyword::from_name(&x.name()).is_some())
+        }
+
+        assert!(options::<PredefinedBinding>());
+        assert!(options::<PredefinedDuplexType>());
+        assert!(options::<PredefinedHolePunch>());
+        assert!(options::<PredefinedInputBin>());
+        assert!(options::<PredefinedMediaType>());
+        assert!(options::<PredefinedPageOrientation>());
+        assert!(options::<PredefinedPageOutputColor>());
+        assert!(options::<PredefinedPageScaling>());
+        assert!(options::<PredefinedPresentationDirection>());
+        assert!(options::<PredefinedStaple>());
+        assert!(options::<InputBinType>());
+        assert!(options::<InputFeedType>());
+        assert!(options::<SelectionType>());
+        assert_eq!(
+            OptionKeyword::from_name(&SelectionType::PickMany.name()),
+            Some(OptionKeyword::PickMany)
+        );
+    }
+}
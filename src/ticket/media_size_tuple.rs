@@ -108,7 +108,7 @@ mod tests {
 
     #[test]
     fn test_is_roll() {
-        assert_eq!(MediaSizeTuple::mm(210, 297).is_roll(), false);
+        assert!(!MediaSizeTuple::mm(210, 297).is_roll());
         assert!(MediaSizeTuple::mm(210, 0).is_roll());
         assert!(MediaSizeTuple::mm(0, 297).is_roll());
     }
@@ -0,0 +1,604 @@
+use super::{
+    document::{
+        same_name, same_value, ParameterDef, ParameterInit, PrintCapabilitiesDocument,
+        PrintFeature, PrintFeatureOption, PrintTicketDocument, Property, PropertyValue,
+        ScoredProperty, WithProperties, NS_PSF,
+    },
+    SelectionType,
+};
+use xml::name::OwnedName;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Represents a change made to a print ticket while validating it against print capabilities.
+pub enum MergeChange {
+    /// The feature is not supported by the device, so it was removed.
+    FeatureRemoved {
+        /// Names of the feature and its parent features, from the outermost one.
+        path: Vec<OwnedName>,
+    },
+    /// The requested option is not supported by the device, so it was replaced.
+    OptionReplaced {
+        /// Names of the feature and its parent features, from the outermost one.
+        path: Vec<OwnedName>,
+        /// The option requested by the ticket.
+        requested: PrintFeatureOption,
+        /// The option selected instead.
+        replacement: PrintFeatureOption,
+    },
+    /// The option is not supported by the device, or the feature accepts only one option, so it was removed.
+    OptionRemoved {
+        /// Names of the feature and its parent features, from the outermost one.
+        path: Vec<OwnedName>,
+        /// The removed option.
+        option: PrintFeatureOption,
+    },
+    /// The parameter is not defined by the device, or its value is invalid, so it was removed.
+    ParameterRemoved {
+        /// The name of the parameter.
+        name: OwnedName,
+        /// The removed value.
+        value: PropertyValue,
+    },
+    /// The parameter value is out of the range defined by the device, so it was adjusted.
+    ParameterAdjusted {
+        /// The name of the parameter.
+        name: OwnedName,
+        /// The value requested by the ticket.
+        requested: PropertyValue,
+        /// The value used instead.
+        adjusted: PropertyValue,
+    },
+    /// The parameter is required by a selected option but not initialized, so its default value was used.
+    ParameterDefaulted {
+        /// The name of the parameter.
+        name: OwnedName,
+        /// The default value.
+        value: PropertyValue,
+    },
+}
+
+#[derive(Clone, Debug)]
+/// Represents the result of [`merge_and_validate`].
+pub struct MergedPrintTicket {
+    /// The merged and validated print ticket.
+    pub document: PrintTicketDocument,
+    /// Changes made while validating the merged print ticket.
+    pub changes: Vec<MergeChange>,
+}
+
+/// Merge the delta print ticket into the base print ticket, without validation.
+///
+/// Features, properties and parameter initializations of the delta take precedence over the ones of the base with the same name.
+/// Options of a feature are replaced as a whole, while sub-features are merged recursively.
+pub fn merge_print_tickets(
+    base: &PrintTicketDocument,
+    delta: &PrintTicketDocument,
+) -> PrintTicketDocument {
+    PrintTicketDocument {
+        extra: base.extra.clone(),
+        ..PrintTicketDocument::new(
+            merge_properties(&base.properties, &delta.properties),
+            merge_parameter_inits(&base.parameter_inits, &delta.parameter_inits),
+            merge_features(&base.features, &delta.features),
+        )
+    }
+}
+
+/// Merge the delta print ticket into the base print ticket, and validate the result against the given print capabilities.
+///
+/// This is a pure-Rust emulation of [`PTMergeAndValidatePrintTicket`], which works without a printer device:
+/// - Features that are not supported by the device are removed.
+/// - Options that are not supported by the device are replaced by the option selected in the base ticket,
+///   the identity option, or the first option of the feature, in that order.
+/// - Parameters that are not defined by the device are removed, and out-of-range values are adjusted.
+/// - Parameters required by the selected options are initialized with their default values.
+///
+/// [`PTMergeAndValidatePrintTicket`]: https://learn.microsoft.com/en-us/windows/win32/api/prntvpt/nf-prntvpt-ptmergeandvalidateprintticket
+pub fn merge_and_validate(
+    base: &PrintTicketDocument,
+    delta: &PrintTicketDocument,
+    capabilities: &PrintCapabilitiesDocument,
+) -> MergedPrintTicket {
+    let merged = merge_print_tickets(base, delta);
+    let mut changes = vec![];
+
+    let features = validate_features(
+        merged.features,
+        &capabilities.features,
+        &base.features,
+        &mut vec![],
+        &mut changes,
+    );
+
+    let mut parameter_inits = vec![];
+    for parameter_init in merged.parameter_inits {
+        let Some(def) = capabilities
+            .parameter_defs
+            .iter()
+            .find(|x| same_name(&x.name, &parameter_init.name))
+        else {
+            changes.push(MergeChange::ParameterRemoved {
+                name: parameter_init.name,
+                value: parameter_init.value,
+            });
+            continue;
+        };
+        match validate_parameter_value(def, &parameter_init.value) {
+            Some(adjusted) if same_value(&adjusted, &parameter_init.value) => {
+                parameter_inits.push(parameter_init);
+            }
+            Some(adjusted) => {
+                changes.push(MergeChange::ParameterAdjusted {
+                    name: parameter_init.name.clone(),
+                    requested: parameter_init.value.clone(),
+                    adjusted: adjusted.clone(),
+                });
+                parameter_inits.push(ParameterInit {
+                    value: adjusted,
+                    ..parameter_init
+                });
+            }
+            None => {
+                changes.push(MergeChange::ParameterRemoved {
+                    name: parameter_init.name,
+                    value: parameter_init.value,
+                });
+            }
+        }
+    }
+
+    let mut dependent = vec![];
+    collect_parameters_dependent(&features, &mut dependent);
+    for name in dependent {
+        if parameter_inits.iter().any(|x| same_name(&x.name, &name)) {
+            continue;
+        }
+        let default_value = capabilities
+            .parameter_defs
+            .iter()
+            .find(|x| same_name(&x.name, &name))
+            .and_then(|x| x.default_value());
+        if let Some(value) = default_value {
+            changes.push(MergeChange::ParameterDefaulted {
+                name: name.clone(),
+                value: value.clone(),
+            });
+            parameter_inits.push(ParameterInit::new(name, value.clone()));
+        }
+    }
+
+    MergedPrintTicket {
+        document: PrintTicketDocument {
+            properties: merged.properties,
+            parameter_inits,
+            features,
+            extra: merged.extra,
+        },
+        changes,
+    }
+}
+
+fn merge_properties(base: &[Property], delta: &[Property]) -> Vec<Property> {
+    let mut result = base.to_vec();
+    for property in delta {
+        match result
+            .iter_mut()
+            .find(|x| same_name(&x.name, &property.name))
+        {
+            Some(existing) => *existing = property.clone(),
+            None => result.push(property.clone()),
+        }
+    }
+    result
+}
+
+fn merge_parameter_inits(base: &[ParameterInit], delta: &[ParameterInit]) -> Vec<ParameterInit> {
+    let mut result = base.to_vec();
+    for parameter_init in delta {
+        match result
+            .iter_mut()
+            .find(|x| same_name(&x.name, &parameter_init.name))
+        {
+            Some(existing) => *existing = parameter_init.clone(),
+            None => result.push(parameter_init.clone()),
+        }
+    }
+    result
+}
+
+fn merge_features(base: &[PrintFeature], delta: &[PrintFeature]) -> Vec<PrintFeature> {
+    let mut result = base.to_vec();
+    for feature in delta {
+        match result
+            .iter_mut()
+            .find(|x| same_name(&x.name, &feature.name))
+        {
+            Some(existing) => {
+                if !feature.options.is_empty() {
+                    existing.options = feature.options.clone();
+                }
+                existing.properties = merge_properties(&existing.properties, &feature.properties);
+                existing.features = merge_features(&existing.features, &feature.features);
+            }
+            None => result.push(feature.clone()),
+        }
+    }
+    result
+}
+
+fn validate_features(
+    features: Vec<PrintFeature>,
+    capabilities: &[PrintFeature],
+    base: &[PrintFeature],
+    path: &mut Vec<OwnedName>,
+    changes: &mut Vec<MergeChange>,
+) -> Vec<PrintFeature> {
+    let mut result = vec![];
+    for feature in features {
+        path.push(feature.name.clone());
+        match capabilities
+            .iter()
+            .find(|x| same_name(&x.name, &feature.name))
+        {
+            Some(capability) => {
+                let base_feature = base.iter().find(|x| same_name(&x.name, &feature.name));
+                let options =
+                    validate_options(feature.options, capability, base_feature, path, changes);
+                let features = validate_features(
+                    feature.features,
+                    &capability.features,
+                    base_feature.map_or(&[], |x| x.features.as_slice()),
+                    path,
+                    changes,
+                );
+                result.push(PrintFeature {
+                    options,
+                    features,
+                    ..feature
+                });
+            }
+            None => changes.push(MergeChange::FeatureRemoved { path: path.clone() }),
+        }
+        path.pop();
+    }
+    result
+}
+
+fn validate_options(
+    requested: Vec<PrintFeatureOption>,
+    capability: &PrintFeature,
+    base: Option<&PrintFeature>,
+    path: &[OwnedName],
+    changes: &mut Vec<MergeChange>,
+) -> Vec<PrintFeatureOption> {
+    let pick_many = is_pick_many(capability);
+
+    let (mut accepted, rejected): (Vec<_>, Vec<_>) = requested
+        .into_iter()
+        .partition(|x| is_option_supported(x, capability));
+    if !pick_many && accepted.len() > 1 {
+        for option in accepted.split_off(1) {
+            changes.push(MergeChange::OptionRemoved {
+                path: path.to_vec(),
+                option,
+            });
+        }
+    }
+
+    let mut rejected = rejected.into_iter();
+    if accepted.is_empty() {
+        if let Some(requested) = rejected.next() {
+            match default_option(capability, base) {
+                Some(replacement) => {
+                    changes.push(MergeChange::OptionReplaced {
+                        path: path.to_vec(),
+                        requested,
+                        replacement: replacement.clone(),
+                    });
+                    accepted.push(replacement);
+                }
+                None => changes.push(MergeChange::OptionRemoved {
+                    path: path.to_vec(),
+                    option: requested,
+                }),
+            }
+        }
+    }
+    for option in rejected {
+        changes.push(MergeChange::OptionRemoved {
+            path: path.to_vec(),
+            option,
+        });
+    }
+    accepted
+}
+
+/// Check whether the feature of print capabilities accepts more than one option.
+pub(crate) fn is_pick_many(capability: &PrintFeature) -> bool {
+    SelectionType::of(capability) == SelectionType::PickMany
+}
+
+pub(crate) fn is_option_supported(option: &PrintFeatureOption, capability: &PrintFeature) -> bool {
+    capability.options.iter().any(|x| option_matches(option, x))
+}
+
+fn option_matches(requested: &PrintFeatureOption, supported: &PrintFeatureOption) -> bool {
+    match (&requested.name, &supported.name) {
+        (Some(a), Some(b)) => same_name(a, b),
+        (None, None) => requested.scored_properties.iter().all(|x| {
+            supported
+                .scored_properties
+                .iter()
+                .any(|y| scored_property_matches(x, y))
+        }),
+        _ => false,
+    }
+}
+
+fn scored_property_matches(requested: &ScoredProperty, supported: &ScoredProperty) -> bool {
+    let name_matches = match (&requested.name, &supported.name) {
+        (Some(a), Some(b)) => same_name(a, b),
+        (None, None) => true,
+        _ => false,
+    };
+    if !name_matches {
+        return false;
+    }
+    // the value of a parameterized scored-property is validated along with the parameter
+    if supported.parameter_ref.is_some() {
+        return true;
+    }
+    let value_matches = match (&requested.value, &supported.value) {
+        (Some(a), Some(b)) => same_value(a, b),
+        (None, None) => true,
+        _ => false,
+    };
+    value_matches
+        && requested.scored_properties.iter().all(|x| {
+            supported
+                .scored_properties
+                .iter()
+                .any(|y| scored_property_matches(x, y))
+        })
+}
+
+fn default_option(
+    capability: &PrintFeature,
+    base: Option<&PrintFeature>,
+) -> Option<PrintFeatureOption> {
+    if let Some(option) = base
+        .into_iter()
+        .flat_map(|x| x.options.iter())
+        .find(|x| is_option_supported(x, capability))
+    {
+        return Some(option.clone());
+    }
+    let is_identity = |option: &&PrintFeatureOption| {
+        option
+            .get_property("IdentityOption", Some(NS_PSF))
+            .and_then(|x| x.value.as_ref())
+            .and_then(|x| x.string())
+            .is_some_and(|x| x.trim().eq_ignore_ascii_case("true"))
+    };
+    capability
+        .options
+        .iter()
+        .find(is_identity)
+        .or_else(|| capability.options.first())
+        .map(|x| PrintFeatureOption::new(x.name.clone(), x.scored_properties.clone(), vec![]))
+}
+
+/// Validate the value against the parameter definition.
+/// Return the (maybe adjusted) value, or `None` if the value cannot be used.
+pub(crate) fn validate_parameter_value(
+    def: &ParameterDef,
+    value: &PropertyValue,
+) -> Option<PropertyValue> {
+    def.definition().adjust_value(value)
+}
+
+fn collect_parameters_dependent(features: &[PrintFeature], result: &mut Vec<OwnedName>) {
+    for feature in features {
+        for option in &feature.options {
+            for name in option.parameters_dependent() {
+                if !result.iter().any(|x| same_name(x, &name)) {
+                    result.push(name);
+                }
+            }
+        }
+        collect_parameters_dependent(&feature.features, result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{merge_and_validate, merge_print_tickets, MergeChange};
+    use crate::ticket::document::{
+        reader::ParsableXmlDocument, PrintTicketDocument, PropertyValue, NS_PSK,
+    };
+    use crate::ticket::test_fixtures::sample_capabilities_document;
+    use xml::name::OwnedName;
+
+    fn ticket(body: &str) -> PrintTicketDocument {
+        let xml = format!(
+            r#"<psf:PrintTicket version="1"
+    xmlns:psf="http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework"
+    xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+    xmlns:xsd="http://www.w3.org/2001/XMLSchema"
+    xmlns:psk="http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords"
+    xmlns:ns0000="http://schemas.microsoft.com/windows/printing/oemdriverpt/Generic___Text_Only_10_0_22621_3235_">
+    {}
+</psf:PrintTicket>"#,
+            body
+        );
+        PrintTicketDocument::parse_from_bytes(xml).unwrap()
+    }
+
+    fn selected_option(document: &PrintTicketDocument, feature: &str) -> Option<OwnedName> {
+        document
+            .features
+            .iter()
+            .find(|x| x.name.local_name == feature)
+            .and_then(|x| x.options.first())
+            .and_then(|x| x.name.clone())
+    }
+
+    #[test]
+    fn merge_without_validation() {
+        let base = ticket(
+            r#"<psf:Feature name="psk:PageOrientation"><psf:Option name="psk:Portrait" /></psf:Feature>
+    <psf:Feature name="psk:PageMediaSize"><psf:Option name="psk:ISOA3" /></psf:Feature>"#,
+        );
+        let delta = ticket(
+            r#"<psf:Feature name="psk:PageMediaSize"><psf:Option name="psk:ISOA4" /></psf:Feature>"#,
+        );
+        let merged = merge_print_tickets(&base, &delta);
+        assert_eq!(merged.features.len(), 2);
+        assert_eq!(
+            selected_option(&merged, "PageMediaSize").map(|x| x.local_name),
+            Some("ISOA4".to_string())
+        );
+        assert_eq!(
+            selected_option(&merged, "PageOrientation").map(|x| x.local_name),
+            Some("Portrait".to_string())
+        );
+    }
+
+    #[test]
+    fn supported_option_is_kept() {
+        let base = ticket("");
+        let delta = ticket(
+            r#"<psf:Feature name="psk:PageMediaSize"><psf:Option name="psk:ISOA4" /></psf:Feature>"#,
+        );
+        let result = merge_and_validate(&base, &delta, &sample_capabilities_document());
+        assert!(result.changes.is_empty(), "{:#?}", result.changes);
+        assert_eq!(
+            selected_option(&result.document, "PageMediaSize").map(|x| x.local_name),
+            Some("ISOA4".to_string())
+        );
+    }
+
+    #[test]
+    fn unsupported_option_is_replaced_by_base_option() {
+        let base = ticket(
+            r#"<psf:Feature name="psk:PageMediaSize"><psf:Option name="psk:ISOA3" /></psf:Feature>"#,
+        );
+        let delta = ticket(
+            r#"<psf:Feature name="psk:PageMediaSize"><psf:Option name="psk:ISOA0" /></psf:Feature>"#,
+        );
+        let result = merge_and_validate(&base, &delta, &sample_capabilities_document());
+        assert_eq!(
+            selected_option(&result.document, "PageMediaSize").map(|x| x.local_name),
+            Some("ISOA3".to_string())
+        );
+        assert!(matches!(
+            result.changes.as_slice(),
+            [MergeChange::OptionReplaced { requested, .. }]
+                if requested.name.as_ref().map(|x| x.local_name.as_str()) == Some("ISOA0")
+        ));
+    }
+
+    #[test]
+    fn unsupported_option_is_replaced_by_identity_option() {
+        let base = ticket("");
+        let delta = ticket(
+            r#"<psf:Feature name="psk:JobNUpAllDocumentsContiguously">
+        <psf:Option>
+            <psf:ScoredProperty name="psk:PagesPerSheet">
+                <psf:Value xsi:type="xsd:integer">3</psf:Value>
+            </psf:ScoredProperty>
+        </psf:Option>
+    </psf:Feature>"#,
+        );
+        let result = merge_and_validate(&base, &delta, &sample_capabilities_document());
+        let MergeChange::OptionReplaced { replacement, .. } = &result.changes[0] else {
+            panic!("unexpected changes: {:#?}", result.changes);
+        };
+        assert_eq!(
+            replacement.scored_properties[0].value,
+            Some(PropertyValue::Integer(1))
+        );
+    }
+
+    #[test]
+    fn unnamed_option_is_matched_by_scored_properties() {
+        let base = ticket("");
+        let delta = ticket(
+            r#"<psf:Feature name="psk:JobNUpAllDocumentsContiguously">
+        <psf:Option>
+            <psf:ScoredProperty name="psk:PagesPerSheet">
+                <psf:Value xsi:type="xsd:integer">4</psf:Value>
+            </psf:ScoredProperty>
+        </psf:Option>
+    </psf:Feature>"#,
+        );
+        let result = merge_and_validate(&base, &delta, &sample_capabilities_document());
+        assert!(result.changes.is_empty(), "{:#?}", result.changes);
+    }
+
+    #[test]
+    fn unsupported_feature_is_removed() {
+        let base = ticket("");
+        let delta = ticket(
+            r#"<psf:Feature name="psk:JobStapleAllDocuments"><psf:Option name="psk:StapleTopLeft" /></psf:Feature>"#,
+        );
+        let result = merge_and_validate(&base, &delta, &sample_capabilities_document());
+        assert!(result.document.features.is_empty());
+        assert_eq!(
+            result.changes,
+            vec![MergeChange::FeatureRemoved {
+                path: vec![OwnedName::qualified(
+                    "JobStapleAllDocuments",
+                    NS_PSK,
+                    Some("psk")
+                )]
+            }]
+        );
+    }
+
+    #[test]
+    fn parameters_are_validated() {
+        let base = ticket("");
+        let delta = ticket(
+            r#"<psf:ParameterInit name="psk:JobCopiesAllDocuments">
+        <psf:Value xsi:type="xsd:integer">10000</psf:Value>
+    </psf:ParameterInit>
+    <psf:ParameterInit name="psk:UnknownParameter">
+        <psf:Value xsi:type="xsd:integer">1</psf:Value>
+    </psf:ParameterInit>
+    <psf:Feature name="psk:PageMediaSize">
+        <psf:Option name="psk:CustomMediaSize">
+            <psf:ScoredProperty name="psk:MediaSizeWidth">
+                <psf:ParameterRef name="psk:PageMediaSizeMediaSizeWidth" />
+            </psf:ScoredProperty>
+            <psf:ScoredProperty name="psk:MediaSizeHeight">
+                <psf:ParameterRef name="psk:PageMediaSizeMediaSizeHeight" />
+            </psf:ScoredProperty>
+        </psf:Option>
+    </psf:Feature>"#,
+        );
+        let result = merge_and_validate(&base, &delta, &sample_capabilities_document());
+        let value_of = |name: &str| {
+            result
+                .document
+                .parameter_inits
+                .iter()
+                .find(|x| x.name.local_name == name)
+                .map(|x| x.value.clone())
+        };
+        assert_eq!(
+            value_of("JobCopiesAllDocuments"),
+            Some(PropertyValue::Integer(9999))
+        );
+        assert_eq!(value_of("UnknownParameter"), None);
+        assert_eq!(
+            value_of("PageMediaSizeMediaSizeWidth"),
+            Some(PropertyValue::Integer(2540))
+        );
+        assert_eq!(
+            value_of("PageMediaSizeMediaSizeHeight"),
+            Some(PropertyValue::Integer(4233))
+        );
+        assert_eq!(result.changes.len(), 4, "{:#?}", result.changes);
+    }
+}
@@ -1,38 +1,84 @@
+mod any_feature;
+mod binding;
+#[cfg(windows)]
 mod builder;
 mod copies;
 /// Document object model representation of print schema.
 pub mod document;
 mod feature_option_pack;
+mod hole_punch;
+mod input_bin;
 mod job_duplex;
+mod keyword;
 mod media_size_tuple;
+mod merge;
+mod n_up;
+mod option_matcher;
+mod output_bin;
 mod page_imageable_size;
 mod page_media_size;
+mod page_media_type;
 mod page_orientation;
 mod page_output_color;
 mod page_resolution;
+mod page_scaling;
+mod predefined_binding;
 mod predefined_duplex_type;
+mod predefined_hole_punch;
+mod predefined_input_bin;
 mod predefined_media_name;
+mod predefined_media_type;
 mod predefined_page_orientation;
 mod predefined_page_output_color;
+mod predefined_page_scaling;
+mod predefined_presentation_direction;
+mod predefined_staple;
 mod print_capabilities;
 mod print_ticket;
+mod scope;
+mod staple;
+#[cfg(test)]
+mod test_fixtures;
+mod validation;
 
+pub use any_feature::*;
+pub use binding::*;
+#[cfg(windows)]
 pub use builder::*;
 pub use copies::*;
 pub use feature_option_pack::*;
+pub use hole_punch::*;
+pub use input_bin::*;
 pub use job_duplex::*;
+pub use keyword::*;
 pub use media_size_tuple::*;
+pub use merge::*;
+pub use n_up::*;
+pub use option_matcher::*;
+pub use output_bin::*;
 pub use page_imageable_size::*;
 pub use page_media_size::*;
+pub use page_media_type::*;
 pub use page_orientation::*;
 pub use page_output_color::*;
 pub use page_resolution::*;
+pub use page_scaling::*;
+pub use predefined_binding::*;
 pub use predefined_duplex_type::*;
+pub use predefined_hole_punch::*;
+pub use predefined_input_bin::*;
 pub use predefined_media_name::*;
+pub use predefined_media_type::*;
 pub use predefined_page_orientation::*;
 pub use predefined_page_output_color::*;
+pub use predefined_page_scaling::*;
+pub use predefined_presentation_direction::*;
+pub use predefined_staple::*;
 pub use print_capabilities::*;
 pub use print_ticket::*;
+pub use scope::*;
+pub use staple::*;
+pub use validation::*;
 
 /// The default print ticket XML.
 pub const DEFAULT_PRINT_TICKET_XML: &str = r#"<psf:PrintTicket xmlns:psf="http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xmlns:xsd="http://www.w3.org/2001/XMLSchema" version="1" xmlns:psk="http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords"></psf:PrintTicket>"#;
@@ -0,0 +1,127 @@
+use super::{
+    define_feature_option_pack,
+    document::{same_name, PrintFeatureOption, WithScoredProperties, NS_PSK},
+    AnyFeature, AnyOption, FeatureKeyword, FeatureOptionPack, Keyword, PredefinedName,
+    PredefinedPresentationDirection, PrintCapabilities, PrintTicket,
+};
+use xml::name::OwnedName;
+
+define_feature_option_pack!(
+    FeatureKeyword::JobNUpAllDocumentsContiguously.name(),
+    JobNUp
+);
+define_feature_option_pack!(FeatureKeyword::DocumentNUp.name(), DocumentNUp);
+
+#[derive(Clone, Debug)]
+/// Represents an option of the `PresentationDirection` sub-feature of [`JobNUp`] or [`DocumentNUp`].
+///
+/// It is converted into a [`PrintTicket`] which selects the option under its N-up feature.
+pub struct PresentationDirection(AnyOption);
+
+impl PresentationDirection {
+    /// Get the DOM of the option.
+    pub fn option(&self) -> &PrintFeatureOption {
+        self.0.option()
+    }
+
+    /// Get the names of the N-up feature and the `PresentationDirection` sub-feature.
+    pub fn feature_path(&self) -> &[OwnedName] {
+        self.0.feature_path()
+    }
+
+    /// Get display name of the option.
+    pub fn display_name(&self) -> Option<&str> {
+        self.0.display_name()
+    }
+
+    /// Get the predefined name of the option.
+    /// If the option is not predefined, `None` is returned.
+    pub fn as_predefined_name(&self) -> Option<PredefinedPresentationDirection> {
+        self.option()
+            .name
+            .as_ref()
+            .and_then(PredefinedPresentationDirection::from_name)
+    }
+}
+
+impl From<PresentationDirection> for PrintTicket {
+    fn from(value: PresentationDirection) -> Self {
+        value.0.into()
+    }
+}
+
+fn presentation_directions(
+    capabilities: &PrintCapabilities,
+    feature_name: OwnedName,
+) -> impl Iterator<Item = PresentationDirection> {
+    let sub_feature_name = FeatureKeyword::PresentationDirection.name();
+    AnyFeature::list(capabilities)
+        .filter(|x| same_name(x.name(), &feature_name))
+        .flat_map(|x| x.features().collect::<Vec<_>>())
+        .filter(|x| same_name(x.name(), &sub_feature_name))
+        .flat_map(|x| x.options().collect::<Vec<_>>())
+        .map(PresentationDirection)
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
+macro_rules! impl_n_up {
+    ($name:ident) => {
+        impl $name {
+            /// Get the number of pages printed on each sheet.
+            pub fn pages_per_sheet(&self) -> Option<u32> {
+                self.option
+                    .get_scored_property("PagesPerSheet", Some(NS_PSK))
+                    .and_then(|x| x.value_with(&self.parameters))
+                    .and_then(|x| x.integer())
+                    .and_then(|x| u32::try_from(x).ok())
+            }
+
+            /// List the options of the `PresentationDirection` sub-feature defined in the capabilities.
+            pub fn presentation_directions(
+                capabilities: &PrintCapabilities,
+            ) -> impl Iterator<Item = PresentationDirection> {
+                presentation_directions(capabilities, Self::feature_name())
+            }
+        }
+    };
+}
+
+impl_n_up!(JobNUp);
+impl_n_up!(DocumentNUp);
+
+#[cfg(test)]
+mod tests {
+    use crate::ticket::test_fixtures::sample_capabilities;
+    use crate::ticket::{
+        document::{reader::ParsableXmlDocument, PrintTicketDocument},
+        DocumentNUp, JobNUp, PredefinedPresentationDirection, PrintTicket,
+    };
+
+    #[test]
+    fn list_n_up_with_presentation_directions() {
+        let capabilities = sample_capabilities();
+        let pages = capabilities
+            .job_n_ups()
+            .filter_map(|x| x.pages_per_sheet())
+            .collect::<Vec<_>>();
+        assert_eq!(&pages[..2], &[1, 2]);
+        assert_eq!(capabilities.document_n_ups().count(), 0);
+
+        let directions = JobNUp::presentation_directions(&capabilities).collect::<Vec<_>>();
+        assert_eq!(
+            directions[0].as_predefined_name(),
+            Some(PredefinedPresentationDirection::RightBottom)
+        );
+        assert_eq!(
+            DocumentNUp::presentation_directions(&capabilities).count(),
+            0
+        );
+
+        let ticket = PrintTicket::from(directions[0].clone());
+        let document = PrintTicketDocument::parse_from_bytes(ticket.xml).unwrap();
+        let n_up = &document.features[0];
+        assert_eq!(n_up.name.local_name, "JobNUpAllDocumentsContiguously");
+        assert_eq!(n_up.features[0].name.local_name, "PresentationDirection");
+    }
+}
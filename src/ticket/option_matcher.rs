@@ -0,0 +1,365 @@
+use super::{
+    document::{
+        same_name, same_value, ParameterInit, PrintFeatureOption, PropertyValue, ScoredProperty,
+    },
+    merge::validate_parameter_value,
+    FeatureOptionPack, PrintCapabilities,
+};
+use xml::name::OwnedName;
+
+#[derive(Clone, Debug, PartialEq)]
+/// Represents a device-supported option matched against a requested option.
+pub struct OptionMatch {
+    /// The device-supported option.
+    pub option: PrintFeatureOption,
+    /// The parameters that is used by the option.
+    ///
+    /// For parameterized scored-properties, the parameters are initialized with the requested values where possible.
+    pub parameters: Vec<ParameterInit>,
+    /// How closely the option matches the requested one, from `0.0` (nothing in common) to `1.0` (exact match).
+    pub score: f64,
+}
+
+impl PrintCapabilities {
+    /// Rank the options of the given feature by how closely they match the requested option.
+    ///
+    /// Options with the same name as the requested one are exact matches.
+    /// Otherwise, the values of the scored-properties are compared, with parameter references resolved through `parameters`.
+    /// The best match comes first.
+    pub fn rank_options_for_feature(
+        &self,
+        feature_name: OwnedName,
+        requested: &PrintFeatureOption,
+        parameters: &[ParameterInit],
+    ) -> Vec<OptionMatch> {
+        let mut result = self
+            .options_for_feature(feature_name)
+            .map(|option| self.match_option(requested, parameters, option))
+            .collect::<Vec<_>>();
+        // prefer fixed options to parameterized ones on ties,
+        // and keep the device order otherwise as the sort is stable
+        result.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.parameters.len().cmp(&b.parameters.len()))
+        });
+        result
+    }
+
+    /// Find the option of the given feature that matches the requested option best.
+    ///
+    /// See [`PrintCapabilities::rank_options_for_feature`] for details.
+    pub fn best_option_for_feature(
+        &self,
+        feature_name: OwnedName,
+        requested: &PrintFeatureOption,
+        parameters: &[ParameterInit],
+    ) -> Option<OptionMatch> {
+        self.rank_options_for_feature(feature_name, requested, parameters)
+            .into_iter()
+            .next()
+    }
+
+    /// Find the device-supported option that matches the given option pack best, typically one taken from another device.
+    ///
+    /// Return the option pack for this device along with its score. See [`OptionMatch::score`] for the meaning of the score.
+    pub fn closest_option<T: FeatureOptionPack>(&self, requested: &T) -> Option<(T, f64)> {
+        self.best_option_for_feature(
+            T::feature_name(),
+            requested.option(),
+            requested.parameters(),
+        )
+        .map(|x| (T::new(x.option, x.parameters), x.score))
+    }
+
+    fn match_option(
+        &self,
+        requested: &PrintFeatureOption,
+        requested_parameters: &[ParameterInit],
+        option: &PrintFeatureOption,
+    ) -> OptionMatch {
+        let mut parameters = self
+            .default_parameters_for(option.parameters_dependent().as_slice())
+            .collect::<Vec<_>>();
+        let score = match (&requested.name, &option.name) {
+            (Some(a), Some(b)) if same_name(a, b) => 1.0,
+            _ => self.score_scored_properties(
+                &requested.scored_properties,
+                requested_parameters,
+                &option.scored_properties,
+                &mut parameters,
+            ),
+        };
+        OptionMatch {
+            option: option.clone(),
+            parameters,
+            score,
+        }
+    }
+
+    fn score_scored_properties(
+        &self,
+        requested: &[ScoredProperty],
+        requested_parameters: &[ParameterInit],
+        supported: &[ScoredProperty],
+        parameters: &mut Vec<ParameterInit>,
+    ) -> f64 {
+        if requested.is_empty() {
+            return 0.0;
+        }
+        let total = requested
+            .iter()
+            .map(|x| {
+                supported
+                    .iter()
+                    .find(|y| match (&x.name, &y.name) {
+                        (Some(a), Some(b)) => same_name(a, b),
+                        _ => false,
+                    })
+                    .map_or(0.0, |y| {
+                        self.score_scored_property(x, requested_parameters, y, parameters)
+                    })
+            })
+            .sum::<f64>();
+        total / requested.len() as f64
+    }
+
+    fn score_scored_property(
+        &self,
+        requested: &ScoredProperty,
+        requested_parameters: &[ParameterInit],
+        supported: &ScoredProperty,
+        parameters: &mut Vec<ParameterInit>,
+    ) -> f64 {
+        let requested_value = requested.value_with(requested_parameters);
+        // a requested value that is missing, or references a parameter that is not given, matches nothing
+        let value_score = match (requested_value, &supported.parameter_ref) {
+            (Some(requested_value), Some(parameter_ref)) => {
+                // the device accepts a range of values, so try to use the requested one
+                let adjusted = self
+                    .document
+                    .parameter_defs
+                    .iter()
+                    .find(|x| same_name(&x.name, parameter_ref))
+                    .and_then(|x| validate_parameter_value(x, requested_value));
+                match adjusted {
+                    Some(adjusted) => {
+                        let score = value_similarity(requested_value, &adjusted);
+                        match parameters
+                            .iter_mut()
+                            .find(|x| same_name(&x.name, parameter_ref))
+                        {
+                            Some(existing) => existing.value = adjusted,
+                            None => {
+                                parameters.push(ParameterInit::new(parameter_ref.clone(), adjusted))
+                            }
+                        }
+                        score
+                    }
+                    None => 0.0,
+                }
+            }
+            (Some(requested_value), None) => supported
+                .value_with(parameters)
+                .map_or(0.0, |x| value_similarity(requested_value, x)),
+            (None, _) => 0.0,
+        };
+        if requested.scored_properties.is_empty() {
+            value_score
+        } else {
+            let sub_score = self.score_scored_properties(
+                &requested.scored_properties,
+                requested_parameters,
+                &supported.scored_properties,
+                parameters,
+            );
+            if requested.value.is_none() && requested.parameter_ref.is_none() {
+                // only the sub-scored-properties carry values
+                sub_score
+            } else {
+                (value_score + sub_score) / 2.0
+            }
+        }
+    }
+}
+
+/// Get the value of a number.
+fn numeric_value(value: &PropertyValue) -> Option<f64> {
+    match value {
+        PropertyValue::Integer(i) => Some(f64::from(*i)),
+        PropertyValue::BigInteger(x) | PropertyValue::Decimal(x) => x.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Compare two values. Numbers are compared by their relative difference, other values must be equal.
+fn value_similarity(a: &PropertyValue, b: &PropertyValue) -> f64 {
+    match (numeric_value(a), numeric_value(b)) {
+        (Some(a), Some(b)) => {
+            let max = a.abs().max(b.abs());
+            if max == 0.0 {
+                1.0
+            } else {
+                (1.0 - (a - b).abs() / max).max(0.0)
+            }
+        }
+        _ => {
+            if same_value(a, b) {
+                1.0
+            } else {
+                0.0
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ticket::test_fixtures::sample_capabilities;
+    use crate::ticket::{
+        document::{ParameterInit, PrintFeatureOption, PropertyValue, ScoredProperty, NS_PSK},
+        FeatureOptionPack, FeatureOptionPackWithPredefined, MediaSizeTuple, PageMediaSize,
+        PageResolution, PredefinedMediaName,
+    };
+    use xml::name::OwnedName;
+
+    fn media_size_option(name: &str, width: i32, height: i32) -> PrintFeatureOption {
+        let scored_property = |name: &str, value: i32| {
+            ScoredProperty::new(
+                Some(OwnedName::qualified(name, NS_PSK, Some("psk"))),
+                None,
+                Some(PropertyValue::Integer(value)),
+                vec![],
+                vec![],
+            )
+        };
+        PrintFeatureOption::new(
+            Some(OwnedName::qualified(name, NS_PSK, Some("psk"))),
+            vec![
+                scored_property("MediaSizeWidth", width),
+                scored_property("MediaSizeHeight", height),
+            ],
+            vec![],
+        )
+    }
+
+    #[test]
+    fn same_name_is_exact_match() {
+        let capabilities = sample_capabilities();
+        let requested = PageMediaSize::new(media_size_option("ISOA4", 0, 0), vec![]);
+        let (matched, score) = capabilities.closest_option(&requested).unwrap();
+        assert_eq!(score, 1.0);
+        assert_eq!(
+            matched.as_predefined_name(),
+            Some(PredefinedMediaName::ISOA4)
+        );
+    }
+
+    #[test]
+    fn match_by_scored_properties() {
+        let capabilities = sample_capabilities();
+        // a vendor-specific A4 from another device
+        let mut option = media_size_option("ISOA4", 210000, 297000);
+        option.name = Some(OwnedName::qualified("A4", "http://vendor/", Some("ns0001")));
+        let requested = PageMediaSize::new(option, vec![]);
+        let (matched, score) = capabilities.closest_option(&requested).unwrap();
+        assert_eq!(score, 1.0);
+        assert_eq!(matched.size(), MediaSizeTuple::mm(210, 297));
+    }
+
+    #[test]
+    fn custom_size_is_matched_through_parameters() {
+        let capabilities = sample_capabilities();
+        let mut option = media_size_option("CustomMediaSize", 0, 0);
+        option.name = None;
+        for (scored_property, parameter) in option.scored_properties.iter_mut().zip([
+            "PageMediaSizeMediaSizeWidth",
+            "PageMediaSizeMediaSizeHeight",
+        ]) {
+            scored_property.value = None;
+            scored_property.parameter_ref =
+                Some(OwnedName::qualified(parameter, NS_PSK, Some("psk")));
+        }
+        let parameters = vec![
+            ParameterInit::new(
+                OwnedName::qualified("PageMediaSizeMediaSizeWidth", NS_PSK, Some("psk")),
+                PropertyValue::Integer(123456),
+            ),
+            ParameterInit::new(
+                OwnedName::qualified("PageMediaSizeMediaSizeHeight", NS_PSK, Some("psk")),
+                PropertyValue::Integer(234567),
+            ),
+        ];
+        let requested = PageMediaSize::new(option, parameters);
+        let (matched, score) = capabilities.closest_option(&requested).unwrap();
+        assert_eq!(score, 1.0);
+        assert_eq!(matched.size(), MediaSizeTuple::micron(123456, 234567));
+    }
+
+    #[test]
+    fn unresolved_parameter_is_not_matched() {
+        let capabilities = sample_capabilities();
+        let mut option = media_size_option("CustomMediaSize", 0, 0);
+        option.name = None;
+        for scored_property in option.scored_properties.iter_mut() {
+            scored_property.value = None;
+            scored_property.parameter_ref = Some(OwnedName::qualified(
+                "PageMediaSizeMediaSizeWidth",
+                NS_PSK,
+                Some("psk"),
+            ));
+        }
+        // the referenced parameter is not given
+        let requested = PageMediaSize::new(option, vec![]);
+        let (_, score) = capabilities.closest_option(&requested).unwrap();
+        assert_eq!(score, 0.0);
+    }
+
+    #[test]
+    fn closest_resolution() {
+        let capabilities = sample_capabilities();
+        let scored_property = |name: &str, value: i32| {
+            ScoredProperty::new(
+                Some(OwnedName::qualified(name, NS_PSK, Some("psk"))),
+                None,
+                Some(PropertyValue::Integer(value)),
+                vec![],
+                vec![],
+            )
+        };
+        let requested = PageResolution::new(
+            PrintFeatureOption::new(
+                None,
+                vec![
+                    scored_property("ResolutionX", 300),
+                    scored_property("ResolutionY", 300),
+                ],
+                vec![],
+            ),
+            vec![],
+        );
+        let (matched, score) = capabilities.closest_option(&requested).unwrap();
+        assert_eq!(matched.dpi(), (600, 600));
+        assert_eq!(score, 0.5);
+    }
+
+    #[test]
+    fn ranking_is_ordered() {
+        let capabilities = sample_capabilities();
+        let requested = media_size_option("Unknown", 210000, 297000);
+        let ranking = capabilities.rank_options_for_feature(
+            OwnedName::qualified("PageMediaSize", NS_PSK, Some("psk")),
+            &requested,
+            &[],
+        );
+        assert!(ranking.windows(2).all(|x| x[0].score >= x[1].score));
+        assert_eq!(
+            ranking[0]
+                .option
+                .name
+                .as_ref()
+                .map(|x| x.local_name.as_str()),
+            Some("ISOA4")
+        );
+    }
+}
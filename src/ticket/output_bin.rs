@@ -0,0 +1,3 @@
+use super::{define_feature_option_pack, FeatureKeyword, Keyword};
+
+define_feature_option_pack!(FeatureKeyword::JobOutputBin.name(), JobOutputBin);
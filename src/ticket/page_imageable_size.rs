@@ -1,10 +1,13 @@
+#[cfg(windows)]
 use super::{
-    document::{
-        reader::{ParsableXmlDocument, ParsePrintSchemaError},
-        PrintCapabilitiesDocument, WithProperties, NS_PSK,
-    },
-    FetchPrintCapabilitiesError, MediaSizeTuple, PageMediaSize, PrintCapabilities, PrintTicket,
+    document::reader::ParsableXmlDocument, FetchPrintCapabilitiesError, PageMediaSize,
+    PrintCapabilities, PrintTicket,
+};
+use super::{
+    document::{query::SchemaPath, reader::ParsePrintSchemaError, PrintCapabilitiesDocument},
+    Keyword, MediaSizeTuple, PropertyKeyword,
 };
+#[cfg(windows)]
 use crate::printer::PrinterDevice;
 use thiserror::Error;
 
@@ -20,9 +23,13 @@ pub struct PageImageableSize {
 }
 
 #[derive(Error, Debug)]
+#[non_exhaustive]
 /// Represents an error occurred while fetching imageable size.
+///
+/// [`PageImageableSizeError::FetchCapabilitiesError`] only exists on Windows, where the capabilities can be fetched from a device.
 pub enum PageImageableSizeError {
-    /// Failed to fetch print capabilities.
+    /// Failed to fetch print capabilities. Only available on Windows.
+    #[cfg(windows)]
     #[error("Failed to fetch print capabilities")]
     FetchCapabilitiesError(#[from] FetchPrintCapabilitiesError),
     /// Failed to parse print capabilities.
@@ -36,21 +43,32 @@ pub enum PageImageableSizeError {
     },
 }
 
-fn get_u32_property(
-    properties: &impl WithProperties,
-    name: &'static str,
-    ns: Option<&str>,
+/// Get the integer value of the property at the given path of keywords.
+///
+/// The field of the error is the first keyword on the path which is missing, or the last one if its value is invalid.
+fn get_u32_value(
+    caps: &PrintCapabilitiesDocument,
+    names: &[PropertyKeyword],
 ) -> Result<u32, PageImageableSizeError> {
-    properties
-        .get_property(name, ns)
-        .and_then(|x| x.value.as_ref())
+    let path = |names: &[PropertyKeyword]| SchemaPath::from_names(names.iter().map(|x| x.name()));
+    if let Some(value) = caps
+        .query_value(&path(names))
         .and_then(|x| x.integer())
         .and_then(|x| u32::try_from(x).ok())
-        .ok_or(PageImageableSizeError::FieldError { field: name })
+    {
+        return Ok(value);
+    }
+    let missing = (1..names.len())
+        .find(|&i| caps.query(&path(&names[..i])).is_empty())
+        .unwrap_or(names.len());
+    Err(PageImageableSizeError::FieldError {
+        field: names[missing - 1].local_name(),
+    })
 }
 
 impl PageImageableSize {
     /// Try to fetch the imageable size for the given printer device and media size.
+    #[cfg(windows)]
     pub fn try_fetch(
         device: &PrinterDevice,
         media: PageMediaSize,
@@ -58,22 +76,26 @@ impl PageImageableSize {
         let ticket = PrintTicket::from(media);
         let caps_xml = PrintCapabilities::fetch_xml_for_ticket(device, Some(&ticket))?;
         let caps = PrintCapabilitiesDocument::parse_from_bytes(&caps_xml)?;
-        let imageable_size = caps.get_property("PageImageableSize", Some(NS_PSK)).ok_or(
-            PageImageableSizeError::FieldError {
-                field: "PageImageableSize",
-            },
-        )?;
-        let size_w = get_u32_property(imageable_size, "ImageableSizeWidth", Some(NS_PSK))?;
-        let size_h = get_u32_property(imageable_size, "ImageableSizeHeight", Some(NS_PSK))?;
-        let imageable_area = imageable_size
-            .get_property("ImageableArea", Some(NS_PSK))
-            .ok_or(PageImageableSizeError::FieldError {
-                field: "ImageableArea",
-            })?;
-        let origin_w = get_u32_property(imageable_area, "OriginWidth", Some(NS_PSK))?;
-        let origin_h = get_u32_property(imageable_area, "OriginHeight", Some(NS_PSK))?;
-        let extent_w = get_u32_property(imageable_area, "ExtentWidth", Some(NS_PSK))?;
-        let extent_h = get_u32_property(imageable_area, "ExtentHeight", Some(NS_PSK))?;
+        Self::from_capabilities(&caps)
+    }
+
+    /// Read the imageable size from a print capabilities document.
+    ///
+    /// The document is expected to be generated for a print ticket that selects the media size,
+    /// as `PageImageableSize::try_fetch` does.
+    pub fn from_capabilities(
+        caps: &PrintCapabilitiesDocument,
+    ) -> Result<Self, PageImageableSizeError> {
+        const SIZE: PropertyKeyword = PropertyKeyword::PageImageableSize;
+        const AREA: PropertyKeyword = PropertyKeyword::ImageableArea;
+        let size = |name| get_u32_value(caps, &[SIZE, name]);
+        let area = |name| get_u32_value(caps, &[SIZE, AREA, name]);
+        let size_w = size(PropertyKeyword::ImageableSizeWidth)?;
+        let size_h = size(PropertyKeyword::ImageableSizeHeight)?;
+        let origin_w = area(PropertyKeyword::OriginWidth)?;
+        let origin_h = area(PropertyKeyword::OriginHeight)?;
+        let extent_w = area(PropertyKeyword::ExtentWidth)?;
+        let extent_h = area(PropertyKeyword::ExtentHeight)?;
         Ok(Self {
             size: MediaSizeTuple::micron(size_w, size_h),
             origin: MediaSizeTuple::micron(origin_w, origin_h),
@@ -84,11 +106,15 @@ impl PageImageableSize {
 
 #[cfg(test)]
 mod tests {
-    use super::PageImageableSize;
-    use crate::{test_utils::null_device, ticket::PrintCapabilities};
+    use super::{PageImageableSize, PageImageableSizeError};
+    use crate::ticket::test_fixtures::{capabilities_with, sample_capabilities_document};
+    use crate::ticket::MediaSizeTuple;
 
+    #[cfg(windows)]
     #[test]
     fn get_imageable_size() {
+        use crate::{test_utils::null_device, ticket::PrintCapabilities};
+
         let device = null_device::thread_local();
         let capabilities = PrintCapabilities::fetch(&device).unwrap();
         for media in capabilities.page_media_sizes() {
@@ -97,4 +123,45 @@ mod tests {
             println!("{:#?}", imageable_size);
         }
     }
+
+    #[test]
+    fn imageable_size_from_capabilities() {
+        let caps = sample_capabilities_document();
+        let imageable_size = PageImageableSize::from_capabilities(&caps).unwrap();
+        assert_eq!(imageable_size.size, MediaSizeTuple::micron(209973, 296968));
+        assert_eq!(imageable_size.origin, MediaSizeTuple::micron(0, 0));
+        assert_eq!(
+            imageable_size.extent,
+            MediaSizeTuple::micron(209973, 296333)
+        );
+    }
+
+    #[test]
+    fn missing_field() {
+        let field = |body: &str| {
+            let caps = capabilities_with(body).document;
+            match PageImageableSize::from_capabilities(&caps) {
+                Err(PageImageableSizeError::FieldError { field }) => field,
+                x => panic!("unexpected result: {:?}", x),
+            }
+        };
+        assert_eq!(field(""), "PageImageableSize");
+        let size = r#"<psf:Property name="psk:ImageableSizeWidth"><psf:Value xsi:type="xsd:integer">100</psf:Value></psf:Property>
+        <psf:Property name="psk:ImageableSizeHeight"><psf:Value xsi:type="xsd:integer">200</psf:Value></psf:Property>"#;
+        assert_eq!(
+            field(&format!(
+                r#"<psf:Property name="psk:PageImageableSize">{}</psf:Property>"#,
+                size
+            )),
+            "ImageableArea"
+        );
+        assert_eq!(
+            field(
+                r#"<psf:Property name="psk:PageImageableSize">
+        <psf:Property name="psk:ImageableSizeWidth"><psf:Value xsi:type="xsd:integer">-1</psf:Value></psf:Property>
+    </psf:Property>"#
+            ),
+            "ImageableSizeWidth"
+        );
+    }
 }
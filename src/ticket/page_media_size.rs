@@ -1,12 +1,11 @@
 use super::{
     define_feature_option_pack,
-    document::{ParameterInit, PrintFeatureOption, WithScoredProperties, NS_PSK},
-    MediaSizeTuple, PredefinedMediaName,
+    document::{WithScoredProperties, NS_PSK},
+    FeatureKeyword, Keyword, MediaSizeTuple, PredefinedMediaName,
 };
-use xml::name::OwnedName;
 
 define_feature_option_pack!(
-    OwnedName::qualified("PageMediaSize", NS_PSK, Some("psk")),
+    FeatureKeyword::PageMediaSize.name(),
     PageMediaSize,
     PredefinedMediaName
 );
@@ -30,7 +29,7 @@ impl PageMediaSize {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, windows))]
 mod tests {
     use crate::{
         test_utils::null_device,
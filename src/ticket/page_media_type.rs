@@ -0,0 +1,7 @@
+use super::{define_feature_option_pack, FeatureKeyword, Keyword, PredefinedMediaType};
+
+define_feature_option_pack!(
+    FeatureKeyword::PageMediaType.name(),
+    PageMediaType,
+    PredefinedMediaType
+);
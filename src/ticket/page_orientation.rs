@@ -1,17 +1,12 @@
-use super::{
-    define_feature_option_pack,
-    document::{ParameterInit, PrintFeatureOption, NS_PSK},
-    PredefinedPageOrientation,
-};
-use xml::name::OwnedName;
+use super::{define_feature_option_pack, FeatureKeyword, Keyword, PredefinedPageOrientation};
 
 define_feature_option_pack!(
-    OwnedName::qualified("PageOrientation", NS_PSK, Some("psk")),
+    FeatureKeyword::PageOrientation.name(),
     PageOrientation,
     PredefinedPageOrientation
 );
 
-#[cfg(test)]
+#[cfg(all(test, windows))]
 mod tests {
     use crate::{
         test_utils::null_device,
@@ -1,12 +1,7 @@
-use super::{
-    define_feature_option_pack,
-    document::{ParameterInit, PrintFeatureOption, NS_PSK},
-    PredefinedPageOutputColor,
-};
-use xml::name::OwnedName;
+use super::{define_feature_option_pack, FeatureKeyword, Keyword, PredefinedPageOutputColor};
 
 define_feature_option_pack!(
-    OwnedName::qualified("PageOutputColor", NS_PSK, Some("psk")),
+    FeatureKeyword::PageOutputColor.name(),
     PageOutputColor,
     PredefinedPageOutputColor
 );
@@ -1,13 +1,10 @@
 use super::{
     define_feature_option_pack,
-    document::{ParameterInit, PrintFeatureOption, WithScoredProperties, NS_PSK},
+    document::{WithScoredProperties, NS_PSK},
+    FeatureKeyword, Keyword,
 };
-use xml::name::OwnedName;
 
-define_feature_option_pack!(
-    OwnedName::qualified("PageResolution", NS_PSK, Some("psk")),
-    PageResolution
-);
+define_feature_option_pack!(FeatureKeyword::PageResolution.name(), PageResolution);
 
 impl PageResolution {
     /// Get the resolution of the page in DPI.
@@ -28,7 +25,7 @@ impl PageResolution {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, windows))]
 mod tests {
     use crate::{
         test_utils::null_device,
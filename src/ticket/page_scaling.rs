@@ -0,0 +1,216 @@
+use super::{
+    define_feature_option_pack,
+    document::{PropertyValue, WithScoredProperties, NS_PSK},
+    set_referenced_parameter, FeatureKeyword, Keyword, PredefinedPageScaling, PrintCapabilities,
+    PropertyKeyword, SetParameterError,
+};
+
+define_feature_option_pack!(
+    FeatureKeyword::PageScaling.name(),
+    PageScaling,
+    PredefinedPageScaling
+);
+
+impl PageScaling {
+    fn scored_value(&self, property: PropertyKeyword) -> Option<i32> {
+        self.option
+            .get_scored_property(property.local_name(), Some(NS_PSK))
+            .and_then(|x| x.value_with(&self.parameters))
+            .and_then(|x| x.integer())
+    }
+
+    /// Get the scale of the width and the height in percent.
+    ///
+    /// For [`PredefinedPageScaling::CustomSquare`], both are the value of the `psk:Scale` scored property.
+    pub fn scale(&self) -> Option<(i32, i32)> {
+        if let Some(scale) = self.scored_value(PropertyKeyword::Scale) {
+            return Some((scale, scale));
+        }
+        Some((
+            self.scored_value(PropertyKeyword::ScaleWidth)?,
+            self.scored_value(PropertyKeyword::ScaleHeight)?,
+        ))
+    }
+
+    /// Get the offset of the width and the height in microns.
+    pub fn offset(&self) -> Option<(i32, i32)> {
+        Some((
+            self.scored_value(PropertyKeyword::OffsetWidth)?,
+            self.scored_value(PropertyKeyword::OffsetHeight)?,
+        ))
+    }
+
+    /// Set the parameter referenced by the given scored property, like `psk:ScaleWidth`,
+    /// after checking the value against the parameter definition of the capabilities.
+    pub fn set_parameter(
+        &mut self,
+        capabilities: &PrintCapabilities,
+        property: PropertyKeyword,
+        value: i32,
+    ) -> Result<(), SetParameterError> {
+        set_referenced_parameter(
+            capabilities,
+            &self.option,
+            &mut self.parameters,
+            property,
+            PropertyValue::Integer(value),
+        )
+    }
+
+    /// Set the scale in percent, for both [`PredefinedPageScaling::Custom`] and [`PredefinedPageScaling::CustomSquare`].
+    ///
+    /// The parameters are checked against the capabilities, see [`PageScaling::set_parameter`].
+    pub fn set_scale(
+        &mut self,
+        capabilities: &PrintCapabilities,
+        percent: i32,
+    ) -> Result<(), SetParameterError> {
+        if self
+            .option
+            .get_scored_property(PropertyKeyword::Scale.local_name(), Some(NS_PSK))
+            .is_some()
+        {
+            return self.set_parameter(capabilities, PropertyKeyword::Scale, percent);
+        }
+        self.set_parameter(capabilities, PropertyKeyword::ScaleWidth, percent)?;
+        self.set_parameter(capabilities, PropertyKeyword::ScaleHeight, percent)
+    }
+
+    /// Set the offset of the width and the height in microns.
+    ///
+    /// The parameters are checked against the capabilities, see [`PageScaling::set_parameter`].
+    pub fn set_offset(
+        &mut self,
+        capabilities: &PrintCapabilities,
+        width: i32,
+        height: i32,
+    ) -> Result<(), SetParameterError> {
+        self.set_parameter(capabilities, PropertyKeyword::OffsetWidth, width)?;
+        self.set_parameter(capabilities, PropertyKeyword::OffsetHeight, height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ticket::test_fixtures::capabilities_with;
+    use crate::ticket::{
+        document::{reader::ParsableXmlDocument, PrintTicketDocument},
+        FeatureOptionPackWithPredefined, PageScaling, PredefinedPageScaling, PrintCapabilities,
+        PrintTicket, PropertyKeyword, SetParameterError,
+    };
+
+    fn capabilities() -> PrintCapabilities {
+        let parameter = |name: &str, default: i32, min: i32, max: i32| {
+            format!(
+                r#"<psf:ParameterDef name="psk:{name}">
+        <psf:Property name="psf:DataType">
+            <psf:Value xsi:type="xsd:QName">xsd:integer</psf:Value>
+        </psf:Property>
+        <psf:Property name="psf:DefaultValue">
+            <psf:Value xsi:type="xsd:integer">{default}</psf:Value>
+        </psf:Property>
+        <psf:Property name="psf:MinValue">
+            <psf:Value xsi:type="xsd:integer">{min}</psf:Value>
+        </psf:Property>
+        <psf:Property name="psf:MaxValue">
+            <psf:Value xsi:type="xsd:integer">{max}</psf:Value>
+        </psf:Property>
+    </psf:ParameterDef>"#
+            )
+        };
+        let scored = |name: &str, parameter: &str| {
+            format!(
+                r#"<psf:ScoredProperty name="psk:{name}">
+                <psf:ParameterRef name="psk:{parameter}" />
+            </psf:ScoredProperty>"#
+            )
+        };
+        capabilities_with(&format!(
+            r#"{}
+    {}
+    {}
+    {}
+    {}
+    <psf:Feature name="psk:PageScaling">
+        <psf:Option name="psk:None" />
+        <psf:Option name="psk:FitApplicationMediaSizeToPageImageableSize" />
+        <psf:Option name="psk:Custom">
+            {}
+            {}
+            {}
+            {}
+        </psf:Option>
+        <psf:Option name="psk:CustomSquare">
+            {}
+            {}
+            {}
+        </psf:Option>
+    </psf:Feature>"#,
+            parameter("PageScalingScaleWidth", 100, 1, 1000),
+            parameter("PageScalingScaleHeight", 100, 1, 1000),
+            parameter("PageScalingScale", 100, 1, 1000),
+            parameter("PageScalingOffsetWidth", 0, -10000, 10000),
+            parameter("PageScalingOffsetHeight", 0, -10000, 10000),
+            scored("ScaleWidth", "PageScalingScaleWidth"),
+            scored("ScaleHeight", "PageScalingScaleHeight"),
+            scored("OffsetWidth", "PageScalingOffsetWidth"),
+            scored("OffsetHeight", "PageScalingOffsetHeight"),
+            scored("Scale", "PageScalingScale"),
+            scored("OffsetWidth", "PageScalingOffsetWidth"),
+            scored("OffsetHeight", "PageScalingOffsetHeight"),
+        ))
+    }
+
+    fn find(capabilities: &PrintCapabilities, name: PredefinedPageScaling) -> PageScaling {
+        capabilities
+            .page_scalings()
+            .find(|x| x.as_predefined_name() == Some(name))
+            .unwrap()
+    }
+
+    #[test]
+    fn custom_scaling() {
+        let capabilities = capabilities();
+        let mut custom = find(&capabilities, PredefinedPageScaling::Custom);
+        assert_eq!(custom.scale(), Some((100, 100)));
+        assert_eq!(custom.offset(), Some((0, 0)));
+        custom.set_scale(&capabilities, 50).unwrap();
+        custom.set_offset(&capabilities, -2000, 3000).unwrap();
+        assert_eq!(custom.scale(), Some((50, 50)));
+        assert!(matches!(
+            custom.set_parameter(&capabilities, PropertyKeyword::ScaleWidth, 2000),
+            Err(SetParameterError::InvalidValue(_))
+        ));
+        assert!(matches!(
+            custom.set_parameter(&capabilities, PropertyKeyword::Scale, 50),
+            Err(SetParameterError::NotParameterized(_))
+        ));
+
+        let ticket = PrintTicket::from(custom);
+        let document = PrintTicketDocument::parse_from_bytes(ticket.xml).unwrap();
+        let values = document
+            .parameter_inits
+            .iter()
+            .map(|x| (x.name.local_name.as_str(), x.value.integer()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![
+                ("PageScalingScaleWidth", Some(50)),
+                ("PageScalingScaleHeight", Some(50)),
+                ("PageScalingOffsetWidth", Some(-2000)),
+                ("PageScalingOffsetHeight", Some(3000)),
+            ]
+        );
+
+        let mut square = find(&capabilities, PredefinedPageScaling::CustomSquare);
+        square.set_scale(&capabilities, 200).unwrap();
+        assert_eq!(square.scale(), Some((200, 200)));
+
+        let fit = find(
+            &capabilities,
+            PredefinedPageScaling::FitApplicationMediaSizeToPageImageableSize,
+        );
+        assert_eq!(fit.scale(), None);
+    }
+}
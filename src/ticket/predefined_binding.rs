@@ -0,0 +1,36 @@
+use super::{define_predefined_name, document::NS_PSK};
+use strum::{EnumString, IntoStaticStr, VariantArray};
+
+define_predefined_name! {
+    namespace = NS_PSK;
+    #[derive(EnumString, IntoStaticStr, VariantArray, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+    /// Represents a predefined binding method.
+    pub enum PredefinedBinding {
+        /// No binding.
+        None,
+        /// Bind along the bottom edge.
+        BindBottom,
+        /// Bind along the left edge.
+        BindLeft,
+        /// Bind along the right edge.
+        BindRight,
+        /// Bind along the top edge.
+        BindTop,
+        /// Fold and bind as a booklet.
+        Booklet,
+        /// Stitch along the bottom edge.
+        EdgeStitchBottom,
+        /// Stitch along the left edge.
+        EdgeStitchLeft,
+        /// Stitch along the right edge.
+        EdgeStitchRight,
+        /// Stitch along the top edge.
+        EdgeStitchTop,
+        /// Fold the sheets.
+        Fold,
+        /// Offset the output from the previous one.
+        JogOffset,
+        /// Trim the sheets.
+        Trim,
+    }
+}
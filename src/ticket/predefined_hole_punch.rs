@@ -0,0 +1,20 @@
+use super::{define_predefined_name, document::NS_PSK};
+use strum::{EnumString, IntoStaticStr, VariantArray};
+
+define_predefined_name! {
+    namespace = NS_PSK;
+    #[derive(EnumString, IntoStaticStr, VariantArray, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+    /// Represents a predefined hole punch edge.
+    pub enum PredefinedHolePunch {
+        /// No holes.
+        None,
+        /// Holes along the left edge.
+        LeftEdge,
+        /// Holes along the right edge.
+        RightEdge,
+        /// Holes along the top edge.
+        TopEdge,
+        /// Holes along the bottom edge.
+        BottomEdge,
+    }
+}
@@ -0,0 +1,44 @@
+use super::{define_predefined_name, document::NS_PSK};
+use strum::{EnumString, IntoStaticStr, VariantArray};
+
+define_predefined_name! {
+    namespace = NS_PSK;
+    #[derive(EnumString, IntoStaticStr, VariantArray, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+    /// Represents a predefined input bin.
+    pub enum PredefinedInputBin {
+        /// The device selects the input bin.
+        AutoSelect,
+        /// Automatic sheet feeder.
+        AutoSheetFeeder,
+        /// Paper cassette.
+        Cassette,
+        /// Manual feed.
+        Manual,
+        /// Tractor feed for continuous media.
+        Tractor,
+    }
+}
+
+define_predefined_name! {
+    namespace = NS_PSK;
+    #[derive(EnumString, IntoStaticStr, VariantArray, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+    /// Represents the kind of media an input bin holds, as the `psk:BinType` property.
+    pub enum InputBinType {
+        /// Continuous media, such as fan-fold paper or rolls.
+        ContinuousFeed,
+        /// Separate sheets.
+        SheetFeed,
+    }
+}
+
+define_predefined_name! {
+    namespace = NS_PSK;
+    #[derive(EnumString, IntoStaticStr, VariantArray, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+    /// Represents how media is fed from an input bin, as the `psk:FeedType` property.
+    pub enum InputFeedType {
+        /// Media is fed by the device.
+        Automatic,
+        /// Media is fed by hand.
+        Manual,
+    }
+}
@@ -0,0 +1,40 @@
+use super::{define_predefined_name, document::NS_PSK};
+use strum::{EnumString, IntoStaticStr, VariantArray};
+
+define_predefined_name! {
+    namespace = NS_PSK;
+    #[derive(EnumString, IntoStaticStr, VariantArray, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+    #[allow(missing_docs)]
+    /// Represents a predefined media type.
+    pub enum PredefinedMediaType {
+        AutoSelect,
+        Archival,
+        BackPrintFilm,
+        Bond,
+        CardStock,
+        Continuous,
+        EnvelopePlain,
+        EnvelopeWindow,
+        Fabric,
+        HighResolution,
+        Label,
+        MultiLayerForm,
+        MultiPartForm,
+        None,
+        Photographic,
+        PhotographicFilm,
+        PhotographicGlossy,
+        PhotographicHighGloss,
+        PhotographicMatte,
+        PhotographicSatin,
+        PhotographicSemiGloss,
+        Plain,
+        Screen,
+        ScreenPaged,
+        Stationery,
+        TabStockFull,
+        TabStockPreCut,
+        Transparency,
+        TShirtTransfer,
+    }
+}
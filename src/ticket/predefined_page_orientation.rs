@@ -1,25 +1,15 @@
-use super::{document::NS_PSK, PredefinedName};
-use std::str::FromStr;
-use strum::EnumString;
-use xml::name::OwnedName;
+use super::{define_predefined_name, document::NS_PSK};
+use strum::{EnumString, IntoStaticStr, VariantArray};
 
-#[derive(EnumString, Debug, PartialEq, Eq, Hash, Clone, Copy)]
-#[allow(missing_docs)]
-/// Represents a predefined page orientation.
-pub enum PredefinedPageOrientation {
-    Portrait,
-    Landscape,
-    ReversePortrait,
-    ReverseLandscape,
-}
-
-impl PredefinedName for PredefinedPageOrientation {
-    /// Get predefined media name from the given name.
-    fn from_name(name: &OwnedName) -> Option<Self> {
-        if name.namespace_ref() == Some(NS_PSK) {
-            Self::from_str(name.local_name.as_str()).ok()
-        } else {
-            None
-        }
+define_predefined_name! {
+    namespace = NS_PSK;
+    #[derive(EnumString, IntoStaticStr, VariantArray, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+    #[allow(missing_docs)]
+    /// Represents a predefined page orientation.
+    pub enum PredefinedPageOrientation {
+        Portrait,
+        Landscape,
+        ReversePortrait,
+        ReverseLandscape,
     }
 }
@@ -0,0 +1,24 @@
+use super::{define_predefined_name, document::NS_PSK};
+use strum::{EnumString, IntoStaticStr, VariantArray};
+
+define_predefined_name! {
+    namespace = NS_PSK;
+    #[derive(EnumString, IntoStaticStr, VariantArray, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+    /// Represents a predefined page scaling mode.
+    pub enum PredefinedPageScaling {
+        /// No scaling.
+        None,
+        /// Scale the width and the height separately, by the `PageScalingScaleWidth` and `PageScalingScaleHeight` parameters.
+        Custom,
+        /// Scale the width and the height together, by the `PageScalingScale` parameter.
+        CustomSquare,
+        /// Fit the bleed size of the application to the imageable size of the page.
+        FitApplicationBleedSizeToPageImageableSize,
+        /// Fit the content size of the application to the imageable size of the page.
+        FitApplicationContentSizeToPageImageableSize,
+        /// Fit the media size of the application to the imageable size of the page.
+        FitApplicationMediaSizeToPageImageableSize,
+        /// Fit the media size of the application to the media size of the page.
+        FitApplicationMediaSizeToPageMediaSize,
+    }
+}
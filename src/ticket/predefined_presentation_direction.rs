@@ -0,0 +1,26 @@
+use super::{define_predefined_name, document::NS_PSK};
+use strum::{EnumString, IntoStaticStr, VariantArray};
+
+define_predefined_name! {
+    namespace = NS_PSK;
+    #[derive(EnumString, IntoStaticStr, VariantArray, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+    /// Represents a predefined order of the pages on a sheet, for the `PresentationDirection` sub-feature of N-up features.
+    pub enum PredefinedPresentationDirection {
+        /// Right, then down.
+        RightBottom,
+        /// Down, then right.
+        BottomRight,
+        /// Left, then down.
+        LeftBottom,
+        /// Down, then left.
+        BottomLeft,
+        /// Right, then up.
+        RightTop,
+        /// Up, then right.
+        TopRight,
+        /// Left, then up.
+        LeftTop,
+        /// Up, then left.
+        TopLeft,
+    }
+}
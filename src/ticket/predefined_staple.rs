@@ -0,0 +1,30 @@
+use super::{define_predefined_name, document::NS_PSK};
+use strum::{EnumString, IntoStaticStr, VariantArray};
+
+define_predefined_name! {
+    namespace = NS_PSK;
+    #[derive(EnumString, IntoStaticStr, VariantArray, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+    /// Represents a predefined staple location.
+    pub enum PredefinedStaple {
+        /// No staple.
+        None,
+        /// Staple along the fold of a booklet.
+        SaddleStitch,
+        /// One staple at the bottom left.
+        StapleBottomLeft,
+        /// One staple at the bottom right.
+        StapleBottomRight,
+        /// Two staples along the bottom edge.
+        StapleDualBottom,
+        /// Two staples along the left edge.
+        StapleDualLeft,
+        /// Two staples along the right edge.
+        StapleDualRight,
+        /// Two staples along the top edge.
+        StapleDualTop,
+        /// One staple at the top left.
+        StapleTopLeft,
+        /// One staple at the top right.
+        StapleTopRight,
+    }
+}
@@ -1,19 +1,27 @@
+#[cfg(windows)]
 use super::{
-    document::{
-        reader::{ParsableXmlDocument, ParsePrintSchemaError},
-        ParameterInit, PrintCapabilitiesDocument, PrintFeatureOption, WithProperties, NS_PSF,
-        NS_PSK,
-    },
-    Copies, FeatureOptionPack, JobDuplex, PageMediaSize, PageOrientation, PageOutputColor,
-    PageResolution, PrintTicket,
+    document::reader::{ParsableXmlDocument, ParsePrintSchemaError},
+    PrintTicket,
+};
+use super::{
+    document::{ParameterInit, PrintCapabilitiesDocument, PrintFeatureOption, NS_PSK},
+    AnyFeature, Copies, DocumentBinding, DocumentHolePunch, DocumentInputBin, DocumentNUp,
+    DocumentStaple, FeatureOptionPack, JobBinding, JobDuplex, JobHolePunch, JobInputBin, JobNUp,
+    JobOutputBin, JobStaple, PageInputBin, PageMediaSize, PageMediaType, PageOrientation,
+    PageOutputColor, PageResolution, PageScaling,
 };
+#[cfg(windows)]
 use crate::{
     printer::PrinterDevice,
     utils::{stream::read_com_stream, wchar},
 };
+#[cfg(windows)]
 use scopeguard::defer;
+#[cfg(windows)]
 use std::fmt::Debug;
+#[cfg(windows)]
 use thiserror::Error;
+#[cfg(windows)]
 use windows::{
     core::{BSTR, PCWSTR},
     Win32::{
@@ -25,6 +33,7 @@ use windows::{
 };
 use xml::name::OwnedName;
 
+#[cfg(windows)]
 #[derive(Error, Debug)]
 /// Represents an error occurred while fetching print capabilities.
 pub enum FetchPrintCapabilitiesError {
@@ -52,6 +61,7 @@ pub struct PrintCapabilities {
     pub document: PrintCapabilitiesDocument,
 }
 
+#[cfg(windows)]
 impl PrintCapabilities {
     /// Fetch print capabilities XML (without parsing it) for the given printer device.
     pub fn fetch_xml(device: &PrinterDevice) -> Result<Vec<u8>, FetchPrintCapabilitiesError> {
@@ -107,16 +117,15 @@ impl PrintCapabilities {
             .map_err(FetchPrintCapabilitiesError::ParseError)?;
         Ok(PrintCapabilities { document })
     }
+}
 
+impl PrintCapabilities {
     /// Defines all parameters with default values.
     pub fn default_parameters(&self) -> impl Iterator<Item = ParameterInit> + '_ {
         self.document.parameter_defs.iter().filter_map(|param_def| {
-            param_def
-                .default_value()
-                .map(|default_value| ParameterInit {
-                    name: param_def.name.clone(),
-                    value: default_value.clone(),
-                })
+            param_def.default_value().map(|default_value| {
+                ParameterInit::new(param_def.name.clone(), default_value.clone())
+            })
         })
     }
 
@@ -139,12 +148,9 @@ impl PrintCapabilities {
                     .is_ok()
             })
             .filter_map(move |param_def| {
-                param_def
-                    .default_value()
-                    .map(|default_value| ParameterInit {
-                        name: param_def.name.clone(),
-                        value: default_value.clone(),
-                    })
+                param_def.default_value().map(|default_value| {
+                    ParameterInit::new(param_def.name.clone(), default_value.clone())
+                })
             })
     }
 
@@ -163,11 +169,79 @@ impl PrintCapabilities {
             .flat_map(|x| x.options.iter())
     }
 
+    /// Get all top-level features, including the ones without a typed [`FeatureOptionPack`].
+    pub fn features(&self) -> impl Iterator<Item = AnyFeature<'_>> + '_ {
+        AnyFeature::list(self)
+    }
+
+    /// Get all features, including the nested ones. Each feature is followed by its sub-features.
+    pub fn all_features(&self) -> impl Iterator<Item = AnyFeature<'_>> + '_ {
+        AnyFeature::list_all(self)
+    }
+
     /// Get all page media sizes.
     pub fn page_media_sizes(&self) -> impl Iterator<Item = PageMediaSize> + '_ {
         PageMediaSize::list(self)
     }
 
+    /// Get all supported page media types.
+    pub fn page_media_types(&self) -> impl Iterator<Item = PageMediaType> + '_ {
+        PageMediaType::list(self)
+    }
+
+    /// Get all input bins that can be selected for the whole job.
+    pub fn job_input_bins(&self) -> impl Iterator<Item = JobInputBin> + '_ {
+        JobInputBin::list(self)
+    }
+
+    /// Get all input bins that can be selected for each document.
+    pub fn document_input_bins(&self) -> impl Iterator<Item = DocumentInputBin> + '_ {
+        DocumentInputBin::list(self)
+    }
+
+    /// Get all input bins that can be selected for each page.
+    pub fn page_input_bins(&self) -> impl Iterator<Item = PageInputBin> + '_ {
+        PageInputBin::list(self)
+    }
+
+    /// Get all output bins that can be selected for the whole job.
+    pub fn job_output_bins(&self) -> impl Iterator<Item = JobOutputBin> + '_ {
+        JobOutputBin::list(self)
+    }
+
+    /// Get all supported staple locations for the whole job.
+    pub fn job_staples(&self) -> impl Iterator<Item = JobStaple> + '_ {
+        JobStaple::list(self)
+    }
+
+    /// Get all supported staple locations for each document.
+    pub fn document_staples(&self) -> impl Iterator<Item = DocumentStaple> + '_ {
+        DocumentStaple::list(self)
+    }
+
+    /// Get all supported hole punch edges for the whole job.
+    pub fn job_hole_punches(&self) -> impl Iterator<Item = JobHolePunch> + '_ {
+        JobHolePunch::list(self)
+    }
+
+    /// Get all supported hole punch edges for each document.
+    pub fn document_hole_punches(&self) -> impl Iterator<Item = DocumentHolePunch> + '_ {
+        DocumentHolePunch::list(self)
+    }
+
+    /// Get all supported binding methods for the whole job.
+    ///
+    /// # Note
+    /// This corresponds to the Print Schema's `JobBindAllDocuments` keyword.
+    pub fn job_bindings(&self) -> impl Iterator<Item = JobBinding> + '_ {
+        JobBinding::list(self)
+    }
+
+    /// Get all supported binding methods for each document.
+    pub fn document_bindings(&self) -> impl Iterator<Item = DocumentBinding> + '_ {
+        DocumentBinding::list(self)
+    }
+
     /// Get all supported page orientations.
     pub fn page_orientations(&self) -> impl Iterator<Item = PageOrientation> + '_ {
         PageOrientation::list(self)
@@ -191,12 +265,32 @@ impl PrintCapabilities {
         PageResolution::list(self)
     }
 
+    /// Get all supported numbers of pages per sheet for the whole job.
+    ///
+    /// # Note
+    /// This corresponds to the Print Schema's `JobNUpAllDocumentsContiguously` keyword.
+    /// See [`JobNUp::presentation_directions`] for the options of its `PresentationDirection` sub-feature.
+    pub fn job_n_ups(&self) -> impl Iterator<Item = JobNUp> + '_ {
+        JobNUp::list(self)
+    }
+
+    /// Get all supported numbers of pages per sheet for each document.
+    ///
+    /// See [`DocumentNUp::presentation_directions`] for the options of its `PresentationDirection` sub-feature.
+    pub fn document_n_ups(&self) -> impl Iterator<Item = DocumentNUp> + '_ {
+        DocumentNUp::list(self)
+    }
+
+    /// Get all supported page scaling modes.
+    pub fn page_scalings(&self) -> impl Iterator<Item = PageScaling> + '_ {
+        PageScaling::list(self)
+    }
+
     /// Get the maximum number of copies that a printer can print. Return `None` if the device does not report a maximum.
     ///
     /// # Note
     /// This corresponds to the Print Schema's `JobCopiesAllDocuments` keyword, not the `DocumentCopiesAllPages` keyword, or the `PageCopies` keyword. If the printer can print unlimited copies, the property value is 9999.
     pub fn max_copies(&self) -> Option<Copies> {
-        println!("{:#?}", self.document.parameter_defs);
         self.document
             .parameter_defs
             .iter()
@@ -204,9 +298,7 @@ impl PrintCapabilities {
                 x.name.local_name == "JobCopiesAllDocuments"
                     && x.name.namespace_ref() == Some(NS_PSK)
             })
-            .and_then(|x| x.get_property("MaxValue", Some(NS_PSF)))
-            .and_then(|x| x.value.as_ref())
-            .and_then(|x| x.integer())
+            .and_then(|x| x.definition().max_value)
             .and_then(|x| u16::try_from(x).ok())
             .map(Copies)
     }
@@ -214,17 +306,48 @@ impl PrintCapabilities {
 
 #[cfg(test)]
 mod tests {
+    #[cfg(windows)]
     use super::PrintCapabilities;
-    use crate::test_utils::null_device;
+    use crate::ticket::test_fixtures::sample_capabilities;
+    use crate::ticket::{FeatureOptionPackWithPredefined, MediaSizeTuple, PredefinedMediaName};
+
+    #[cfg(windows)]
     #[test]
     fn test_fetch_xml() {
-        let device = null_device::thread_local();
+        let device = crate::test_utils::null_device::thread_local();
         PrintCapabilities::fetch_xml(&device).unwrap();
     }
 
+    #[cfg(windows)]
     #[test]
     fn test_fetch_xml_and_parse() {
-        let device = null_device::thread_local();
+        let device = crate::test_utils::null_device::thread_local();
         PrintCapabilities::fetch(&device).unwrap();
     }
+
+    #[test]
+    fn list_page_media_sizes_from_document() {
+        let capabilities = sample_capabilities();
+        let a4 = capabilities
+            .page_media_sizes()
+            .find(|x| x.as_predefined_name() == Some(PredefinedMediaName::ISOA4))
+            .unwrap();
+        assert_eq!(a4.size(), MediaSizeTuple::mm(210, 297));
+    }
+
+    #[test]
+    fn max_copies_from_document() {
+        let capabilities = sample_capabilities();
+        assert_eq!(capabilities.max_copies().map(|x| x.0), Some(9999));
+    }
+
+    #[test]
+    fn page_resolutions_from_document() {
+        let capabilities = sample_capabilities();
+        let dpi = capabilities
+            .page_resolutions()
+            .map(|x| x.dpi())
+            .collect::<Vec<_>>();
+        assert_eq!(dpi, vec![(600, 600)]);
+    }
 }
@@ -1,11 +1,22 @@
+#[cfg(windows)]
+use super::PrintScope;
 use super::{
-    document::{writer::XmlDocumentRoot, PrintTicketDocument},
+    document::{
+        reader::{ParsableXmlDocument, ParseOptions, ParsePrintSchemaError},
+        writer::XmlDocumentRoot,
+        PrintTicketDocument,
+    },
     DEFAULT_PRINT_TICKET_XML,
 };
+#[cfg(windows)]
 use crate::{printer::PrinterDevice, utils::wchar};
+#[cfg(windows)]
 use scopeguard::defer;
+#[cfg(windows)]
 use std::ptr;
+#[cfg(windows)]
 use thiserror::Error;
+#[cfg(windows)]
 use windows::{
     core::{BSTR, PCWSTR},
     Win32::{
@@ -14,8 +25,9 @@ use windows::{
             Printing::{
                 ClosePrinter, DocumentPropertiesW, OpenPrinterW,
                 PrintTicket::{
-                    kPTJobScope, kUserDefaultDevmode, PTCloseProvider,
-                    PTConvertPrintTicketToDevMode, PTOpenProvider, PTReleaseMemory,
+                    kPTDocumentScope, kPTJobScope, kPTPageScope, kUserDefaultDevmode,
+                    PTCloseProvider, PTConvertPrintTicketToDevMode, PTOpenProvider,
+                    PTReleaseMemory,
                 },
                 PRINTER_HANDLE,
             },
@@ -38,6 +50,7 @@ impl Default for PrintTicket {
     }
 }
 
+#[cfg(windows)]
 #[derive(Error, Debug)]
 /// Represents an error occurred while converting print ticket to dev mode.
 pub enum ToDevModeError {
@@ -76,10 +89,48 @@ impl PrintTicket {
         &self.xml
     }
 
+    /// Parse the XML of the print ticket as a document, which can be edited and converted back by [`PrintTicket::from_document`].
+    ///
+    /// The document is parsed in lossless mode, so the content which is not part of the model is written back unchanged.
+    pub fn document(&self) -> Result<PrintTicketDocument, ParsePrintSchemaError> {
+        PrintTicketDocument::parse_from_bytes_with_options(
+            &self.xml,
+            &ParseOptions::new().lossless(true),
+        )
+    }
+
+    /// Create a new print ticket from the given document.
+    ///
+    /// Unlike the [`From`] conversion, an error is returned if the document cannot be serialised.
+    pub fn from_document(document: &PrintTicketDocument) -> Result<Self, xml::writer::Error> {
+        Ok(Self {
+            xml: document.try_to_xml()?,
+        })
+    }
+}
+
+#[cfg(windows)]
+impl PrintTicket {
     /// Convert the print ticket to [`DEVMODE`] data.
     ///
     /// [`DEVMODE`]: https://learn.microsoft.com/en-us/windows/win32/api/wingdi/ns-wingdi-devmodew
     pub fn to_dev_mode(&self, device: &PrinterDevice) -> Result<Vec<u8>, ToDevModeError> {
+        self.to_dev_mode_for_scope(device, PrintScope::Job)
+    }
+
+    /// Convert the print ticket to [`DEVMODE`] data, only with the settings of the given scope and the narrower ones.
+    ///
+    /// [`DEVMODE`]: https://learn.microsoft.com/en-us/windows/win32/api/wingdi/ns-wingdi-devmodew
+    pub fn to_dev_mode_for_scope(
+        &self,
+        device: &PrinterDevice,
+        scope: PrintScope,
+    ) -> Result<Vec<u8>, ToDevModeError> {
+        let scope = match scope {
+            PrintScope::Job => kPTJobScope,
+            PrintScope::Document => kPTDocumentScope,
+            PrintScope::Page => kPTPageScope,
+        };
         unsafe {
             let provider =
                 PTOpenProvider(PCWSTR(wchar::to_wide_chars(device.os_name()).as_ptr()), 1)
@@ -98,7 +149,7 @@ impl PrintTicket {
                 provider,
                 &stream,
                 kUserDefaultDevmode,
-                kPTJobScope,
+                scope,
                 ptr::addr_of_mut!(dev_mode_size),
                 ptr::addr_of_mut!(dev_mode_data),
                 Some(&mut error_message),
@@ -166,3 +217,71 @@ impl From<PrintTicketDocument> for PrintTicket {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::PrintTicket;
+    use crate::ticket::document::{PrintFeatureOption, PropertyValue, NS_PSK};
+    use xml::name::OwnedName;
+
+    #[test]
+    fn edit_through_document() {
+        let ticket =
+            PrintTicket::from_xml(include_bytes!("../../test_data/print_ticket.xml").to_vec());
+        let mut document = ticket.document().unwrap();
+        let orientation = OwnedName::qualified("PageOrientation", NS_PSK, Some("psk"));
+        let landscape = PrintFeatureOption::new(
+            Some(OwnedName::qualified("Landscape", NS_PSK, Some("psk"))),
+            vec![],
+            vec![],
+        );
+        let copies = OwnedName::qualified("JobCopiesAllDocuments", NS_PSK, Some("psk"));
+        document.set_feature_option(orientation.clone(), landscape.clone());
+        document.set_parameter_init(copies.clone(), PropertyValue::Integer(2));
+
+        let edited = PrintTicket::from_document(&document)
+            .unwrap()
+            .document()
+            .unwrap();
+        assert_eq!(edited, document);
+        assert_eq!(edited.selected_option(&orientation), Some(&landscape));
+        assert_eq!(
+            edited.parameter_value(&copies),
+            Some(&PropertyValue::Integer(2))
+        );
+    }
+
+    #[test]
+    fn edit_keeps_vendor_content() {
+        // in the form written by the serialiser, so that an unedited document is written back as it is
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><!-- written by the vendor driver --><psf:PrintTicket xmlns:ns0000="http://vendor/private" xmlns:psf="http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework" xmlns:psk="http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords" version="1">
+    <ns0000:Private ns0000:mode="fast"><ns0000:Blob>AAEC</ns0000:Blob></ns0000:Private>
+    <psf:Feature name="psk:PageOrientation">
+        <!-- orientation -->
+        <psf:Option name="psk:Portrait" />
+    </psf:Feature>
+    <psf:Feature name="ns0000:Finishing" ns0000:tag="x">
+        <psf:Option name="ns0000:Glossy" />
+    </psf:Feature>
+</psf:PrintTicket>"#;
+        let ticket = PrintTicket::from_xml(xml);
+        let mut document = ticket.document().unwrap();
+        assert_eq!(
+            PrintTicket::from_document(&document).unwrap().get_xml(),
+            xml.as_bytes()
+        );
+        document.set_feature_option(
+            OwnedName::qualified("PageOrientation", NS_PSK, Some("psk")),
+            PrintFeatureOption::new(
+                Some(OwnedName::qualified("Landscape", NS_PSK, Some("psk"))),
+                vec![],
+                vec![],
+            ),
+        );
+        let edited = PrintTicket::from_document(&document).unwrap();
+        assert_eq!(
+            String::from_utf8(edited.into_xml()).unwrap(),
+            xml.replace("psk:Portrait", "psk:Landscape")
+        );
+    }
+}
@@ -0,0 +1,247 @@
+use super::{document::PrintTicketDocument, merge_print_tickets};
+use xml::name::OwnedName;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Represents the scope of a feature or a parameter, from the broadest to the narrowest.
+pub enum PrintScope {
+    /// Applies to the whole print job, shown by the `Job` keyword prefix.
+    Job,
+    /// Applies to one document of the job, shown by the `Document` keyword prefix.
+    Document,
+    /// Applies to one page of a document, shown by the `Page` keyword prefix.
+    Page,
+}
+
+impl PrintScope {
+    /// Classify the name of a feature or a parameter by its keyword prefix.
+    ///
+    /// The prefix convention is followed by the public keywords and by most private ones,
+    /// so the namespace is not checked. `None` is returned for names without any of the prefixes.
+    pub fn of(name: &OwnedName) -> Option<Self> {
+        let local_name = name.local_name.as_str();
+        [
+            ("Job", PrintScope::Job),
+            ("Document", PrintScope::Document),
+            ("Page", PrintScope::Page),
+        ]
+        .into_iter()
+        .find(|(prefix, _)| {
+            local_name
+                .strip_prefix(prefix)
+                .is_some_and(|x| x.starts_with(|c: char| !c.is_lowercase()))
+        })
+        .map(|(_, scope)| scope)
+    }
+
+    /// Check whether a ticket of this scope may contain a setting of the given scope.
+    ///
+    /// A ticket may contain the settings of its own scope and the narrower ones,
+    /// for example a document ticket may contain document and page settings.
+    pub fn contains(self, scope: PrintScope) -> bool {
+        self <= scope
+    }
+}
+
+impl PrintTicketDocument {
+    /// Keep only the features and parameter initializations of the given scope, see [`PrintScope::of`].
+    ///
+    /// Settings without a scope prefix, like most vendor ones, apply wherever they are given, so they are kept.
+    /// Properties of the document are kept.
+    pub fn filter_scope(&self, scope: PrintScope) -> PrintTicketDocument {
+        self.filter_scope_by(|x| x == scope)
+    }
+
+    /// Keep only the features and parameter initializations which a ticket of the given scope may contain,
+    /// see [`PrintScope::contains`].
+    ///
+    /// Settings without a scope prefix, like most vendor ones, apply wherever they are given, so they are kept.
+    /// Properties of the document are kept.
+    pub fn restrict_to_scope(&self, scope: PrintScope) -> PrintTicketDocument {
+        self.filter_scope_by(|x| scope.contains(x))
+    }
+
+    fn filter_scope_by(&self, predicate: impl Fn(PrintScope) -> bool) -> PrintTicketDocument {
+        let matches = |name: &OwnedName| PrintScope::of(name).is_none_or(&predicate);
+        PrintTicketDocument {
+            properties: self.properties.clone(),
+            parameter_inits: self
+                .parameter_inits
+                .iter()
+                .filter(|x| matches(&x.name))
+                .cloned()
+                .collect(),
+            features: self
+                .features
+                .iter()
+                .filter(|x| matches(&x.name))
+                .cloned()
+                .collect(),
+            extra: self.extra.clone(),
+        }
+    }
+}
+
+/// Resolve the effective print ticket of a page from the job, document and page tickets.
+///
+/// Following the inheritance rules of the Print Schema, each ticket only contributes the settings it may contain
+/// (see [`PrintTicketDocument::restrict_to_scope`]), and the settings of a narrower ticket take precedence
+/// over the ones of a broader ticket, as merged by [`merge_print_tickets`].
+/// Settings without a scope prefix are taken from every ticket, at the level of the ticket giving them.
+pub fn resolve_page_ticket(
+    job: &PrintTicketDocument,
+    document: Option<&PrintTicketDocument>,
+    page: Option<&PrintTicketDocument>,
+) -> PrintTicketDocument {
+    let mut result = job.restrict_to_scope(PrintScope::Job);
+    for (ticket, scope) in [(document, PrintScope::Document), (page, PrintScope::Page)] {
+        if let Some(ticket) = ticket {
+            result = merge_print_tickets(&result, &ticket.restrict_to_scope(scope));
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{resolve_page_ticket, PrintScope};
+    use crate::ticket::document::{reader::ParsableXmlDocument, PrintTicketDocument, NS_PSK};
+    use xml::name::OwnedName;
+
+    fn ticket(body: &str) -> PrintTicketDocument {
+        let xml = format!(
+            r#"<psf:PrintTicket version="1"
+    xmlns:psf="http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework"
+    xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+    xmlns:xsd="http://www.w3.org/2001/XMLSchema"
+    xmlns:psk="http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords">
+    {}
+</psf:PrintTicket>"#,
+            body
+        );
+        PrintTicketDocument::parse_from_bytes(xml).unwrap()
+    }
+
+    fn selected(ticket: &PrintTicketDocument, feature: &str) -> Option<String> {
+        ticket
+            .selected_option(&OwnedName::qualified(feature, NS_PSK, Some("psk")))
+            .and_then(|x| x.name.as_ref())
+            .map(|x| x.local_name.clone())
+    }
+
+    #[test]
+    fn classify_names() {
+        let psk = |x: &str| OwnedName::qualified(x, NS_PSK, Some("psk"));
+        assert_eq!(
+            PrintScope::of(&psk("JobCopiesAllDocuments")),
+            Some(PrintScope::Job)
+        );
+        assert_eq!(
+            PrintScope::of(&psk("DocumentCollate")),
+            Some(PrintScope::Document)
+        );
+        assert_eq!(
+            PrintScope::of(&psk("PageMediaSize")),
+            Some(PrintScope::Page)
+        );
+        assert_eq!(
+            PrintScope::of(&OwnedName::local("PageDevmodeSnapshot")),
+            Some(PrintScope::Page)
+        );
+        assert_eq!(PrintScope::of(&psk("Pages")), None);
+        assert_eq!(PrintScope::of(&psk("Jobless")), None);
+        assert_eq!(PrintScope::of(&psk("Copies")), None);
+        assert!(PrintScope::Document.contains(PrintScope::Page));
+        assert!(!PrintScope::Page.contains(PrintScope::Document));
+    }
+
+    #[test]
+    fn filter_by_scope() {
+        let ticket = ticket(
+            r#"<psf:ParameterInit name="psk:JobCopiesAllDocuments"><psf:Value xsi:type="xsd:integer">2</psf:Value></psf:ParameterInit>
+    <psf:Feature name="psk:JobDuplexAllDocumentsContiguously"><psf:Option name="psk:OneSided" /></psf:Feature>
+    <psf:Feature name="psk:DocumentCollate"><psf:Option name="psk:Collated" /></psf:Feature>
+    <psf:Feature name="psk:PageOrientation"><psf:Option name="psk:Portrait" /></psf:Feature>"#,
+        );
+        let page = ticket.filter_scope(PrintScope::Page);
+        assert_eq!(page.features.len(), 1);
+        assert!(page.parameter_inits.is_empty());
+        let job = ticket.filter_scope(PrintScope::Job);
+        assert_eq!(job.features.len(), 1);
+        assert_eq!(job.parameter_inits.len(), 1);
+        assert_eq!(
+            ticket
+                .restrict_to_scope(PrintScope::Document)
+                .features
+                .len(),
+            2
+        );
+        assert_eq!(ticket.restrict_to_scope(PrintScope::Job), ticket);
+    }
+
+    #[test]
+    fn resolve_effective_page_ticket() {
+        let job = ticket(
+            r#"<psf:Feature name="psk:JobDuplexAllDocumentsContiguously"><psf:Option name="psk:OneSided" /></psf:Feature>
+    <psf:Feature name="psk:PageOrientation"><psf:Option name="psk:Portrait" /></psf:Feature>
+    <psf:Feature name="psk:PageMediaSize"><psf:Option name="psk:ISOA4" /></psf:Feature>"#,
+        );
+        let document = ticket(
+            r#"<psf:Feature name="psk:JobDuplexAllDocumentsContiguously"><psf:Option name="psk:TwoSidedLongEdge" /></psf:Feature>
+    <psf:Feature name="psk:DocumentCollate"><psf:Option name="psk:Collated" /></psf:Feature>
+    <psf:Feature name="psk:PageMediaSize"><psf:Option name="psk:ISOA3" /></psf:Feature>"#,
+        );
+        let page = ticket(
+            r#"<psf:Feature name="psk:DocumentCollate"><psf:Option name="psk:Uncollated" /></psf:Feature>
+    <psf:Feature name="psk:PageOrientation"><psf:Option name="psk:Landscape" /></psf:Feature>"#,
+        );
+
+        let effective = resolve_page_ticket(&job, Some(&document), Some(&page));
+        assert_eq!(
+            selected(&effective, "JobDuplexAllDocumentsContiguously").as_deref(),
+            Some("OneSided")
+        );
+        assert_eq!(
+            selected(&effective, "DocumentCollate").as_deref(),
+            Some("Collated")
+        );
+        assert_eq!(
+            selected(&effective, "PageMediaSize").as_deref(),
+            Some("ISOA3")
+        );
+        assert_eq!(
+            selected(&effective, "PageOrientation").as_deref(),
+            Some("Landscape")
+        );
+
+        let effective = resolve_page_ticket(&job, None, None);
+        assert_eq!(effective, job);
+    }
+
+    #[test]
+    fn resolve_vendor_settings() {
+        let vendor = |body: &str| {
+            ticket(&format!(
+                r#"<psf:Feature name="ns0000:Watermark" xmlns:ns0000="http://vendor/private">{}</psf:Feature>"#,
+                body
+            ))
+        };
+        let job = vendor(r#"<psf:Option name="ns0000:Draft" />"#);
+        let document = vendor(r#"<psf:Option name="ns0000:Confidential" />"#);
+        let page = ticket(
+            r#"<psf:ParameterInit name="ns0000:WatermarkText" xmlns:ns0000="http://vendor/private"><psf:Value xsi:type="xsd:string">Copy</psf:Value></psf:ParameterInit>"#,
+        );
+        assert_eq!(document.filter_scope(PrintScope::Page), document);
+
+        let watermark = OwnedName::qualified("Watermark", "http://vendor/private", Some("ns0000"));
+        let effective = resolve_page_ticket(&job, Some(&document), Some(&page));
+        assert_eq!(
+            effective
+                .selected_option(&watermark)
+                .and_then(|x| x.name.as_ref())
+                .map(|x| x.local_name.as_str()),
+            Some("Confidential")
+        );
+        assert_eq!(effective.parameter_inits.len(), 1);
+        assert_eq!(effective.parameter_inits[0].value.string(), Some("Copy"));
+    }
+}
@@ -0,0 +1,79 @@
+use super::{define_feature_option_pack, FeatureKeyword, Keyword, PredefinedStaple};
+
+define_feature_option_pack!(
+    FeatureKeyword::JobStapleAllDocuments.name(),
+    JobStaple,
+    PredefinedStaple
+);
+define_feature_option_pack!(
+    FeatureKeyword::DocumentStaple.name(),
+    DocumentStaple,
+    PredefinedStaple
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::ticket::test_fixtures::capabilities_with;
+    use crate::ticket::{
+        document::{reader::ParsableXmlDocument, PrintTicketDocument},
+        FeatureOptionPack, FeatureOptionPackWithPredefined, JobStaple, PredefinedHolePunch,
+        PredefinedStaple, PrintCapabilities, PrintTicket,
+    };
+
+    fn capabilities() -> PrintCapabilities {
+        capabilities_with(
+            r#"    <psf:Feature name="psk:JobStapleAllDocuments">
+        <psf:Option name="psk:None" />
+        <psf:Option name="psk:StapleTopLeft" />
+        <psf:Option name="psk:SaddleStitch" />
+    </psf:Feature>
+    <psf:Feature name="psk:DocumentHolePunch">
+        <psf:Option name="psk:None" />
+        <psf:Option name="psk:LeftEdge" />
+    </psf:Feature>
+    <psf:Feature name="psk:JobOutputBin">
+        <psf:Option name="ns0000:Finisher" />
+    </psf:Feature>"#,
+        )
+    }
+
+    #[test]
+    fn list_finishing_options() {
+        let capabilities = capabilities();
+        assert_eq!(
+            capabilities
+                .job_staples()
+                .filter_map(|x| x.as_predefined_name())
+                .collect::<Vec<_>>(),
+            vec![
+                PredefinedStaple::None,
+                PredefinedStaple::StapleTopLeft,
+                PredefinedStaple::SaddleStitch
+            ]
+        );
+        assert_eq!(capabilities.document_staples().count(), 0);
+        assert_eq!(
+            capabilities
+                .document_hole_punches()
+                .filter_map(|x| x.as_predefined_name())
+                .collect::<Vec<_>>(),
+            vec![PredefinedHolePunch::None, PredefinedHolePunch::LeftEdge]
+        );
+        assert_eq!(capabilities.job_output_bins().count(), 1);
+    }
+
+    #[test]
+    fn staple_into_print_ticket() {
+        let staple = capabilities()
+            .job_staples()
+            .find(|x| x.as_predefined_name() == Some(PredefinedStaple::StapleTopLeft))
+            .unwrap();
+        let ticket = PrintTicket::from(staple);
+        let document = PrintTicketDocument::parse_from_bytes(ticket.xml).unwrap();
+        let feature = document.feature(&JobStaple::feature_name()).unwrap();
+        assert_eq!(
+            feature.options[0].name.as_ref().unwrap().local_name,
+            "StapleTopLeft"
+        );
+    }
+}
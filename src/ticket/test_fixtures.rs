@@ -0,0 +1,53 @@
+//! Documents shared by the unit tests.
+
+use super::{
+    document::{
+        reader::ParsableXmlDocument, PrintCapabilitiesDocument, PrintDeviceCapabilitiesDocument,
+        PrintTicketDocument,
+    },
+    PrintCapabilities,
+};
+
+/// The attributes of a print capabilities root element, with `ns0000` for vendor names.
+pub(crate) const NAMESPACES: &str = r#"version="1"
+    xmlns:psf="http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework"
+    xmlns:psk="http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords"
+    xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+    xmlns:xsd="http://www.w3.org/2001/XMLSchema"
+    xmlns:ns0000="http://vendor/private""#;
+
+/// The print capabilities document of `test_data/print_capabilities.xml`.
+pub(crate) fn sample_capabilities_document() -> PrintCapabilitiesDocument {
+    let xml = include_bytes!("../../test_data/print_capabilities.xml");
+    PrintCapabilitiesDocument::parse_from_bytes(xml).unwrap()
+}
+
+/// The print capabilities of `test_data/print_capabilities.xml`.
+pub(crate) fn sample_capabilities() -> PrintCapabilities {
+    PrintCapabilities {
+        document: sample_capabilities_document(),
+    }
+}
+
+/// The print device capabilities document of `test_data/print_device_capabilities.xml`.
+pub(crate) fn sample_device_capabilities_document() -> PrintDeviceCapabilitiesDocument {
+    let xml = include_bytes!("../../test_data/print_device_capabilities.xml");
+    PrintDeviceCapabilitiesDocument::parse_from_bytes(xml).unwrap()
+}
+
+/// The print ticket document of `test_data/print_ticket.xml`.
+pub(crate) fn sample_ticket_document() -> PrintTicketDocument {
+    let xml = include_bytes!("../../test_data/print_ticket.xml");
+    PrintTicketDocument::parse_from_bytes(xml).unwrap()
+}
+
+/// Parse the print capabilities with the given content of the root element, see [`NAMESPACES`].
+pub(crate) fn capabilities_with(body: &str) -> PrintCapabilities {
+    let xml = format!(
+        "<psf:PrintCapabilities {}>\n{}\n</psf:PrintCapabilities>",
+        NAMESPACES, body
+    );
+    PrintCapabilities {
+        document: PrintCapabilitiesDocument::parse_from_bytes(xml).unwrap(),
+    }
+}
@@ -0,0 +1,701 @@
+use super::{
+    document::{
+        diff::{fmt_name, fmt_optional_value},
+        same_name, ParameterDef, ParameterValueError, PrintCapabilitiesDocument, PrintFeature,
+        PrintFeatureOption, PrintTicketDocument, PropertyValue,
+    },
+    merge::{is_option_supported, is_pick_many},
+};
+use std::fmt;
+use xml::name::OwnedName;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Represents how serious a [`ValidationFinding`] is.
+pub enum Severity {
+    /// The document is usable, but a consumer may not interpret it as intended.
+    Warning,
+    /// The document violates the Print Schema Framework.
+    Error,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Represents a violation of the Print Schema Framework found by the validation.
+pub struct ValidationFinding {
+    /// How serious the finding is.
+    pub severity: Severity,
+    /// What is found.
+    pub kind: FindingKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Represents what is found by the validation.
+pub enum FindingKind {
+    /// The feature appears more than once in the same parent.
+    DuplicateFeature {
+        /// Names of the feature and its parent features, from the outermost one.
+        path: Vec<OwnedName>,
+    },
+    /// The option appears more than once in the feature.
+    DuplicateOption {
+        /// Names of the feature and its parent features, from the outermost one.
+        path: Vec<OwnedName>,
+        /// The name of the option.
+        option: OwnedName,
+    },
+    /// The parameter is initialized or defined more than once.
+    DuplicateParameter {
+        /// The name of the parameter.
+        name: OwnedName,
+    },
+    /// The feature of a print ticket does not select exactly one option.
+    OptionCount {
+        /// Names of the feature and its parent features, from the outermost one.
+        path: Vec<OwnedName>,
+        /// The number of selected options.
+        count: usize,
+    },
+    /// The feature is not supported by the device.
+    UnsupportedFeature {
+        /// Names of the feature and its parent features, from the outermost one.
+        path: Vec<OwnedName>,
+    },
+    /// The option is not supported by the device.
+    UnsupportedOption {
+        /// Names of the feature and its parent features, from the outermost one.
+        path: Vec<OwnedName>,
+        /// The requested option.
+        option: PrintFeatureOption,
+    },
+    /// A scored-property references a parameter which is not defined.
+    UndefinedParameterRef {
+        /// Names of the feature and its parent features, from the outermost one.
+        path: Vec<OwnedName>,
+        /// The referenced parameter.
+        parameter: OwnedName,
+    },
+    /// A scored-property of a print ticket references a parameter which is not initialized.
+    MissingParameterInit {
+        /// Names of the feature and its parent features, from the outermost one.
+        path: Vec<OwnedName>,
+        /// The referenced parameter.
+        parameter: OwnedName,
+    },
+    /// The parameter is initialized but not defined by the device.
+    UndefinedParameter {
+        /// The name of the parameter.
+        name: OwnedName,
+    },
+    /// The parameter definition lacks a required property.
+    IncompleteParameterDef {
+        /// The name of the parameter.
+        name: OwnedName,
+        /// The local name of the missing property.
+        property: &'static str,
+    },
+    /// The type of the parameter value differs from the `DataType` of the definition.
+    ParameterTypeMismatch {
+        /// The name of the parameter.
+        name: OwnedName,
+        /// The `DataType` of the definition.
+        expected: OwnedName,
+        /// The type of the value.
+        found: OwnedName,
+    },
+    /// The parameter value is kept as raw text, as it is invalid for its type.
+    InvalidParameterValue {
+        /// The name of the parameter.
+        name: OwnedName,
+        /// The invalid value.
+        value: PropertyValue,
+    },
+    /// The parameter value violates the range or length constraints of the definition.
+    ParameterOutOfRange {
+        /// The name of the parameter.
+        name: OwnedName,
+        /// The invalid value.
+        value: PropertyValue,
+    },
+}
+
+impl fmt::Display for ValidationFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{}: {}", severity, self.kind)
+    }
+}
+
+impl fmt::Display for FindingKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct Path<'a>(&'a [OwnedName]);
+        impl fmt::Display for Path<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                for (i, name) in self.0.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str("/")?;
+                    }
+                    fmt_name(name, f)?;
+                }
+                Ok(())
+            }
+        }
+        struct Name<'a>(Option<&'a OwnedName>);
+        impl fmt::Display for Name<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self.0 {
+                    Some(name) => fmt_name(name, f),
+                    None => f.write_str("<unnamed>"),
+                }
+            }
+        }
+        struct Value<'a>(&'a PropertyValue);
+        impl fmt::Display for Value<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt_optional_value(Some(self.0), f)
+            }
+        }
+
+        match self {
+            FindingKind::DuplicateFeature { path } => {
+                write!(f, "feature {} is duplicated", Path(path))
+            }
+            FindingKind::DuplicateOption { path, option } => {
+                write!(
+                    f,
+                    "option {} of feature {} is duplicated",
+                    Name(Some(option)),
+                    Path(path)
+                )
+            }
+            FindingKind::DuplicateParameter { name } => {
+                write!(f, "parameter {} is duplicated", Name(Some(name)))
+            }
+            FindingKind::OptionCount { path, count } => write!(
+                f,
+                "feature {} selects {} options instead of one",
+                Path(path),
+                count
+            ),
+            FindingKind::UnsupportedFeature { path } => {
+                write!(f, "feature {} is not supported", Path(path))
+            }
+            FindingKind::UnsupportedOption { path, option } => write!(
+                f,
+                "option {} of feature {} is not supported",
+                Name(option.name.as_ref()),
+                Path(path)
+            ),
+            FindingKind::UndefinedParameterRef { path, parameter } => write!(
+                f,
+                "feature {} references undefined parameter {}",
+                Path(path),
+                Name(Some(parameter))
+            ),
+            FindingKind::MissingParameterInit { path, parameter } => write!(
+                f,
+                "feature {} references uninitialized parameter {}",
+                Path(path),
+                Name(Some(parameter))
+            ),
+            FindingKind::UndefinedParameter { name } => {
+                write!(f, "parameter {} is not defined", Name(Some(name)))
+            }
+            FindingKind::IncompleteParameterDef { name, property } => {
+                write!(
+                    f,
+                    "parameter {} has no {} property",
+                    Name(Some(name)),
+                    property
+                )
+            }
+            FindingKind::ParameterTypeMismatch {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "parameter {} expects {} but found {}",
+                Name(Some(name)),
+                Name(Some(expected)),
+                Name(Some(found))
+            ),
+            FindingKind::InvalidParameterValue { name, value } => {
+                write!(
+                    f,
+                    "parameter {} has an invalid value: {}",
+                    Name(Some(name)),
+                    Value(value)
+                )
+            }
+            FindingKind::ParameterOutOfRange { name, value } => {
+                write!(
+                    f,
+                    "parameter {} is out of range: {}",
+                    Name(Some(name)),
+                    Value(value)
+                )
+            }
+        }
+    }
+}
+
+/// Check the print ticket against the rules of the Print Schema Framework.
+///
+/// Without print capabilities, only the structure of the ticket is checked:
+/// duplicated features and parameters, the number of selected options,
+/// and the parameters referenced by the selected options.
+pub fn validate_print_ticket(ticket: &PrintTicketDocument) -> Vec<ValidationFinding> {
+    let mut findings = vec![];
+    let mut validator = Validator {
+        ticket: Some(ticket),
+        capabilities: None,
+        findings: &mut findings,
+    };
+    validator.check_parameter_inits(ticket);
+    validator.check_ticket_features(&ticket.features, None, &mut vec![]);
+    findings
+}
+
+/// Check the print ticket against the rules of the Print Schema Framework and the given print capabilities.
+///
+/// In addition to [`validate_print_ticket`], features, options and parameters are checked
+/// against the ones supported by the device, and parameter values against their definitions.
+/// The print capabilities themselves are checked by [`validate_print_capabilities`].
+pub fn validate_print_ticket_against(
+    ticket: &PrintTicketDocument,
+    capabilities: &PrintCapabilitiesDocument,
+) -> Vec<ValidationFinding> {
+    let mut findings = vec![];
+    let mut validator = Validator {
+        ticket: Some(ticket),
+        capabilities: Some(capabilities),
+        findings: &mut findings,
+    };
+    validator.check_parameter_inits(ticket);
+    validator.check_ticket_features(&ticket.features, Some(&capabilities.features), &mut vec![]);
+    findings
+}
+
+/// Check the print capabilities against the rules of the Print Schema Framework.
+///
+/// Duplicated features, options and parameters, incomplete parameter definitions,
+/// default values violating their definitions, and references to undefined parameters are reported.
+pub fn validate_print_capabilities(
+    capabilities: &PrintCapabilitiesDocument,
+) -> Vec<ValidationFinding> {
+    let mut findings = vec![];
+    let mut validator = Validator {
+        ticket: None,
+        capabilities: Some(capabilities),
+        findings: &mut findings,
+    };
+    validator.check_parameter_defs(capabilities);
+    validator.check_capability_features(&capabilities.features, &mut vec![]);
+    findings
+}
+
+struct Validator<'a> {
+    ticket: Option<&'a PrintTicketDocument>,
+    capabilities: Option<&'a PrintCapabilitiesDocument>,
+    findings: &'a mut Vec<ValidationFinding>,
+}
+
+impl Validator<'_> {
+    fn report(&mut self, severity: Severity, kind: FindingKind) {
+        self.findings.push(ValidationFinding { severity, kind });
+    }
+
+    fn parameter_def(&self, name: &OwnedName) -> Option<&ParameterDef> {
+        self.capabilities?
+            .parameter_defs
+            .iter()
+            .find(|x| same_name(&x.name, name))
+    }
+
+    fn check_parameter_inits(&mut self, ticket: &PrintTicketDocument) {
+        for (i, parameter_init) in ticket.parameter_inits.iter().enumerate() {
+            if ticket.parameter_inits[..i]
+                .iter()
+                .any(|x| same_name(&x.name, &parameter_init.name))
+            {
+                self.report(
+                    Severity::Error,
+                    FindingKind::DuplicateParameter {
+                        name: parameter_init.name.clone(),
+                    },
+                );
+                continue;
+            }
+            if self.capabilities.is_none() {
+                continue;
+            }
+            match self.parameter_def(&parameter_init.name) {
+                Some(def) => {
+                    if let Some(kind) = check_parameter_value(def, &parameter_init.value) {
+                        self.report(Severity::Error, kind);
+                    }
+                }
+                None => self.report(
+                    Severity::Warning,
+                    FindingKind::UndefinedParameter {
+                        name: parameter_init.name.clone(),
+                    },
+                ),
+            }
+        }
+    }
+
+    fn check_parameter_defs(&mut self, capabilities: &PrintCapabilitiesDocument) {
+        for (i, def) in capabilities.parameter_defs.iter().enumerate() {
+            if capabilities.parameter_defs[..i]
+                .iter()
+                .any(|x| same_name(&x.name, &def.name))
+            {
+                self.report(
+                    Severity::Error,
+                    FindingKind::DuplicateParameter {
+                        name: def.name.clone(),
+                    },
+                );
+                continue;
+            }
+            if def.definition().data_type.is_none() {
+                self.report(
+                    Severity::Error,
+                    FindingKind::IncompleteParameterDef {
+                        name: def.name.clone(),
+                        property: "DataType",
+                    },
+                );
+            }
+            if let Some(kind) = def
+                .default_value()
+                .and_then(|x| check_parameter_value(def, x))
+            {
+                self.report(Severity::Error, kind);
+            }
+        }
+    }
+
+    fn check_ticket_features(
+        &mut self,
+        features: &[PrintFeature],
+        capabilities: Option<&[PrintFeature]>,
+        path: &mut Vec<OwnedName>,
+    ) {
+        for (i, feature) in features.iter().enumerate() {
+            path.push(feature.name.clone());
+            if features[..i]
+                .iter()
+                .any(|x| same_name(&x.name, &feature.name))
+            {
+                self.report(
+                    Severity::Error,
+                    FindingKind::DuplicateFeature { path: path.clone() },
+                );
+            }
+            let capability =
+                capabilities.map(|x| x.iter().find(|x| same_name(&x.name, &feature.name)));
+            match capability {
+                Some(None) => {
+                    self.report(
+                        Severity::Warning,
+                        FindingKind::UnsupportedFeature { path: path.clone() },
+                    );
+                }
+                Some(Some(capability)) => {
+                    self.check_ticket_options(feature, Some(capability), path);
+                    self.check_ticket_features(&feature.features, Some(&capability.features), path);
+                }
+                None => {
+                    self.check_ticket_options(feature, None, path);
+                    self.check_ticket_features(&feature.features, None, path);
+                }
+            }
+            path.pop();
+        }
+    }
+
+    fn check_ticket_options(
+        &mut self,
+        feature: &PrintFeature,
+        capability: Option<&PrintFeature>,
+        path: &[OwnedName],
+    ) {
+        let count = feature.options.len();
+        let pick_many = capability.is_some_and(is_pick_many);
+        // a feature without options may only group sub-features
+        let severity = match count {
+            0 if feature.features.is_empty() => Some(Severity::Error),
+            n if n > 1 && !pick_many && capability.is_some() => Some(Severity::Error),
+            n if n > 1 && !pick_many => Some(Severity::Warning),
+            _ => None,
+        };
+        if let Some(severity) = severity {
+            self.report(
+                severity,
+                FindingKind::OptionCount {
+                    path: path.to_vec(),
+                    count,
+                },
+            );
+        }
+        self.check_duplicate_options(&feature.options, path);
+
+        for option in &feature.options {
+            if let Some(capability) = capability {
+                if !is_option_supported(option, capability) {
+                    self.report(
+                        Severity::Error,
+                        FindingKind::UnsupportedOption {
+                            path: path.to_vec(),
+                            option: option.clone(),
+                        },
+                    );
+                }
+            }
+            for parameter in option.parameters_dependent() {
+                self.check_parameter_ref(parameter, path);
+            }
+        }
+    }
+
+    fn check_capability_features(&mut self, features: &[PrintFeature], path: &mut Vec<OwnedName>) {
+        for (i, feature) in features.iter().enumerate() {
+            path.push(feature.name.clone());
+            if features[..i]
+                .iter()
+                .any(|x| same_name(&x.name, &feature.name))
+            {
+                self.report(
+                    Severity::Error,
+                    FindingKind::DuplicateFeature { path: path.clone() },
+                );
+            }
+            self.check_duplicate_options(&feature.options, path);
+            for option in &feature.options {
+                for parameter in option.parameters_dependent() {
+                    self.check_parameter_ref(parameter, path);
+                }
+            }
+            self.check_capability_features(&feature.features, path);
+            path.pop();
+        }
+    }
+
+    fn check_duplicate_options(&mut self, options: &[PrintFeatureOption], path: &[OwnedName]) {
+        for (i, option) in options.iter().enumerate() {
+            let Some(name) = &option.name else {
+                continue;
+            };
+            if options[..i]
+                .iter()
+                .filter_map(|x| x.name.as_ref())
+                .any(|x| same_name(x, name))
+            {
+                self.report(
+                    Severity::Error,
+                    FindingKind::DuplicateOption {
+                        path: path.to_vec(),
+                        option: name.clone(),
+                    },
+                );
+            }
+        }
+    }
+
+    fn check_parameter_ref(&mut self, parameter: OwnedName, path: &[OwnedName]) {
+        if self.capabilities.is_some() && self.parameter_def(&parameter).is_none() {
+            self.report(
+                Severity::Error,
+                FindingKind::UndefinedParameterRef {
+                    path: path.to_vec(),
+                    parameter,
+                },
+            );
+            return;
+        }
+        let Some(ticket) = self.ticket else {
+            return;
+        };
+        if !ticket
+            .parameter_inits
+            .iter()
+            .any(|x| same_name(&x.name, &parameter))
+        {
+            self.report(
+                Severity::Warning,
+                FindingKind::MissingParameterInit {
+                    path: path.to_vec(),
+                    parameter,
+                },
+            );
+        }
+    }
+}
+
+/// Check the value against the `DataType` and the constraints of the parameter definition.
+fn check_parameter_value(def: &ParameterDef, value: &PropertyValue) -> Option<FindingKind> {
+    let definition = def.definition();
+    // the value cannot be checked without knowing its type
+    definition.data_type.as_ref()?;
+    match definition.check_value(value).err()? {
+        ParameterValueError::TypeMismatch { expected, found } => {
+            Some(FindingKind::ParameterTypeMismatch {
+                name: def.name.clone(),
+                expected: *expected,
+                found: *found,
+            })
+        }
+        ParameterValueError::InvalidValue(_) => Some(FindingKind::InvalidParameterValue {
+            name: def.name.clone(),
+            value: value.clone(),
+        }),
+        _ => Some(FindingKind::ParameterOutOfRange {
+            name: def.name.clone(),
+            value: value.clone(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        validate_print_capabilities, validate_print_ticket, validate_print_ticket_against,
+        FindingKind, Severity, ValidationFinding,
+    };
+    use crate::ticket::document::{
+        reader::ParsableXmlDocument, PrintCapabilitiesDocument, PrintTicketDocument,
+    };
+    use crate::ticket::test_fixtures::{sample_capabilities_document, sample_ticket_document};
+
+    const NAMESPACES: &str = r#"version="1"
+    xmlns:psf="http://schemas.microsoft.com/windows/2003/08/printing/printschemaframework"
+    xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+    xmlns:xsd="http://www.w3.org/2001/XMLSchema"
+    xmlns:psk="http://schemas.microsoft.com/windows/2003/08/printing/printschemakeywords"
+    xmlns:ns0000="http://schemas.microsoft.com/windows/printing/oemdriverpt/Generic___Text_Only_10_0_22621_3235_""#;
+
+    fn summary(findings: &[ValidationFinding]) -> Vec<(Severity, String)> {
+        findings
+            .iter()
+            .map(|x| {
+                let kind = format!("{:?}", x.kind);
+                let kind = kind.split([' ', '{']).next().unwrap().to_string();
+                (x.severity, kind)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn valid_documents() {
+        let capabilities = sample_capabilities_document();
+        let ticket = sample_ticket_document();
+        assert_eq!(validate_print_capabilities(&capabilities), vec![]);
+        assert_eq!(validate_print_ticket(&ticket), vec![]);
+        assert_eq!(
+            validate_print_ticket_against(&ticket, &capabilities),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn invalid_print_ticket() {
+        let xml = format!(
+            r#"<psf:PrintTicket {}>
+    <psf:ParameterInit name="psk:JobCopiesAllDocuments"><psf:Value xsi:type="xsd:string">2</psf:Value></psf:ParameterInit>
+    <psf:ParameterInit name="psk:JobCopiesAllDocuments"><psf:Value xsi:type="xsd:integer">2</psf:Value></psf:ParameterInit>
+    <psf:ParameterInit name="psk:PageMediaSizeMediaSizeWidth"><psf:Value xsi:type="xsd:integer">10</psf:Value></psf:ParameterInit>
+    <psf:Feature name="psk:PageOrientation"><psf:Option name="psk:Portrait" /><psf:Option name="psk:Landscape" /></psf:Feature>
+    <psf:Feature name="psk:DocumentCollate" />
+    <psf:Feature name="psk:PageMediaSize">
+        <psf:Option name="psk:CustomMediaSize">
+            <psf:ScoredProperty name="psk:MediaSizeWidth"><psf:ParameterRef name="psk:PageMediaSizeMediaSizeWidth" /></psf:ScoredProperty>
+            <psf:ScoredProperty name="psk:MediaSizeHeight"><psf:ParameterRef name="psk:PageMediaSizeMediaSizeHeight" /></psf:ScoredProperty>
+        </psf:Option>
+    </psf:Feature>
+    <psf:Feature name="ns0000:NotExisting"><psf:Option name="ns0000:Value" /></psf:Feature>
+</psf:PrintTicket>"#,
+            NAMESPACES
+        );
+        let ticket = PrintTicketDocument::parse_from_bytes(xml).unwrap();
+
+        let findings = validate_print_ticket(&ticket);
+        assert_eq!(
+            summary(&findings),
+            [
+                (Severity::Error, "DuplicateParameter".to_string()),
+                (Severity::Warning, "OptionCount".to_string()),
+                (Severity::Error, "OptionCount".to_string()),
+                (Severity::Warning, "MissingParameterInit".to_string()),
+            ]
+        );
+        assert_eq!(
+            findings[1].to_string(),
+            "warning: feature psk:PageOrientation selects 2 options instead of one"
+        );
+
+        let findings = validate_print_ticket_against(&ticket, &sample_capabilities_document());
+        assert_eq!(
+            summary(&findings),
+            [
+                (Severity::Error, "ParameterTypeMismatch".to_string()),
+                (Severity::Error, "DuplicateParameter".to_string()),
+                (Severity::Error, "ParameterOutOfRange".to_string()),
+                (Severity::Error, "OptionCount".to_string()),
+                (Severity::Error, "OptionCount".to_string()),
+                (Severity::Warning, "MissingParameterInit".to_string()),
+                (Severity::Warning, "UnsupportedFeature".to_string()),
+            ]
+        );
+        assert_eq!(
+            findings[0].to_string(),
+            "error: parameter psk:JobCopiesAllDocuments expects xsd:integer but found xsd:string"
+        );
+        assert!(findings[2].to_string().starts_with("error: parameter psk:"));
+        assert!(!findings[2].to_string().contains("Integer("));
+        assert!(matches!(
+            &findings[0].kind,
+            FindingKind::ParameterTypeMismatch { expected, found, .. }
+                if expected.local_name == "integer" && found.local_name == "string"
+        ));
+    }
+
+    #[test]
+    fn invalid_print_capabilities() {
+        let xml = format!(
+            r#"<psf:PrintCapabilities {}>
+    <psf:ParameterDef name="psk:JobCopiesAllDocuments">
+        <psf:Property name="psf:DataType"><psf:Value xsi:type="xsd:QName">xsd:integer</psf:Value></psf:Property>
+        <psf:Property name="psf:MinValue"><psf:Value xsi:type="xsd:integer">1</psf:Value></psf:Property>
+        <psf:Property name="psf:DefaultValue"><psf:Value xsi:type="xsd:integer">0</psf:Value></psf:Property>
+    </psf:ParameterDef>
+    <psf:ParameterDef name="psk:JobCopiesAllDocuments" />
+    <psf:ParameterDef name="psk:PageMediaSizeMediaSizeWidth" />
+    <psf:Feature name="psk:PageMediaSize">
+        <psf:Option name="psk:CustomMediaSize">
+            <psf:ScoredProperty name="psk:MediaSizeWidth"><psf:ParameterRef name="psk:PageMediaSizeMediaSizeWidth" /></psf:ScoredProperty>
+            <psf:ScoredProperty name="psk:MediaSizeHeight"><psf:ParameterRef name="psk:PageMediaSizeMediaSizeHeight" /></psf:ScoredProperty>
+        </psf:Option>
+        <psf:Option name="psk:CustomMediaSize" />
+    </psf:Feature>
+    <psf:Feature name="psk:PageMediaSize" />
+</psf:PrintCapabilities>"#,
+            NAMESPACES
+        );
+        let capabilities = PrintCapabilitiesDocument::parse_from_bytes(xml).unwrap();
+        assert_eq!(
+            summary(&validate_print_capabilities(&capabilities)),
+            [
+                (Severity::Error, "ParameterOutOfRange".to_string()),
+                (Severity::Error, "DuplicateParameter".to_string()),
+                (Severity::Error, "IncompleteParameterDef".to_string()),
+                (Severity::Error, "DuplicateOption".to_string()),
+                (Severity::Error, "UndefinedParameterRef".to_string()),
+                (Severity::Error, "DuplicateFeature".to_string()),
+            ]
+        );
+    }
+}